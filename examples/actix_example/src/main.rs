@@ -3,7 +3,7 @@ mod services;
 use crate::services::{ActixService, Repository};
 use actix_web::{web, App, HttpServer};
 use std::sync::Arc;
-use teloc::{DiActixHandler, ServiceProvider};
+use teloc::{DiActixHandler, Resolver, ServiceProvider};
 
 async fn index(service: ActixService<'_>, data: String) -> String {
     service.change_and_get_previous(data).await
@@ -28,16 +28,17 @@ async fn main() -> std::io::Result<()> {
         App::new().route(
             "/",
             web::post().to(
-                // `DIActixHandler` gives as input a `ServiceProvider` and a handler function and inject
-                // dependencies from the start args in function.
+                // `DIActixHandler` gives as input a `ServiceProvider` and a handler function, that gets
+                // the request scope to resolve dependencies from and the extractors.
                 DiActixHandler::new(
                     // Global `ServiceProvider`.
                     sp.clone(),
                     // Scope factory that can add scope instances that will be the same between different
                     // dependencies in one scope.
                     |s| s,
-                    // Function that will be called for each `HttpRequest`.
-                    index,
+                    // Function that will be called for each `HttpRequest`. The returned future can
+                    // borrow dependencies from the scope.
+                    |scope, data| Box::pin(index(scope.resolve(), data)),
                 ),
             ),
         )
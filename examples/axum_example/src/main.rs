@@ -0,0 +1,43 @@
+mod services;
+
+use crate::services::{AxumService, Repository};
+use axum::{routing::post, Router};
+use std::sync::Arc;
+use teloc::axum_support::{Inject, ProvideLayer};
+use teloc::{Resolver, ServiceProvider};
+
+async fn index(service: Inject<AxumService>, data: String) -> String {
+    service.change_and_get_previous(data).await
+}
+
+// For tests you can use curl:
+// ```
+// curl --header "Content-Type: application/json" --data '{"some":"json"}' "127.0.0.1:8080"
+// ```
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    // Create the `ServiceProvider` struct that store itself all dependencies.
+    let sp = ServiceProvider::new()
+        // Add dependency with a `Singleton` lifetime. More about lifetimes see in README.md.
+        .add_singleton::<Arc<Repository>>()
+        // Add dependency with a `Transient` lifetime. More about lifetimes see in README.md.
+        .add_transient::<AxumService>();
+    // We need to wrap Arc around `ServiceProvider` for thread-safety and cloning.
+    let sp = Arc::new(sp);
+
+    let app = Router::new().route("/", post(index)).layer(
+        // `ProvideLayer` forks a scope from the `ServiceProvider` for each request.
+        ProvideLayer::new(
+            // Global `ServiceProvider`.
+            sp,
+            // Scope factory that can add scope instances that will be the same between different
+            // dependencies in one scope.
+            |scope, parts| scope.add_instance(parts.method.clone()),
+        )
+        // Every type extracted with `Inject` must be registered.
+        .inject::<AxumService, _>(|scope| scope.resolve()),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,53 @@
+use axum::http::Method;
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
+
+// Repository stores previous request.
+pub struct Repository {
+    data: Mutex<String>,
+}
+
+// #[inject] macro allow to use `Repository` in `ServiceProvider`
+#[teloc::inject]
+impl Repository {
+    pub fn new() -> Self {
+        Repository {
+            data: Mutex::new(String::new()),
+        }
+    }
+}
+
+impl Repository {
+    pub async fn change(&self, new_data: String) {
+        *self.data.lock().await = new_data;
+    }
+
+    pub async fn get(&self) -> MutexGuard<'_, String> {
+        self.data.lock().await
+    }
+}
+
+// Service that handles requests.
+pub struct AxumService {
+    store: Arc<Repository>,
+    method: Method,
+}
+
+// #[inject] macro allow to use `AxumService` in `ServiceProvider`
+#[teloc::inject]
+impl AxumService {
+    pub fn new(store: Arc<Repository>, method: Method) -> Self {
+        Self { store, method }
+    }
+}
+
+impl AxumService {
+    pub async fn change_and_get_previous(&self, new_data: String) -> String {
+        let previous = self.store.get().await.clone();
+        self.store.change(new_data).await;
+        format!(
+            "Request Method: {}\nPrevious request body: {}\n",
+            self.method, previous
+        )
+    }
+}
@@ -1,26 +1,41 @@
 //! Support for `actix-web` crate.
-#![allow(unsafe_code)]
 
 use crate::container::InstanceContainer;
 use crate::dependency::DependencyClone;
 use crate::service_provider::SelectContainer;
 use crate::{Resolver, ServiceProvider};
 use actix_web::dev::*;
-use actix_web::web::Data;
+use actix_web::guard::Guard;
+use actix_web::web::{Data, ServiceConfig};
 use actix_web::Responder;
-use actix_web::{http, FromRequest, HttpRequest};
+use actix_web::{http, FromRequest, HttpRequest, HttpResponse};
 use frunk::{HCons, HNil};
-use std::cell::Ref;
+use std::cell::{Ref, RefCell};
 use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
-use std::ptr::NonNull;
+use std::rc::Rc;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Future of a handler of `DiActixHandler`, that can borrow the request scope for `'a`.
+pub type LocalBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Same as `LocalBoxFuture`, but `Send`. Returned by handlers of `DiActixHandler::new_send`.
+pub type SendBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 /// Struct for inject dependencies from `ServiceProvider` to an actix-web handler function. Works only with
 /// [actix_web::Resource](https://docs.rs/actix-web/3.3.2/actix_web/struct.Resource.html) service.
 ///
-/// **IMPORTANT:** dependencies from the `ServiceProvider` must be first in the list of arguments.
+/// The handler gets the request scope and the extractors (any `FromRequest` type, a tuple for
+/// several ones, or `()`), and returns a boxed future, that can borrow services from the scope.
+/// Usually it calls an `async fn` with arguments resolved from the scope:
+/// `|scope, body: String| Box::pin(index(scope.resolve(), body))`. The closure passes the
+/// arguments itself, so services and extractors can be in any order, and the number of services is
+/// not limited. actix-web implements `FromRequest` for tuples of up to 10 extractors, nest tuples
+/// to take more. The result of the handler cannot borrow the scope. The scope is dropped when the
+/// handler future completes, to keep it alive for a streaming response move a `ScopeGuard` into
+/// the response body.
 ///
 /// For example you can see [example in git repo](https://github.com/p0lunin/teloc/tree/master/examples/actix_example).
 ///
@@ -40,15 +55,78 @@ use std::sync::Arc;
 ///
 /// If you want to get something from the `HttpRequest` by a reference you must got one of types from
 /// the list above or `&HttpRequest` (the reference is important due to Rust lifetime checks).
-pub struct DiActixHandler<SP, ScopeFactory, F, ScopeResult, Args, Infers> {
+///
+/// Usage:
+/// ```
+/// use actix_web::{web, App, HttpRequest};
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Greeter;
+/// #[inject]
+/// impl Greeter {
+///     fn new() -> Self { Greeter }
+/// }
+///
+/// async fn index(greeter: &Greeter, req: &HttpRequest, name: web::Path<String>) -> String {
+///     format!("hello {} from {}", name, req.path())
+/// }
+///
+/// let sp = Arc::new(ServiceProvider::new().add_singleton::<Greeter>());
+/// let app = App::new().route(
+///     "/{name}",
+///     web::get().to(DiActixHandler::new(sp, |s| s, |scope, name| {
+///         Box::pin(index(scope.resolve(), scope.resolve(), name))
+///     })),
+/// );
+/// ```
+///
+/// The result cannot borrow the scope:
+/// ```compile_fail
+/// use actix_web::{web, App, HttpRequest};
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// let sp = Arc::new(ServiceProvider::new());
+/// let app = App::new().route(
+///     "/",
+///     web::get().to(DiActixHandler::new(sp, |s| s, |scope, ()| {
+///         Box::pin(async move { Resolver::<&HttpRequest, _>::resolve(&**scope).path() })
+///     })),
+/// );
+/// ```
+///
+/// ## Migrating from handler functions
+/// Earlier versions took the handler function itself, `DiActixHandler::new(sp, |s| s, index)`,
+/// and resolved its arguments by their types. Services borrowed from the scope need a lifetime
+/// that a generic implementation can not name except `'static`, and a `'static` reference to the
+/// scope can not be created without `unsafe`, so wrap the function into a closure instead:
+/// `DiActixHandler::new(sp, |s| s, |scope, body| Box::pin(index(scope.resolve(), body)))`.
+/// Extractors are passed in one argument, a tuple for several ones, and `ScopeGuard::new(scope)`
+/// replaces the `ScopeGuard` argument.
+///
+/// ## `Send` futures
+/// Futures returned by the handler created with `DiActixHandler::new` are not `Send`. Use
+/// `DiActixHandler::new_send` to get `Send` futures, so handler can be spawned on a multi-threaded
+/// executor. It requires the scope, the handler future and extractors to be `Send` (and the scope
+/// to be `Sync`), so the `HttpRequest` is not added to the scope, and types from the list above
+/// can not be resolved: take them as extractors. The scope is shared by `Arc` instead of `Rc`, and
+/// `SendScopeGuard` is used instead of `ScopeGuard`.
+pub struct DiActixHandler<SP, ScopeFactory, F, Mode = LocalFuture> {
     sp: Arc<SP>,
     scope_factory: ScopeFactory,
-    f: F,
-    phantom: PhantomData<(ScopeResult, Args, Infers)>,
+    f: Arc<F>,
+    mode: PhantomData<Mode>,
 }
 
-impl<ParSP, DepsSP, ScopeFactory, F, ScopeResult, Args, Infers>
-    DiActixHandler<ServiceProvider<ParSP, DepsSP>, ScopeFactory, F, ScopeResult, Args, Infers>
+/// Mode of `DiActixHandler` that returns non-`Send` futures.
+pub struct LocalFuture;
+
+/// Mode of `DiActixHandler` that returns `Send` futures.
+pub struct SendFuture;
+
+impl<ParSP, DepsSP, ScopeFactory, ScopeResult, F>
+    DiActixHandler<ServiceProvider<ParSP, DepsSP>, ScopeFactory, F>
 where
     ScopeFactory: Fn(
             ServiceProvider<
@@ -63,168 +141,298 @@ where
     ///
     /// - `ServiceProvider` is the global provider that can be used between different routes.
     /// - Scope factory is a function that get local scope and can add some local dependencies that
-    /// will be unique in different requests.
+    ///   will be unique in different requests.
     /// - handler function is a function that must be called when new `HttpRequest` incoming.
-    pub fn new(sp: Arc<ServiceProvider<ParSP, DepsSP>>, scope_factory: ScopeFactory, f: F) -> Self {
+    pub fn new<Args, Res>(
+        sp: Arc<ServiceProvider<ParSP, DepsSP>>,
+        scope_factory: ScopeFactory,
+        f: F,
+    ) -> Self
+    where
+        F: for<'s> Fn(&'s Rc<ScopeResult>, Args) -> LocalBoxFuture<'s, Res> + 'static,
+    {
         DiActixHandler {
             sp,
             scope_factory,
-            f,
-            phantom: PhantomData,
+            f: Arc::new(f),
+            mode: PhantomData,
         }
     }
 }
 
-impl<SP, ScopeFactory, F, ScopeResult, Args, Infers> Clone
-    for DiActixHandler<SP, ScopeFactory, F, ScopeResult, Args, Infers>
+impl<ParSP, DepsSP, ScopeFactory, ScopeResult, F>
+    DiActixHandler<ServiceProvider<ParSP, DepsSP>, ScopeFactory, F, SendFuture>
 where
-    ScopeFactory: Clone,
-    F: Clone,
+    ScopeFactory: Fn(ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HNil>) -> ScopeResult
+        + Clone
+        + 'static,
+    ScopeResult: Send + Sync,
 {
+    /// Same as `DiActixHandler::new`, but the handler returns `Send` futures. Scope factory gets the
+    /// scope without `HttpRequest`.
+    pub fn new_send<Args, Res>(
+        sp: Arc<ServiceProvider<ParSP, DepsSP>>,
+        scope_factory: ScopeFactory,
+        f: F,
+    ) -> Self
+    where
+        F: for<'s> Fn(&'s Arc<ScopeResult>, Args) -> SendBoxFuture<'s, Res> + Send + Sync + 'static,
+    {
+        DiActixHandler {
+            sp,
+            scope_factory,
+            f: Arc::new(f),
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<SP, ScopeFactory: Clone, F, Mode> Clone for DiActixHandler<SP, ScopeFactory, F, Mode> {
     fn clone(&self) -> Self {
         Self {
             sp: self.sp.clone(),
             scope_factory: self.scope_factory.clone(),
             f: self.f.clone(),
-            phantom: PhantomData,
+            mode: PhantomData,
         }
     }
 }
 
-// Safety was checked in https://play.rust-lang.org/?version=nightly&mode=debug&edition=2018&gist=118c918dcf33f7fd15faec185e3bcc4b
-// by miri
-#[pin_project::pin_project(PinnedDrop)]
-pub struct SpFuture<SP, Fut> {
-    sp: *mut SP,
-    #[pin]
-    fut: NonNull<Fut>,
+impl<ParSP, DepsSP, ScopeFactory, ScopeResult, F, Args, Res>
+    Factory<(HttpRequest, Args), LocalBoxFuture<'static, Res>, Res>
+    for DiActixHandler<ServiceProvider<ParSP, DepsSP>, ScopeFactory, F>
+where
+    ParSP: 'static,
+    DepsSP: 'static,
+    ScopeFactory: Fn(
+            ServiceProvider<
+                Arc<ServiceProvider<ParSP, DepsSP>>,
+                HCons<InstanceContainer<HttpRequest>, HNil>,
+            >,
+        ) -> ScopeResult
+        + Clone
+        + 'static,
+    ScopeResult: 'static,
+    F: for<'s> Fn(&'s Rc<ScopeResult>, Args) -> LocalBoxFuture<'s, Res> + 'static,
+    Args: 'static,
+    Res: Responder,
+{
+    fn call(&self, (req, args): (HttpRequest, Args)) -> LocalBoxFuture<'static, Res> {
+        let scope = Rc::new((self.scope_factory)(self.sp.fork_arc().add_instance(req)));
+        let f = self.f.clone();
+        Box::pin(async move { f(&scope, args).await })
+    }
 }
 
-impl<SP, Fut> SpFuture<SP, Fut> {
-    pub fn new(sp: *mut SP, f: impl FnOnce(*const SP) -> Fut) -> Pin<Box<Self>> {
-        let mut this = Box::pin(SpFuture {
-            sp,
-            fut: NonNull::dangling(),
-        });
-        let fut = Box::leak(Box::new(f(this.sp)));
-        unsafe {
-            let mut_ref: Pin<&mut Self> = this.as_mut();
-            Pin::get_unchecked_mut(mut_ref).fut = NonNull::from(fut);
+impl<ParSP, DepsSP, ScopeFactory, ScopeResult, F, Args, Res>
+    Factory<(HttpRequest, Args), SendBoxFuture<'static, Res>, Res>
+    for DiActixHandler<ServiceProvider<ParSP, DepsSP>, ScopeFactory, F, SendFuture>
+where
+    ParSP: 'static,
+    DepsSP: 'static,
+    ScopeFactory: Fn(ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HNil>) -> ScopeResult
+        + Clone
+        + 'static,
+    ScopeResult: Send + Sync + 'static,
+    F: for<'s> Fn(&'s Arc<ScopeResult>, Args) -> SendBoxFuture<'s, Res> + Send + Sync + 'static,
+    Args: Send + 'static,
+    Res: Responder,
+{
+    fn call(&self, (_, args): (HttpRequest, Args)) -> SendBoxFuture<'static, Res> {
+        let scope = Arc::new((self.scope_factory)(self.sp.fork_arc()));
+        let f = self.f.clone();
+        Box::pin(async move { f(&scope, args).await })
+    }
+}
+
+/// Keeps the request scope of `DiActixHandler` alive. Create it from the scope in the handler
+/// closure and move it into a streaming response body, so the scope (and services resolved from
+/// it) will be dropped when the stream completes rather than when the handler future completes.
+///
+/// Usage:
+/// ```
+/// use actix_web::{web, HttpResponse};
+/// use futures::stream;
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// async fn handler(words: web::Json<Vec<String>>, guard: ScopeGuard) -> HttpResponse {
+///     let chunks = words.into_inner().into_iter().map(move |word| {
+///         let _keep_scope = &guard;
+///         Ok::<_, actix_web::Error>(web::Bytes::from(word))
+///     });
+///     HttpResponse::Ok().streaming(stream::iter(chunks))
+/// }
+///
+/// let sp = Arc::new(ServiceProvider::new());
+/// let handler = DiActixHandler::new(sp, |s| s, |scope, words| {
+///     Box::pin(handler(words, ScopeGuard::new(scope)))
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ScopeGuard {
+    _scope: Rc<dyn std::any::Any>,
+}
+
+impl ScopeGuard {
+    /// Create a guard that keeps the scope alive.
+    pub fn new<S: 'static>(scope: &Rc<S>) -> Self {
+        ScopeGuard {
+            _scope: scope.clone(),
         }
-        this
     }
 }
 
-#[pin_project::pinned_drop]
-impl<SP, Fut> PinnedDrop for SpFuture<SP, Fut> {
-    fn drop(self: Pin<&mut Self>) {
-        use std::alloc::{dealloc, Layout};
-        unsafe {
-            std::ptr::drop_in_place(self.fut.as_ptr());
-            dealloc(self.fut.as_ptr() as *mut u8, Layout::new::<Fut>());
-            std::ptr::drop_in_place(self.sp);
-            dealloc(self.sp as *mut u8, Layout::new::<SP>());
+/// Same as `ScopeGuard`, but for handlers created with `DiActixHandler::new_send`.
+#[derive(Clone)]
+pub struct SendScopeGuard {
+    _scope: Arc<dyn std::any::Any + Send + Sync>,
+}
+
+impl SendScopeGuard {
+    /// Create a guard that keeps the scope alive.
+    pub fn new<S: Send + Sync + 'static>(scope: &Arc<S>) -> Self {
+        SendScopeGuard {
+            _scope: scope.clone(),
         }
     }
 }
 
-impl<SP, Fut> Future for SpFuture<SP, Fut>
+/// Converts an error of a fallible dependency into a response. If the scope `ServiceProvider` of
+/// `DiActixHandler` resolves `Result<T, E>` and `Arc<dyn ErrorMapper<E>>`, handler can resolve
+/// `T` by `ServiceProvider::resolve_or_respond` and return the response from the `ErrorMapper`
+/// with `?` when `Err` is resolved.
+///
+/// `ErrorMapper` is implemented for closures, so usually it is registered like this:
+/// ```
+/// use actix_web::HttpResponse;
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Unauthorized;
+///
+/// let sp = ServiceProvider::new().add_instance::<Arc<dyn ErrorMapper<Unauthorized>>>(
+///     Arc::new(|_: Unauthorized| HttpResponse::Unauthorized().finish()),
+/// );
+/// ```
+pub trait ErrorMapper<E>: Send + Sync {
+    fn map_error(&self, error: E) -> HttpResponse;
+}
+
+impl<E, F> ErrorMapper<E> for F
 where
-    Fut: Future,
+    F: Fn(E) -> HttpResponse + Send + Sync,
 {
-    type Output = Fut::Output;
-
-    fn poll(
-        self: Pin<&mut Self>,
-        cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Self::Output> {
-        let this = self.project();
-        // SAFETY: we just cast NonNull<T> to &'static mut T and use it only to the end of the function.
-        let fut = unsafe { this.fut.map_unchecked_mut(|x| x.as_mut()) };
-        fut.poll(cx)
+    fn map_error(&self, error: E) -> HttpResponse {
+        self(error)
     }
 }
 
-macro_rules! impl_factory_di_args {
-    (($($num:tt, $param:ident),*), $($arg:ident, $cont:ident, $other:ident),*) => {
-        impl<$($param,)* ParSP, DepsSP, ScopeFactory, ScopeResult, F, Res, $($arg, $cont, $other),*>
-            Factory<
-                (HttpRequest, $($param,)*),
-                Pin<Box<SpFuture<ScopeResult, Pin<Box<dyn Future<Output=Res::Output>>>>>>,
-                Res::Output
-            >
-            for DiActixHandler<ServiceProvider<ParSP, DepsSP>, ScopeFactory, F, ScopeResult, ($(($arg,$cont),)*), ($($other,)*)>
-        where
-            (HttpRequest, $($param,)*): FromRequest + 'static,
-            F: 'static,
-            ParSP: 'static,
-            DepsSP: 'static,
-            F: Clone + Fn($($arg,)* $($param),*) -> Res,
-            Res: Future,
-            Res::Output: Responder,
-            ScopeFactory: Fn(ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HCons<InstanceContainer<HttpRequest>, HNil>>) -> ScopeResult + Clone + 'static,
-            ScopeResult: $(Resolver<'static, &'static $cont, $arg, $other> +)* 'static,
-            Self: 'static,
-        {
-            #[allow(non_snake_case)]
-            #[allow(unused_variables)]
-            fn call(&self, data: (HttpRequest, $($param,)*))
-                -> Pin<Box<SpFuture<ScopeResult, Pin<Box<dyn Future<Output=Res::Output>>>>>>
-            {
-                let (req, $($param,)*) = data;
-                let forked = self.sp.fork_arc().add_instance(req);
-                let scope = Box::new((self.scope_factory)(forked));
-                let ptr = Box::into_raw(scope);
-                SpFuture::new(ptr, move |sp| {
-                    let f = self.f.clone();
-                    $(let $param = $param;)*
-                    Box::pin(async move {
-                        // SAFETY: cast *mut T to &'static mut T is valid because we drop reference early than drop T
-                        // (see impl PinnedDrop for SPFuture)
-                        let sp_ref = unsafe { sp.as_ref() }.unwrap();
-                        $(let $arg = sp_ref.resolve();)*
-                        (f)($($arg,)* $($param),*).await
-                    })
-                } as Pin<Box<dyn Future<Output=Res::Output>>>)
-            }
-        }
+impl<Parent, Conts> ServiceProvider<Parent, Conts> {
+    /// Resolve `Result<T, E>` and convert `Err` into a response by the `Arc<dyn ErrorMapper<E>>`
+    /// of the provider, see `ErrorMapper`. `Result<_, HttpResponse>` is a `Responder`, so handlers
+    /// of `DiActixHandler` can return the error with `?`.
+    pub fn resolve_or_respond<'a, T, E, Infer, MapperInfer>(&'a self) -> Result<T, HttpResponse>
+    where
+        Self:
+            Resolver<'a, Result<T, E>, Infer> + Resolver<'a, Arc<dyn ErrorMapper<E>>, MapperInfer>,
+    {
+        Resolver::<Result<T, E>, Infer>::resolve(self).map_err(|error| {
+            Resolver::<Arc<dyn ErrorMapper<E>>, MapperInfer>::resolve(self).map_error(error)
+        })
     }
 }
 
-macro_rules! impl_factory_di {
-    ($($num:tt, $param:ident),*) => {
-        impl_factory_di_args!(($($num, $param),*),);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3, A4, C4, O4);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3, A4, C4, O4, A5, C5, O5);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3, A4, C4, O4, A5, C5, O5, A6, C6, O6);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3, A4, C4, O4, A5, C5, O5, A6, C6, O6, A7, C7, O7);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3, A4, C4, O4, A5, C5, O5, A6, C6, O6, A7, C7, O7, A8, C8, O8);
-        impl_factory_di_args!(($($num, $param),*), A1, C1, O1, A2, C2, O2, A3, C3, O3, A4, C4, O4, A5, C5, O5, A6, C6, O6, A7, C7, O7, A8, C8, O8, A9, C9, O9);
-    };
+impl DependencyClone for HttpRequest {}
+
+/// Resolves `T` for the `Inject<T>` extractor. It must be added to the app data for each type that
+/// is extracted using `Inject`, see `Inject` for more information.
+pub struct Injector<T>(Box<dyn Fn() -> T>);
+
+impl<T> Injector<T> {
+    /// Create an injector that resolves `T` from the provider by the `resolve` function, usually
+    /// `|sp| sp.resolve()`. The function gets the provider by a reference of any lifetime, so `T`
+    /// cannot borrow services from it.
+    pub fn new<SP, F>(sp: Arc<SP>, resolve: F) -> Self
+    where
+        SP: 'static,
+        F: Fn(&SP) -> T + 'static,
+    {
+        Injector(Box::new(move || resolve(&sp)))
+    }
 }
 
-impl_factory_di!();
-impl_factory_di!(0, B1);
-impl_factory_di!(0, B1, 1, B2);
-impl_factory_di!(0, B1, 1, B2, 2, B3);
-impl_factory_di!(0, B1, 1, B2, 2, B3, 3, B4);
-impl_factory_di!(0, B1, 1, B2, 2, B3, 3, B4, 4, B5);
-impl_factory_di!(0, B1, 1, B2, 2, B3, 3, B4, 4, B5, 5, B6);
-impl_factory_di!(0, B1, 1, B2, 2, B3, 3, B4, 4, B5, 5, B6, 6, B7);
-impl_factory_di!(0, B1, 1, B2, 2, B3, 3, B4, 4, B5, 5, B6, 6, B7, 7, B8);
-impl_factory_di!(0, B1, 1, B2, 2, B3, 3, B4, 4, B5, 5, B6, 6, B7, 7, B8, 8, B9);
+/// Extractor that resolves `T` from the `ServiceProvider`. Unlike `DiActixHandler`, it is a usual
+/// extractor, so it can be used in any position of arguments together with `web::Json`,
+/// `web::Path` and others. The value is accessed through `Deref` and `DerefMut`.
+///
+/// Services are resolved from the root provider, not from the request scope, so `T` must be
+/// owned (`Arc<_>` singletons, transients without borrows). For every injected type you need to
+/// add `Injector<T>` to the app data. Like `web::Data`, the app data is looked up when the request
+/// is handled, so a missing `Injector<T>` fails the request with `500 Internal Server Error`
+/// rather than the build: use `DiActixHandler` to check all dependencies of a handler at compile
+/// time.
+///
+/// Usage:
+/// ```
+/// use actix_web::{web, App};
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Repository;
+/// #[inject]
+/// impl Repository {
+///     fn new() -> Self { Repository }
+/// }
+///
+/// async fn index(path: web::Path<String>, repository: Inject<Arc<Repository>>) -> String {
+///     path.into_inner()
+/// }
+///
+/// let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Repository>>());
+///
+/// let app = App::new()
+///     .app_data(Injector::<Arc<Repository>>::new(sp, |sp| sp.resolve()))
+///     .route("/{name}", web::get().to(index));
+/// ```
+pub struct Inject<T>(T);
 
-impl DependencyClone for HttpRequest {}
+impl<T> std::ops::Deref for Inject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Inject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: 'static> FromRequest for Inject<T> {
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, actix_web::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        std::future::ready(match req.app_data::<Injector<T>>() {
+            Some(injector) => Ok(Inject((injector.0)())),
+            None => Err(actix_web::error::ErrorInternalServerError(format!(
+                "`Injector<{}>` is not registered in the app data",
+                std::any::type_name::<T>()
+            ))),
+        })
+    }
+}
 
 pub struct GetRequestData<T>(T);
 
 macro_rules! impl_resolver_for_request {
     ($(($ty:ty, $get:expr)),*) => {
         $(
-        impl<'a, SP, Index> Resolver<'a, &'a GetRequestData<$ty>, $ty, Index> for SP
+        impl<'a, SP, Index> Resolver<'a, $ty, (&'a GetRequestData<$ty>, Index)> for SP
         where
             Self: SelectContainer<'a, &'a InstanceContainer<HttpRequest>, Index>,
         {
@@ -251,7 +459,7 @@ impl_resolver_for_request! (
 );
 
 impl<'a, T, SP, Index>
-    Resolver<'a, &'a GetRequestData<Option<&'a Data<T>>>, Option<&'a Data<T>>, Index> for SP
+    Resolver<'a, Option<&'a Data<T>>, (&'a GetRequestData<Option<&'a Data<T>>>, Index)> for SP
 where
     T: 'static,
     Self: SelectContainer<'a, &'a InstanceContainer<HttpRequest>, Index>,
@@ -260,3 +468,360 @@ where
         self.get().get().app_data::<Data<T>>()
     }
 }
+
+/// Mode of `DiTransform` that resolves the middleware once per application instance.
+pub struct PerApp;
+
+/// Mode of `DiTransform` that resolves the middleware for every request.
+pub struct PerRequest;
+
+/// Middleware that is resolved from the `ServiceProvider`, so it can receive injected services the
+/// same way handlers do. `M` is an usual actix-web middleware (implements `Transform`).
+///
+/// - `DiTransform::new` resolves `M` once for each application instance (i.e. for every worker)
+///   when the application is built.
+/// - `DiTransform::per_request` resolves `M` for every request, so `Transient` dependencies are
+///   unique for each request. The wrapped service is shared between middleware instances.
+///
+/// `M` is resolved by a function that gets the provider by a reference of any lifetime, usually
+/// `|sp| sp.resolve()`, so it cannot borrow services from the provider.
+///
+/// Usage:
+/// ```
+/// use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+/// use actix_web::{web, App, Error};
+/// use std::future::{ready, Future, Ready};
+/// use std::pin::Pin;
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll};
+/// use teloc::*;
+///
+/// struct Tokens(Vec<&'static str>);
+///
+/// struct Auth {
+///     tokens: Arc<Tokens>,
+/// }
+/// #[inject]
+/// impl Auth {
+///     fn new(tokens: Arc<Tokens>) -> Self {
+///         Auth { tokens }
+///     }
+/// }
+///
+/// struct AuthService<S> {
+///     tokens: Arc<Tokens>,
+///     service: S,
+/// }
+///
+/// impl<S> Transform<S> for Auth
+/// where
+///     S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error>,
+///     S::Future: 'static,
+/// {
+///     type Request = ServiceRequest;
+///     type Response = ServiceResponse;
+///     type Error = Error;
+///     type Transform = AuthService<S>;
+///     type InitError = ();
+///     type Future = Ready<Result<AuthService<S>, ()>>;
+///
+///     fn new_transform(&self, service: S) -> Self::Future {
+///         ready(Ok(AuthService { tokens: self.tokens.clone(), service }))
+///     }
+/// }
+///
+/// impl<S> Service for AuthService<S>
+/// where
+///     S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error>,
+///     S::Future: 'static,
+/// {
+///     type Request = ServiceRequest;
+///     type Response = ServiceResponse;
+///     type Error = Error;
+///     type Future = Pin<Box<dyn Future<Output = Result<ServiceResponse, Error>>>>;
+///
+///     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+///         self.service.poll_ready(cx)
+///     }
+///
+///     fn call(&mut self, req: ServiceRequest) -> Self::Future {
+///         let token = req.headers().get("token").and_then(|t| t.to_str().ok());
+///         if token.map_or(false, |t| self.tokens.0.contains(&t)) {
+///             Box::pin(self.service.call(req))
+///         } else {
+///             Box::pin(ready(Err(actix_web::error::ErrorUnauthorized("unknown token"))))
+///         }
+///     }
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_instance(Arc::new(Tokens(vec!["secret"])))
+///     .add_transient::<Auth>();
+/// let sp = Arc::new(sp);
+///
+/// let app = App::new()
+///     .wrap(DiTransform::<Auth, _>::new(sp, |sp| sp.resolve()))
+///     .route("/", web::get().to(|| async { "Hello!" }));
+/// ```
+pub struct DiTransform<M, Mode = PerApp> {
+    factory: Rc<dyn Fn() -> M>,
+    phantom: PhantomData<Mode>,
+}
+
+impl<M> DiTransform<M, PerApp> {
+    /// Create a middleware that is resolved once for each application instance.
+    pub fn new<SP, F>(sp: Arc<SP>, resolve: F) -> Self
+    where
+        SP: 'static,
+        F: Fn(&SP) -> M + 'static,
+    {
+        DiTransform {
+            factory: Rc::new(move || resolve(&sp)),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<M> DiTransform<M, PerRequest> {
+    /// Create a middleware that is resolved for every request.
+    pub fn per_request<SP, F>(sp: Arc<SP>, resolve: F) -> Self
+    where
+        SP: 'static,
+        F: Fn(&SP) -> M + 'static,
+    {
+        DiTransform {
+            factory: Rc::new(move || resolve(&sp)),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, M> Transform<S> for DiTransform<M, PerApp>
+where
+    M: Transform<S>,
+{
+    type Request = M::Request;
+    type Response = M::Response;
+    type Error = M::Error;
+    type Transform = M::Transform;
+    type InitError = M::InitError;
+    type Future = M::Future;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        (self.factory)().new_transform(service)
+    }
+}
+
+impl<S, M> Transform<S> for DiTransform<M, PerRequest>
+where
+    S: Service,
+    M: Transform<SharedService<S>> + 'static,
+    M::Request: 'static,
+    M::Transform: 'static,
+    M::Future: 'static,
+    M::InitError: std::fmt::Debug,
+    M::Error: Into<actix_web::Error>,
+    actix_web::Error: From<S::Error>,
+{
+    type Request = M::Request;
+    type Response = M::Response;
+    type Error = actix_web::Error;
+    type Transform = PerRequestService<S, M>;
+    type InitError = ();
+    type Future = std::future::Ready<Result<PerRequestService<S, M>, ()>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        std::future::ready(Ok(PerRequestService {
+            service: SharedService(Rc::new(RefCell::new(service))),
+            factory: self.factory.clone(),
+        }))
+    }
+}
+
+/// Service that is shared between middleware instances created by `DiTransform::per_request`.
+pub struct SharedService<S>(Rc<RefCell<S>>);
+
+impl<S> Clone for SharedService<S> {
+    fn clone(&self) -> Self {
+        SharedService(self.0.clone())
+    }
+}
+
+impl<S: Service> Service for SharedService<S> {
+    type Request = S::Request;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.borrow_mut().poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Self::Request) -> Self::Future {
+        self.0.borrow_mut().call(req)
+    }
+}
+
+/// Service created by `DiTransform::per_request`, that resolves the middleware for every request.
+pub struct PerRequestService<S, M> {
+    service: SharedService<S>,
+    factory: Rc<dyn Fn() -> M>,
+}
+
+impl<S, M> Service for PerRequestService<S, M>
+where
+    S: Service,
+    M: Transform<SharedService<S>> + 'static,
+    M::Request: 'static,
+    M::Transform: 'static,
+    M::Future: 'static,
+    M::InitError: std::fmt::Debug,
+    M::Error: Into<actix_web::Error>,
+    actix_web::Error: From<S::Error>,
+{
+    type Request = M::Request;
+    type Response = M::Response;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<M::Response, actix_web::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Self::Request) -> Self::Future {
+        let transform = (self.factory)().new_transform(self.service.clone());
+        Box::pin(async move {
+            let mut service = transform.await.map_err(|e| {
+                actix_web::error::ErrorInternalServerError(format!(
+                    "Failed to create the middleware: {:?}",
+                    e
+                ))
+            })?;
+            std::future::poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(Into::into)?;
+            service.call(req).await.map_err(Into::into)
+        })
+    }
+}
+
+/// Route guard built from a closure that gets the provider and the `RequestHead`, so the check can
+/// use services resolved from the provider. Services are resolved for each check, and the provider
+/// is passed by a reference of any lifetime, so they cannot outlive the check.
+///
+/// Usage:
+/// ```
+/// use actix_web::{web, App};
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct FeatureFlags { beta: bool }
+///
+/// let sp = Arc::new(ServiceProvider::new().add_instance(FeatureFlags { beta: true }));
+///
+/// let app = App::new().route(
+///     "/beta",
+///     web::get()
+///         .guard(DiGuard::new(sp, |sp, _| {
+///             let flags: &FeatureFlags = sp.resolve();
+///             flags.beta
+///         }))
+///         .to(|| async { "Hello, beta tester!" }),
+/// );
+/// ```
+pub struct DiGuard(Box<dyn Fn(&RequestHead) -> bool>);
+
+impl DiGuard {
+    /// Create a guard from the provider and the guard function.
+    pub fn new<SP, F>(sp: Arc<SP>, f: F) -> Self
+    where
+        SP: 'static,
+        F: Fn(&SP, &RequestHead) -> bool + 'static,
+    {
+        DiGuard(Box::new(move |head| f(&sp, head)))
+    }
+}
+
+impl Guard for DiGuard {
+    fn check(&self, head: &RequestHead) -> bool {
+        (self.0)(head)
+    }
+}
+
+impl<Parent, Conts> ServiceProvider<Parent, Conts> {
+    /// Resolve `Arc<T>` and wrap it into `web::Data<T>`, so extractor-based code and services
+    /// managed by teloc share the same instances. `T` must be resolvable as `Arc<T>`, e.g.
+    /// registered with `add_singleton::<Arc<T>>()` or `add_instance(Arc::new(...))`.
+    ///
+    /// Usage:
+    /// ```
+    /// use actix_web::{web, App};
+    /// use std::sync::Arc;
+    /// use teloc::*;
+    ///
+    /// struct Repository;
+    /// #[inject]
+    /// impl Repository {
+    ///     fn new() -> Self { Repository }
+    /// }
+    /// struct Config;
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_singleton::<Arc<Repository>>()
+    ///     .add_instance(Arc::new(Config));
+    ///
+    /// let repository: web::Data<Repository> = sp.to_actix_data::<Repository, _>();
+    /// let app = App::new()
+    ///     .app_data(repository)
+    ///     .configure(sp.actix_data::<(Config,), _>());
+    /// ```
+    pub fn to_actix_data<'a, T: ?Sized, Infer>(&'a self) -> Data<T>
+    where
+        Self: Resolver<'a, Arc<T>, Infer>,
+    {
+        Data::from(self.resolve())
+    }
+
+    /// Resolve all types of the tuple as `web::Data` and return a function that registers them in
+    /// the app, to be passed to `App::configure`. See `ServiceProvider::to_actix_data`.
+    pub fn actix_data<'a, T, Infer>(&'a self) -> Box<dyn FnOnce(&mut ServiceConfig)>
+    where
+        T: ActixDataTuple<'a, Self, Infer>,
+    {
+        T::resolve_data(self)
+    }
+}
+
+/// Tuple of types for `ServiceProvider::actix_data`. You do not need to implement this trait
+/// yourself.
+pub trait ActixDataTuple<'a, SP: ?Sized, Infer> {
+    fn resolve_data(sp: &'a SP) -> Box<dyn FnOnce(&mut ServiceConfig)>;
+}
+
+macro_rules! impl_actix_data_tuple {
+    ($($ty:ident, $infer:ident),*) => {
+        impl<'a, SP, $($ty, $infer),*> ActixDataTuple<'a, SP, ($($infer,)*)> for ($($ty,)*)
+        where
+            SP: $(Resolver<'a, Arc<$ty>, $infer> +)* ?Sized,
+            $($ty: 'static,)*
+        {
+            #[allow(non_snake_case)]
+            fn resolve_data(sp: &'a SP) -> Box<dyn FnOnce(&mut ServiceConfig)> {
+                $(let $ty = Data::<$ty>::from(Resolver::<Arc<$ty>, $infer>::resolve(sp));)*
+                Box::new(move |cfg| {
+                    $(cfg.app_data($ty);)*
+                })
+            }
+        }
+    };
+}
+
+impl_actix_data_tuple!(A1, I1);
+impl_actix_data_tuple!(A1, I1, A2, I2);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3, A4, I4);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8);
+impl_actix_data_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8, A9, I9);
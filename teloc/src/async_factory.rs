@@ -0,0 +1,174 @@
+//! Support for transients created by `async` factories, see
+//! `ServiceProvider::add_transient_factory_async`.
+
+use crate::container::Container;
+use crate::get_dependencies::{FactoryArgs, GetDependencies};
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Resolver, ServiceProvider};
+use alloc::vec::Vec;
+use core::future::{ready, Future, Ready};
+use core::marker::PhantomData;
+
+/// Container of a service with the `Transient` lifetime, that is created by an `async` factory,
+/// see `ServiceProvider::add_transient_factory_async`.
+pub struct AsyncTransientFactoryContainer<Deps, T, F>(F, PhantomData<fn(Deps) -> T>);
+
+impl<Deps, T, F> Container for AsyncTransientFactoryContainer<Deps, T, F> {
+    type Data = F;
+
+    fn init(f: F) -> Self {
+        Self(f, PhantomData)
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<Deps, T, F: Clone> Clone for AsyncTransientFactoryContainer<Deps, T, F> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<Deps, T, F> AsyncTransientFactoryContainer<Deps, T, F> {
+    #[inline]
+    pub fn get(&self) -> &F {
+        &self.0
+    }
+}
+
+/// This trait is used to resolve some object from service provider by awaiting. Services
+/// registered by `ServiceProvider::add_transient_factory_async` can be resolved only by it, other
+/// services of `ServiceProvider` are resolved by it too, as a ready future.
+///
+/// Dependencies of the service are resolved synchronously before the factory is called, so other
+/// services cannot depend on a service with an `async` factory. Take the provider where the
+/// service is needed and await it there.
+///
+/// The type of the service cannot be inferred from the awaited value through the method of this
+/// trait, so `ServiceProvider` has the inherent method `resolve_async` with the same meaning.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// struct Config { port: u16 }
+///
+/// let sp = ServiceProvider::new().add_instance(Config { port: 8080 });
+///
+/// futures::executor::block_on(async {
+///     let config: &Config = sp.resolve_async().await;
+///     assert_eq!(config.port, 8080);
+/// });
+/// ```
+pub trait AsyncResolver<'a, T, Infer> {
+    fn resolve_async(&'a self) -> impl Future<Output = T>;
+}
+
+impl<Parent, Conts> ServiceProvider<Parent, Conts> {
+    /// Resolve a service by awaiting, see `AsyncResolver`.
+    #[inline]
+    pub fn resolve_async<'a, T, Infer>(
+        &'a self,
+    ) -> impl Future<Output = T> + use<'a, T, Infer, Parent, Conts>
+    where
+        Self: AsyncResolver<'a, T, Infer>,
+    {
+        AsyncResolver::resolve_async(self)
+    }
+
+    /// Same as `ServiceProvider::resolve_async`, but nothing is resolved until the future is
+    /// polled: dependencies are resolved and the factory is called on the first poll. Several
+    /// services can be constructed concurrently by joining their futures.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::cell::Cell;
+    /// use teloc::*;
+    ///
+    /// struct Token(&'static str);
+    /// struct Profile(&'static str);
+    ///
+    /// let created = Cell::new(0);
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(&created)
+    ///     .add_transient_factory_async(|(created,): (&&Cell<u32>,)| async move {
+    ///         created.set(created.get() + 1);
+    ///         Token("token")
+    ///     })
+    ///     .add_transient_factory_async(|(created,): (&&Cell<u32>,)| async move {
+    ///         created.set(created.get() + 1);
+    ///         Profile("profile")
+    ///     });
+    ///
+    /// let token = sp.resolve_lazy::<Token, _>();
+    /// let profile = sp.resolve_lazy::<Profile, _>();
+    /// assert_eq!(created.get(), 0);
+    ///
+    /// let (token, profile) = futures::executor::block_on(async {
+    ///     futures::join!(token, profile)
+    /// });
+    /// assert_eq!((token.0, profile.0), ("token", "profile"));
+    /// assert_eq!(created.get(), 2);
+    /// ```
+    pub async fn resolve_lazy<'a, T, Infer>(&'a self) -> T
+    where
+        Self: AsyncResolver<'a, T, Infer>,
+    {
+        AsyncResolver::resolve_async(self).await
+    }
+}
+
+/// Marker of services that are resolved by `AsyncResolver` synchronously.
+pub struct ResolveReady;
+
+impl<'a, Parent, Conts, T, Infer> AsyncResolver<'a, T, (ResolveReady, Infer)>
+    for ServiceProvider<Parent, Conts>
+where
+    Self: Resolver<'a, T, Infer>,
+{
+    #[allow(refining_impl_trait)]
+    fn resolve_async(&'a self) -> Ready<T> {
+        ready(self.resolve())
+    }
+}
+
+impl<'this, 'cont, Deps, T, F, Fut, SP, Index, Infer>
+    AsyncResolver<
+        'this,
+        T,
+        (
+            &'cont AsyncTransientFactoryContainer<Deps, T, F>,
+            Index,
+            Deps,
+            Infer,
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont AsyncTransientFactoryContainer<Deps, T, F>, Index>
+        + GetDependencies<'this, Deps::List, Infer>,
+    Deps: FactoryArgs + 'cont,
+    F: Fn(Deps) -> Fut + 'cont,
+    Fut: Future<Output = T>,
+    T: 'cont,
+{
+    #[allow(refining_impl_trait)]
+    fn resolve_async(&'this self) -> Fut {
+        (self.get().get())(Deps::from_list(self.get_deps()))
+    }
+}
+
+impl<'a, SP, Deps, T, F> WarmUp<'a, SP, ()> for AsyncTransientFactoryContainer<Deps, T, F> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, Deps, T, F> CollectImplementors<'a, SP, Dyn, ()>
+    for AsyncTransientFactoryContainer<Deps, T, F>
+{
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, Deps, T, F> CollectOrder<'a, SP, ()> for AsyncTransientFactoryContainer<Deps, T, F> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
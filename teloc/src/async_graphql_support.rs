@@ -0,0 +1,81 @@
+//! Support for `async-graphql` crate.
+
+use crate::container::SingletonFactoryContainer;
+use crate::ServiceProvider;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::futures_util::future::BoxFuture;
+use frunk::hlist::HList;
+use frunk::{HCons, HNil};
+use std::sync::Arc;
+
+/// Factory stored by the container that is added by `ServiceProvider::add_data_loader`. Unlike
+/// `BoxedFactory` it is `Send + Sync`, so scopes with data loaders can be held across `.await`.
+pub type DataLoaderFactory<L> = Box<dyn Fn(HCons<L, HNil>) -> Arc<DataLoader<L>> + Send + Sync>;
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add `Arc<DataLoader<L>>` that is created once per provider from the `L` loader resolved
+    /// from the same provider. Use it on a forked scope, so every request gets fresh loaders and
+    /// cached values never leak to other requests. `L` must be resolvable by ownership, so add it
+    /// as a transient that takes shared state of the scope (a DB session, for example) as `Arc`.
+    ///
+    /// `spawner` is passed to `DataLoader::new` and is used to run batched loads, it is usually
+    /// `tokio::spawn`. Pass the resolved loader to the GraphQL request with `Request::data`.
+    ///
+    /// Usage:
+    /// ```
+    /// use async_graphql::dataloader::{DataLoader, Loader};
+    /// use std::collections::HashMap;
+    /// use std::sync::Arc;
+    /// use teloc::*;
+    ///
+    /// struct DbSession { user: String }
+    ///
+    /// struct UserLoader { session: Arc<DbSession> }
+    /// #[inject]
+    /// impl UserLoader {
+    ///     fn new(session: Arc<DbSession>) -> Self { UserLoader { session } }
+    /// }
+    /// impl Loader<u32> for UserLoader {
+    ///     type Value = String;
+    ///     type Error = Arc<String>;
+    ///
+    ///     async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, String>, Arc<String>> {
+    ///         Ok(keys.iter().map(|k| (*k, format!("{}#{}", self.session.user, k))).collect())
+    ///     }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new().add_transient::<UserLoader>();
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// runtime.block_on(async {
+    ///     let scope = sp
+    ///         .fork()
+    ///         .add_instance(Arc::new(DbSession { user: "alice".into() }))
+    ///         .add_data_loader::<UserLoader, _, _>(tokio::spawn);
+    ///
+    ///     let loader: Arc<DataLoader<UserLoader>> = scope.resolve();
+    ///     // `schema.execute(async_graphql::Request::new(query).data(loader))`
+    ///     assert_eq!(loader.load_one(1).await.unwrap().unwrap(), "alice#1");
+    /// });
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn add_data_loader<L, S, R>(
+        self,
+        spawner: S,
+    ) -> ServiceProvider<
+        Parent,
+        HCons<
+            SingletonFactoryContainer<HCons<L, HNil>, Arc<DataLoader<L>>, DataLoaderFactory<L>>,
+            Conts,
+        >,
+    >
+    where
+        L: 'static,
+        S: Fn(BoxFuture<'static, ()>) -> R + Clone + Send + Sync + 'static,
+    {
+        let factory: DataLoaderFactory<L> = Box::new(move |HCons { head: loader, .. }| {
+            Arc::new(DataLoader::new(loader, spawner.clone()))
+        });
+        self._add::<SingletonFactoryContainer<_, _, _>>(factory)
+    }
+}
@@ -0,0 +1,90 @@
+//! Support for `axum` crate.
+//!
+//! `ProvideLayer` (from `tower_support`) forks a request scope from the `ServiceProvider` for every
+//! request and puts it into the request extensions, and `Inject<T>` extractor resolves `T` from
+//! this scope. To use axum's own `State` extractor instead, derive `AppState` on the state struct.
+//!
+//! Usage:
+//! ```
+//! use axum::{routing::get, Router};
+//! use std::sync::Arc;
+//! use teloc::axum_support::{Inject, ProvideLayer};
+//! use teloc::*;
+//!
+//! struct Repository;
+//! #[inject]
+//! impl Repository {
+//!     fn new() -> Self { Repository }
+//! }
+//!
+//! struct Controller {
+//!     repository: Arc<Repository>,
+//!     method: axum::http::Method,
+//! }
+//! #[inject]
+//! impl Controller {
+//!     fn new(repository: Arc<Repository>, method: axum::http::Method) -> Self {
+//!         Controller { repository, method }
+//!     }
+//! }
+//!
+//! async fn index(controller: Inject<Controller>) -> String {
+//!     controller.method.to_string()
+//! }
+//!
+//! let sp = Arc::new(
+//!     ServiceProvider::new()
+//!         .add_singleton::<Arc<Repository>>()
+//!         .add_transient::<Controller>(),
+//! );
+//!
+//! let app: Router = Router::new().route("/", get(index)).layer(
+//!     ProvideLayer::new(sp, |scope, parts| scope.add_instance(parts.method.clone()))
+//!         .inject::<Controller, _>(|scope| scope.resolve()),
+//! );
+//! ```
+use crate::tower_support::Scoped;
+use axum::extract::FromRequestParts;
+use http::request::Parts;
+use http::StatusCode;
+use std::future::Future;
+
+pub use crate::tower_support::ProvideLayer;
+pub use teloc_macros::AppState;
+
+/// Extractor that resolves `T` from the request scope created by `ProvideLayer`. The value is
+/// accessed through `Deref` and `DerefMut`. `T` must be registered using `ProvideLayer::inject`,
+/// otherwise the request fails with `500 Internal Server Error`.
+pub struct Inject<T>(T);
+
+impl<T> std::ops::Deref for Inject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Inject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Send + 'static, S: Send + Sync> FromRequestParts<S> for Inject<T> {
+    type Rejection = (StatusCode, String);
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
+        std::future::ready(inject(parts))
+    }
+}
+
+fn inject<T: 'static>(parts: &Parts) -> Result<Inject<T>, (StatusCode, String)> {
+    match Scoped::from_extensions(&parts.extensions) {
+        Ok(Scoped(value)) => Ok(Inject(value)),
+        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
+    }
+}
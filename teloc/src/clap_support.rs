@@ -0,0 +1,158 @@
+//! Support for `clap` crate.
+
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use clap::Parser;
+use frunk::hlist::HList;
+use frunk::HCons;
+use std::ffi::OsString;
+
+type WithSubcommand<Parent, T, A, Conts> = ServiceProvider<
+    Parent,
+    HCons<InstanceContainer<Option<A>>, HCons<InstanceContainer<T>, Conts>>,
+>;
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Parse the command line arguments of the current process into `T` and add it as an
+    /// `Instance`. Like `clap::Parser::parse`, prints the error (or help) and exits the process
+    /// if arguments cannot be parsed.
+    pub fn add_cli_args<T>(self) -> ServiceProvider<Parent, HCons<InstanceContainer<T>, Conts>>
+    where
+        T: Parser,
+    {
+        self.add_instance(T::parse())
+    }
+
+    /// Same as `ServiceProvider::add_cli_args`, but parses the given arguments and returns an
+    /// error instead of exiting the process. The first argument is the binary name.
+    ///
+    /// Arguments of a subcommand can be added to a forked scope, so handler of each command can
+    /// be resolved from the provider, or with `ServiceProvider::try_add_cli_subcommand_from`.
+    ///
+    /// Usage:
+    /// ```
+    /// use clap::{Args, Parser, Subcommand};
+    /// use teloc::*;
+    ///
+    /// #[derive(Parser)]
+    /// struct Cli {
+    ///     #[arg(long)]
+    ///     verbose: bool,
+    ///     #[command(subcommand)]
+    ///     command: Command,
+    /// }
+    ///
+    /// #[derive(Subcommand)]
+    /// enum Command {
+    ///     Greet(GreetArgs),
+    /// }
+    ///
+    /// #[derive(Args, Clone)]
+    /// struct GreetArgs {
+    ///     name: String,
+    /// }
+    ///
+    /// struct GreetHandler<'a> { cli: &'a Cli, args: GreetArgs }
+    /// #[inject]
+    /// impl<'a> GreetHandler<'a> {
+    ///     fn new(cli: &'a Cli, args: &GreetArgs) -> Self { Self { cli, args: args.clone() } }
+    /// }
+    /// impl GreetHandler<'_> {
+    ///     fn run(&self) -> String {
+    ///         if self.cli.verbose { format!("Hello, {}!", self.args.name) } else { self.args.name.clone() }
+    ///     }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .try_add_cli_args_from::<Cli, _, _>(vec!["app", "--verbose", "greet", "world"])
+    ///     .unwrap()
+    ///     .add_transient::<GreetHandler>();
+    ///
+    /// let cli: &Cli = sp.resolve();
+    /// let output = match &cli.command {
+    ///     Command::Greet(args) => {
+    ///         let scope = sp.fork().add_instance(args.clone());
+    ///         let handler: GreetHandler = scope.resolve();
+    ///         handler.run()
+    ///     }
+    /// };
+    /// assert_eq!(output, "Hello, world!");
+    /// ```
+    pub fn try_add_cli_args_from<T, I, Arg>(
+        self,
+        args: I,
+    ) -> Result<ServiceProvider<Parent, HCons<InstanceContainer<T>, Conts>>, clap::Error>
+    where
+        T: Parser,
+        I: IntoIterator<Item = Arg>,
+        Arg: Into<OsString> + Clone,
+    {
+        Ok(self.add_instance(T::try_parse_from(args)?))
+    }
+
+    /// Same as `ServiceProvider::add_cli_args`, but also adds arguments of a subcommand as an
+    /// `Instance` of `Option<A>`. `select` gets the parsed `T` and returns arguments of the
+    /// subcommand if it was matched, so handlers of the subcommand can depend on `&Option<A>`.
+    pub fn add_cli_subcommand<T, A, F>(self, select: F) -> WithSubcommand<Parent, T, A, Conts>
+    where
+        T: Parser,
+        F: FnOnce(&T) -> Option<A>,
+    {
+        let cli = T::parse();
+        let args = select(&cli);
+        self.add_instance(cli).add_instance(args)
+    }
+
+    /// Same as `ServiceProvider::add_cli_subcommand`, but parses the given arguments and returns
+    /// an error instead of exiting the process. The first argument is the binary name.
+    ///
+    /// Usage:
+    /// ```
+    /// use clap::{Args, Parser, Subcommand};
+    /// use teloc::*;
+    ///
+    /// #[derive(Parser)]
+    /// struct Cli {
+    ///     #[command(subcommand)]
+    ///     command: Command,
+    /// }
+    ///
+    /// #[derive(Subcommand)]
+    /// enum Command {
+    ///     Greet(GreetArgs),
+    ///     Version,
+    /// }
+    ///
+    /// #[derive(Args, Clone)]
+    /// struct GreetArgs {
+    ///     name: String,
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .try_add_cli_subcommand_from::<Cli, _, _, _, _>(vec!["app", "greet", "world"], |cli| {
+    ///         match &cli.command {
+    ///             Command::Greet(args) => Some(args.clone()),
+    ///             _ => None,
+    ///         }
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let args: &Option<GreetArgs> = sp.resolve();
+    /// assert_eq!(args.as_ref().unwrap().name, "world");
+    /// ```
+    pub fn try_add_cli_subcommand_from<T, A, I, Arg, F>(
+        self,
+        args: I,
+        select: F,
+    ) -> Result<WithSubcommand<Parent, T, A, Conts>, clap::Error>
+    where
+        T: Parser,
+        I: IntoIterator<Item = Arg>,
+        Arg: Into<OsString> + Clone,
+        F: FnOnce(&T) -> Option<A>,
+    {
+        let cli = T::try_parse_from(args)?;
+        let args = select(&cli);
+        Ok(self.add_instance(cli).add_instance(args))
+    }
+}
@@ -0,0 +1,34 @@
+//! Support for cleanup callbacks of scopes, see `ServiceProvider::on_drop`.
+
+use crate::container::Container;
+use crate::implementors::CollectImplementors;
+use crate::warm_up::WarmUp;
+use alloc::vec::Vec;
+
+/// Container that calls `F` when it is dropped together with the `ServiceProvider`, see
+/// `ServiceProvider::on_drop`.
+pub struct OnDropContainer<F: FnOnce()>(Option<F>);
+
+impl<F: FnOnce()> Container for OnDropContainer<F> {
+    type Data = F;
+
+    fn init(callback: F) -> Self {
+        Self(Some(callback))
+    }
+}
+
+impl<F: FnOnce()> Drop for OnDropContainer<F> {
+    fn drop(&mut self) {
+        if let Some(callback) = self.0.take() {
+            callback()
+        }
+    }
+}
+
+impl<'a, SP, F: FnOnce()> WarmUp<'a, SP, ()> for OnDropContainer<F> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, F: FnOnce()> CollectImplementors<'a, SP, Dyn, ()> for OnDropContainer<F> {
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
@@ -0,0 +1,149 @@
+//! Support for runtime-predicate bindings, see `ServiceProvider::add_singleton_if`.
+
+use crate::container::{BoxedFactory, Container, SingletonCell};
+use crate::dependency::DependencyClone;
+use crate::get_dependencies::{FactoryArgs, GetDependencies};
+use crate::service_provider::SelectContainer;
+use crate::warm_up::WarmUp;
+use crate::Resolver;
+use core::marker::PhantomData;
+
+/// Container of a singleton created by one of two factories, chosen by a predicate at
+/// registration. See `ServiceProvider::add_singleton_if`.
+pub struct ConditionalSingletonContainer<
+    DepsA,
+    DepsB,
+    T,
+    A = BoxedFactory<DepsA, T>,
+    B = BoxedFactory<DepsB, T>,
+> {
+    use_first: bool,
+    if_true: A,
+    if_false: B,
+    instance: SingletonCell<T>,
+    phantom: PhantomData<fn(DepsA, DepsB)>,
+}
+
+impl<DepsA, DepsB, T, A, B> Container for ConditionalSingletonContainer<DepsA, DepsB, T, A, B> {
+    type Data = (bool, A, B);
+
+    fn init((use_first, if_true, if_false): (bool, A, B)) -> Self {
+        Self {
+            use_first,
+            if_true,
+            if_false,
+            instance: SingletonCell::default(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<DepsA, DepsB, T, A: Clone, B: Clone> Clone
+    for ConditionalSingletonContainer<DepsA, DepsB, T, A, B>
+{
+    fn clone(&self) -> Self {
+        Self {
+            use_first: self.use_first,
+            if_true: self.if_true.clone(),
+            if_false: self.if_false.clone(),
+            instance: self.instance.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<DepsA, DepsB, T, A, B> ConditionalSingletonContainer<DepsA, DepsB, T, A, B>
+where
+    DepsA: FactoryArgs,
+    DepsB: FactoryArgs,
+    A: Fn(DepsA) -> T,
+    B: Fn(DepsB) -> T,
+{
+    /// Returns `true` if the first factory is used.
+    #[inline]
+    pub fn uses_first(&self) -> bool {
+        self.use_first
+    }
+
+    fn get_or_init<'a, SP, InferA, InferB>(&self, sp: &'a SP) -> &T
+    where
+        SP: GetDependencies<'a, DepsA::List, InferA> + GetDependencies<'a, DepsB::List, InferB>,
+    {
+        // Only dependencies of the chosen factory are resolved.
+        self.instance.get_or_init(|| {
+            if self.use_first {
+                (self.if_true)(DepsA::from_list(
+                    GetDependencies::<DepsA::List, InferA>::get_deps(sp),
+                ))
+            } else {
+                (self.if_false)(DepsB::from_list(
+                    GetDependencies::<DepsB::List, InferB>::get_deps(sp),
+                ))
+            }
+        })
+    }
+}
+
+impl<'this, 'cont, DepsA, DepsB, T, A, B, SP, Index, InferA, InferB>
+    Resolver<
+        'this,
+        T,
+        (
+            &'cont ConditionalSingletonContainer<DepsA, DepsB, T, A, B>,
+            Index,
+            (InferA, InferB),
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont ConditionalSingletonContainer<DepsA, DepsB, T, A, B>, Index>
+        + GetDependencies<'this, DepsA::List, InferA>
+        + GetDependencies<'this, DepsB::List, InferB>,
+    DepsA: FactoryArgs,
+    DepsB: FactoryArgs,
+    A: Fn(DepsA) -> T,
+    B: Fn(DepsB) -> T,
+    T: DependencyClone,
+{
+    fn resolve(&'this self) -> T {
+        self.get().get_or_init(self).clone()
+    }
+}
+
+impl<'this, 'cont, DepsA, DepsB, T, A, B, SP, Index, InferA, InferB>
+    Resolver<
+        'this,
+        &'cont T,
+        (
+            &'cont ConditionalSingletonContainer<DepsA, DepsB, T, A, B>,
+            Index,
+            (InferA, InferB),
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont ConditionalSingletonContainer<DepsA, DepsB, T, A, B>, Index>
+        + GetDependencies<'this, DepsA::List, InferA>
+        + GetDependencies<'this, DepsB::List, InferB>,
+    DepsA: FactoryArgs,
+    DepsB: FactoryArgs,
+    A: Fn(DepsA) -> T,
+    B: Fn(DepsB) -> T,
+{
+    fn resolve(&'this self) -> &'cont T {
+        self.get().get_or_init(self)
+    }
+}
+
+impl<'a, SP, DepsA, DepsB, T, A, B, InferA, InferB> WarmUp<'a, SP, (InferA, InferB)>
+    for ConditionalSingletonContainer<DepsA, DepsB, T, A, B>
+where
+    SP: GetDependencies<'a, DepsA::List, InferA> + GetDependencies<'a, DepsB::List, InferB>,
+    DepsA: FactoryArgs,
+    DepsB: FactoryArgs,
+    A: Fn(DepsA) -> T,
+    B: Fn(DepsB) -> T,
+{
+    fn warm_up(&'a self, sp: &'a SP) {
+        self.get_or_init(sp);
+    }
+}
@@ -0,0 +1,335 @@
+//! Configuration tree, that values of services are read from by key paths. Enabled by the `config`
+//! feature.
+//!
+//! The tree is a `serde_json::Value`, registered by `ServiceProvider::add_config`. Fields of
+//! services annotated with `#[config("key.path")]` in `#[derive(Dependency)]` are deserialized
+//! from it, so a service does not need a dedicated struct for every slice of the configuration.
+//! Whole sections are resolved as `Section<T>`, see `ConfigSection`.
+//!
+//! Usage:
+//! ```
+//! use serde_json::json;
+//! use teloc::*;
+//!
+//! #[derive(Dependency)]
+//! struct Server {
+//!     #[config("server.port")]
+//!     port: u16,
+//!     #[config("server.hosts.0")]
+//!     host: String,
+//! }
+//!
+//! let sp = ServiceProvider::new()
+//!     .add_config(json!({ "server": { "port": 8080, "hosts": ["localhost"] } }))
+//!     .add_transient::<Server>();
+//!
+//! let server: Server = sp.resolve();
+//! assert_eq!(server.port, 8080);
+//! assert_eq!(server.host, "localhost");
+//! ```
+
+use crate::container::Container;
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::validate::Validate;
+use crate::warm_up::WarmUp;
+use crate::{Resolver, ServiceProvider};
+use frunk::hlist::HList;
+use frunk::HCons;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+/// Parsed configuration, see the module documentation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigTree(Value);
+
+impl ConfigTree {
+    pub fn new(value: Value) -> Self {
+        ConfigTree(value)
+    }
+
+    /// Returns the whole tree.
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Returns the value at the path of keys separated by `.`. Numeric keys are indices of
+    /// arrays. The empty path is the whole tree.
+    pub fn find(&self, path: &str) -> Option<&Value> {
+        if path.is_empty() {
+            return Some(&self.0);
+        }
+        path.split('.').try_fold(&self.0, |value, key| match value {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+    }
+
+    /// Deserialize the value at the path, see `ConfigTree::find`.
+    pub fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ConfigError> {
+        let value = self.find(path).ok_or_else(|| ConfigError::Missing {
+            path: path.to_string(),
+        })?;
+        T::deserialize(value).map_err(|source| ConfigError::Invalid {
+            path: path.to_string(),
+            source,
+        })
+    }
+}
+
+impl From<Value> for ConfigTree {
+    fn from(value: Value) -> Self {
+        ConfigTree(value)
+    }
+}
+
+/// Error of reading a value from `ConfigTree`.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// There is no value at the path.
+    Missing { path: String },
+    /// The value at the path cannot be deserialized to the requested type.
+    Invalid {
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Missing { path } => write!(f, "missing config key `{}`", path),
+            ConfigError::Invalid { path, source } => {
+                write!(f, "invalid config key `{}`: {}", path, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Missing { .. } => None,
+            ConfigError::Invalid { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Type of a section of the configuration at the path `KEY`, see `ConfigTree::find`.
+pub trait ConfigSection: DeserializeOwned + Send + Sync + 'static {
+    const KEY: &'static str;
+}
+
+/// Section `T` of the configuration. It is deserialized when it is resolved for the first time,
+/// and later resolves share it, so sections that are not used are never parsed.
+///
+/// Resolving panics if the section is missing or invalid. Resolve `Result<Section<T>, ConfigError>`
+/// to handle the error, or `Option<Section<T>>` if the section is optional: it is `None` when the
+/// key is missing. To report errors of sections by `ServiceProvider::try_warm_up` instead of
+/// panicking later, add `ServiceProvider::add_validation::<Result<Section<T>, ConfigError>>` for
+/// sections whose type implements `Validate`.
+///
+/// Usage:
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use teloc::config::{ConfigError, ConfigSection, Section};
+/// use teloc::*;
+///
+/// #[derive(Deserialize)]
+/// struct DbConfig {
+///     url: String,
+/// }
+/// impl ConfigSection for DbConfig {
+///     const KEY: &'static str = "database";
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct CacheConfig {
+///     size: usize,
+/// }
+/// impl ConfigSection for CacheConfig {
+///     const KEY: &'static str = "cache";
+/// }
+///
+/// struct Database {
+///     config: Section<DbConfig>,
+/// }
+/// #[inject]
+/// impl Database {
+///     fn new(config: Section<DbConfig>) -> Self { Database { config } }
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_config(json!({ "database": { "url": "postgres://localhost/app" } }))
+///     .add_transient::<Database>();
+///
+/// let db: Database = sp.resolve();
+/// assert_eq!(db.config.url, "postgres://localhost/app");
+///
+/// let cache: Option<Section<CacheConfig>> = sp.resolve();
+/// assert!(cache.is_none());
+///
+/// let cache: Result<Section<CacheConfig>, ConfigError> = sp.resolve();
+/// assert!(matches!(cache, Err(ConfigError::Missing { .. })));
+/// ```
+#[derive(Debug)]
+pub struct Section<T>(Arc<T>);
+
+impl<T> Clone for Section<T> {
+    fn clone(&self) -> Self {
+        Section(self.0.clone())
+    }
+}
+
+impl<T: Validate> Validate for Section<T> {
+    fn validate(&self) -> Result<(), String> {
+        self.0.validate()
+    }
+}
+
+impl<T> Deref for Section<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+type Sections = Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+/// Container of the configuration tree and of its sections that are already deserialized, see
+/// `ServiceProvider::add_config`.
+pub struct ConfigContainer {
+    tree: ConfigTree,
+    sections: Arc<Sections>,
+}
+
+impl Container for ConfigContainer {
+    type Data = ConfigTree;
+
+    fn init(tree: ConfigTree) -> Self {
+        ConfigContainer {
+            tree,
+            sections: Arc::default(),
+        }
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl Clone for ConfigContainer {
+    fn clone(&self) -> Self {
+        ConfigContainer {
+            tree: self.tree.clone(),
+            sections: self.sections.clone(),
+        }
+    }
+}
+
+impl ConfigContainer {
+    #[inline]
+    pub fn get(&self) -> &ConfigTree {
+        &self.tree
+    }
+
+    /// Returns the section `T`, deserializing it on the first call.
+    pub fn section<T: ConfigSection>(&self) -> Result<Section<T>, ConfigError> {
+        // A panic while the lock is held cannot leave the map in an invalid state.
+        let mut sections = self.sections.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(section) = sections.get(&TypeId::of::<T>()) {
+            let section = section
+                .clone()
+                .downcast::<T>()
+                .expect("section of other type");
+            return Ok(Section(section));
+        }
+        let section = Arc::new(self.tree.get::<T>(T::KEY)?);
+        sections.insert(TypeId::of::<T>(), section.clone());
+        Ok(Section(section))
+    }
+}
+
+impl<'this, 'cont, SP, Index> Resolver<'this, &'cont ConfigTree, (&'cont ConfigContainer, Index)>
+    for SP
+where
+    SP: SelectContainer<'this, &'cont ConfigContainer, Index>,
+{
+    fn resolve(&'this self) -> &'cont ConfigTree {
+        self.get().get()
+    }
+}
+
+impl<'this, 'cont, T, SP, Index> Resolver<'this, Section<T>, (&'cont ConfigContainer, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont ConfigContainer, Index>,
+    T: ConfigSection,
+{
+    fn resolve(&'this self) -> Section<T> {
+        self.get().section().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<'this, 'cont, T, SP, Index>
+    Resolver<'this, Result<Section<T>, ConfigError>, (&'cont ConfigContainer, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont ConfigContainer, Index>,
+    T: ConfigSection,
+{
+    fn resolve(&'this self) -> Result<Section<T>, ConfigError> {
+        self.get().section()
+    }
+}
+
+impl<'this, 'cont, T, SP, Index>
+    Resolver<'this, Option<Section<T>>, (&'cont ConfigContainer, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont ConfigContainer, Index>,
+    T: ConfigSection,
+{
+    fn resolve(&'this self) -> Option<Section<T>> {
+        match self.get().section() {
+            Ok(section) => Some(section),
+            Err(ConfigError::Missing { .. }) => None,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<'a, SP> WarmUp<'a, SP, ()> for ConfigContainer {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized> CollectImplementors<'a, SP, Dyn, ()> for ConfigContainer {
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP> CollectOrder<'a, SP, ()> for ConfigContainer {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+/// Used by `#[derive(Dependency)]` for fields with `#[config]`. Panics with the error, because
+/// `Dependency::init` cannot fail.
+#[doc(hidden)]
+#[track_caller]
+pub fn field<T: DeserializeOwned>(config: &ConfigTree, path: &str) -> T {
+    config.get(path).unwrap_or_else(|e| panic!("{}", e))
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add the configuration tree, so fields with `#[config("key.path")]` and `Section<T>` are
+    /// read from it. `config` is usually a `serde_json::Value` parsed from a file. See the module
+    /// documentation for an example.
+    pub fn add_config(
+        self,
+        config: impl Into<ConfigTree>,
+    ) -> ServiceProvider<Parent, HCons<ConfigContainer, Conts>> {
+        self._add::<ConfigContainer>(config.into())
+    }
+}
@@ -0,0 +1,144 @@
+//! Dependency injection for message-queue workers. Enabled by the `consumer` feature.
+
+use crate::container::InstanceContainer;
+use crate::{ArcFork, ServiceProvider};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use frunk::{HCons, HNil};
+
+/// Scope that is forked from the `ServiceProvider` for every consumed message. It contains the
+/// message (delivery) itself.
+pub type ConsumerScope<SP, D> = ArcFork<SP, HCons<InstanceContainer<D>, HNil>>;
+
+/// Future of a handler of `DiConsumer` and of a job of `jobs::Scheduler`, that can borrow the scope
+/// for `'a`.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Message handler that owns the `ServiceProvider` and forks a scope for every consumed message,
+/// like web integrations do for requests. It is not bound to a particular broker: pass deliveries
+/// of `lapin`, `rdkafka` or any other client to `DiConsumer::consume`.
+///
+/// - Scope factory gets the forked scope with the delivery, and can add some local dependencies
+///   that will be unique in different messages (for example, metadata parsed from headers).
+/// - Handler gets the scope by reference and returns a boxed future, that can borrow the scope.
+///   Usually it calls an `async fn` with arguments resolved from the scope:
+///   `|scope| Box::pin(handle(scope.resolve(), scope.resolve()))`. The result of the handler
+///   cannot borrow the scope, which is dropped when the future completes.
+///
+/// Usage:
+/// ```
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Delivery { routing_key: String, data: Vec<u8> }
+///
+/// struct Repository;
+/// #[inject]
+/// impl Repository {
+///     fn new() -> Self { Repository }
+/// }
+///
+/// struct MessageLog<'a> { routing_key: &'a str }
+/// #[inject]
+/// impl<'a> MessageLog<'a> {
+///     fn new(delivery: &'a Delivery) -> Self { MessageLog { routing_key: &delivery.routing_key } }
+/// }
+///
+/// async fn handle(delivery: &Delivery, _: Arc<Repository>, log: MessageLog<'_>) -> String {
+///     format!("{}: {} bytes", log.routing_key, delivery.data.len())
+/// }
+///
+/// let sp = Arc::new(
+///     ServiceProvider::new()
+///         .add_singleton::<Arc<Repository>>()
+///         .add_transient::<MessageLog>(),
+/// );
+/// let consumer = DiConsumer::new(sp, |scope| scope, |scope| {
+///     Box::pin(handle(scope.resolve(), scope.resolve(), scope.resolve()))
+/// });
+///
+/// // In the consuming loop: `while let Some(delivery) = stream.next().await { ... }`
+/// let delivery = Delivery { routing_key: "orders".into(), data: vec![1, 2, 3] };
+/// let result = futures::executor::block_on(consumer.consume(delivery));
+/// assert_eq!(result, "orders: 3 bytes");
+/// ```
+///
+/// The result cannot borrow the scope:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// let sp = Arc::new(ServiceProvider::new());
+/// let consumer = DiConsumer::new(sp, |scope: ConsumerScope<_, u8>| scope, |scope| {
+///     Box::pin(async move { Resolver::<&u8, _>::resolve(scope) })
+/// });
+/// consumer.consume(1);
+/// ```
+pub struct DiConsumer<SP, D, ScopeFactory, F> {
+    sp: Arc<SP>,
+    scope_factory: ScopeFactory,
+    f: Arc<F>,
+    phantom: PhantomData<fn(D)>,
+}
+
+impl<ParSP, DepsSP, D, ScopeFactory, ScopeResult, F, R>
+    DiConsumer<ServiceProvider<ParSP, DepsSP>, D, ScopeFactory, F>
+where
+    ScopeFactory: Fn(ConsumerScope<ServiceProvider<ParSP, DepsSP>, D>) -> ScopeResult,
+    ScopeResult: Send + Sync + 'static,
+    F: for<'s> Fn(&'s ScopeResult) -> BoxFuture<'s, R> + Send + Sync + 'static,
+    R: 'static,
+{
+    /// Creates `DiConsumer` with specified `ServiceProvider`, scope factory and handler function.
+    pub fn new(sp: Arc<ServiceProvider<ParSP, DepsSP>>, scope_factory: ScopeFactory, f: F) -> Self {
+        DiConsumer {
+            sp,
+            scope_factory,
+            f: Arc::new(f),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Fork a new scope with the `delivery` and call the handler with it. The scope is dropped
+    /// after the returned future completes or is dropped.
+    pub fn consume(&self, delivery: D) -> ConsumeFuture<R> {
+        let scope = (self.scope_factory)(self.sp.fork_arc().add_instance(delivery));
+        ConsumeFuture::new(scope, self.f.clone())
+    }
+}
+
+impl<SP, D, ScopeFactory: Clone, F> Clone for DiConsumer<SP, D, ScopeFactory, F> {
+    fn clone(&self) -> Self {
+        DiConsumer {
+            sp: self.sp.clone(),
+            scope_factory: self.scope_factory.clone(),
+            f: self.f.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Future returned by `DiConsumer::consume`. Owns the message scope while the handler runs.
+pub struct ConsumeFuture<R>(BoxFuture<'static, R>);
+
+impl<R: 'static> ConsumeFuture<R> {
+    pub(crate) fn new<S, F>(scope: S, f: Arc<F>) -> Self
+    where
+        S: Send + Sync + 'static,
+        F: for<'s> Fn(&'s S) -> BoxFuture<'s, R> + Send + Sync + 'static,
+    {
+        ConsumeFuture(Box::pin(async move { f(&scope).await }))
+    }
+}
+
+impl<R> Future for ConsumeFuture<R> {
+    type Output = R;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        self.0.as_mut().poll(cx)
+    }
+}
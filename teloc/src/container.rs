@@ -1,10 +1,24 @@
 use crate::dependency::DependencyClone;
-use crate::get_dependencies::GetDependencies;
+use crate::get_dependencies::{FactoryArgs, GetDependencies};
 use crate::service_provider::SelectContainer;
 use crate::{Dependency, Resolver};
+use alloc::boxed::Box;
+#[cfg(feature = "shared-singletons")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 use frunk::HNil;
+#[cfg(feature = "sync")]
 use once_cell::sync::OnceCell;
-use std::marker::PhantomData;
+#[cfg(not(feature = "sync"))]
+use once_cell::unsync::OnceCell;
+
+// With the `shared-singletons` feature clones of a container point to the same cell, so a singleton
+// is created once for all clones of the `ServiceProvider`.
+#[cfg(feature = "shared-singletons")]
+pub(crate) type SingletonCell<T> = Arc<OnceCell<T>>;
+#[cfg(not(feature = "shared-singletons"))]
+pub(crate) type SingletonCell<T> = OnceCell<T>;
 
 /// Init is a trait used in [`ServiceProvider`] for create an empty version of `Container`. If you
 /// create your own version of container and you want that it can work with other container like
@@ -41,7 +55,7 @@ where
     }
 }
 impl<'this, 'cont, T, SP, Index, Deps, Infer>
-    Resolver<'this, &'cont TransientContainer<T>, T, (Index, Deps, Infer)> for SP
+    Resolver<'this, T, (&'cont TransientContainer<T>, Index, Deps, Infer)> for SP
 where
     SP: SelectContainer<'this, &'cont TransientContainer<T>, Index>
         + GetDependencies<'this, Deps, Infer>,
@@ -54,12 +68,12 @@ where
 }
 
 #[derive(Debug)]
-pub struct SingletonContainer<T>(OnceCell<T>);
+pub struct SingletonContainer<T>(SingletonCell<T>);
 impl<T> Container for SingletonContainer<T> {
     type Data = ();
 
     fn init(_: ()) -> Self {
-        Self(OnceCell::new())
+        Self(SingletonCell::default())
     }
 }
 impl<'a, T, Deps> ResolveContainer<'a, &'a T, Deps> for SingletonContainer<T>
@@ -72,7 +86,7 @@ where
 }
 
 impl<'this, 'cont, T, SP, Index, Deps, Infer>
-    Resolver<'this, &'cont SingletonContainer<T>, T, (Index, Deps, Infer)> for SP
+    Resolver<'this, T, (&'cont SingletonContainer<T>, Index, Deps, Infer)> for SP
 where
     SP: GetDependencies<'this, Deps, Infer>
         + SelectContainer<'this, &'cont SingletonContainer<T>, Index>,
@@ -85,7 +99,7 @@ where
     }
 }
 impl<'this, 'cont, T, SP, Index, Deps, Infer>
-    Resolver<'this, &'cont SingletonContainer<T>, &'cont T, (Index, Deps, Infer)> for SP
+    Resolver<'this, &'cont T, (&'cont SingletonContainer<T>, Index, Deps, Infer)> for SP
 where
     SP: GetDependencies<'this, Deps, Infer>
         + SelectContainer<'this, &'cont SingletonContainer<T>, Index>,
@@ -118,7 +132,7 @@ impl<'a, T> ResolveContainer<'a, &'a T, HNil> for InstanceContainer<T> {
         &ct.0
     }
 }
-impl<'this, 'cont, T, SP, Index> Resolver<'this, &'cont InstanceContainer<T>, T, Index> for SP
+impl<'this, 'cont, T, SP, Index> Resolver<'this, T, (&'cont InstanceContainer<T>, Index)> for SP
 where
     SP: SelectContainer<'this, &'cont InstanceContainer<T>, Index>,
     InstanceContainer<T>: ResolveContainer<'cont, &'cont T, HNil>,
@@ -128,7 +142,7 @@ where
         InstanceContainer::resolve_container(self.get(), || HNil).clone()
     }
 }
-impl<'this, 'cont, T, SP, Index> Resolver<'this, &'cont InstanceContainer<T>, &'cont T, Index>
+impl<'this, 'cont, T, SP, Index> Resolver<'this, &'cont T, (&'cont InstanceContainer<T>, Index)>
     for SP
 where
     SP: SelectContainer<'this, &'cont InstanceContainer<T>, Index>,
@@ -145,6 +159,170 @@ impl<T> InstanceContainer<T> {
     }
 }
 
+/// Container of a collection of instances, that is resolved as a slice, see
+/// `ServiceProvider::add_instances`.
+#[derive(Debug)]
+pub struct InstancesContainer<T>(Vec<T>);
+impl<T> Container for InstancesContainer<T> {
+    type Data = Vec<T>;
+
+    fn init(instances: Vec<T>) -> Self {
+        Self(instances)
+    }
+}
+impl<'this, 'cont, T, SP, Index> Resolver<'this, &'cont [T], (&'cont InstancesContainer<T>, Index)>
+    for SP
+where
+    SP: SelectContainer<'this, &'cont InstancesContainer<T>, Index>,
+{
+    fn resolve(&'this self) -> &'cont [T] {
+        self.get().get()
+    }
+}
+impl<T> InstancesContainer<T> {
+    #[inline]
+    pub fn get(&self) -> &[T] {
+        &self.0
+    }
+
+    pub(crate) fn extend(&mut self, instances: impl IntoIterator<Item = T>) {
+        self.0.extend(instances)
+    }
+}
+
+/// Factory stored by `TransientFactoryContainer` and `SingletonFactoryContainer` by default. Boxing
+/// hides the type of the closure, so types of providers with factories can be written by hand.
+pub type BoxedFactory<Deps, T> = Box<dyn Fn(Deps) -> T>;
+
+pub struct TransientFactoryContainer<Deps, T, F = BoxedFactory<Deps, T>>(
+    F,
+    PhantomData<fn(Deps) -> T>,
+);
+impl<Deps, T, F> Container for TransientFactoryContainer<Deps, T, F> {
+    type Data = F;
+
+    fn init(f: F) -> Self {
+        Self(f, PhantomData)
+    }
+}
+impl<'a, Deps, T, F> ResolveContainer<'a, T, Deps> for TransientFactoryContainer<Deps, T, F>
+where
+    F: Fn(Deps) -> T,
+{
+    fn resolve_container<G: Fn() -> Deps>(ct: &'a Self, get_deps: G) -> T {
+        (ct.0)(get_deps())
+    }
+}
+impl<'this, 'cont, Deps, T, F, SP, Index, Infer>
+    Resolver<
+        'this,
+        T,
+        (
+            &'cont TransientFactoryContainer<Deps, T, F>,
+            Index,
+            Deps,
+            Infer,
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont TransientFactoryContainer<Deps, T, F>, Index>
+        + GetDependencies<'this, Deps::List, Infer>,
+    Deps: FactoryArgs,
+    F: Fn(Deps) -> T + 'cont,
+    Deps: 'cont,
+    T: 'cont,
+{
+    fn resolve(&'this self) -> T {
+        TransientFactoryContainer::resolve_container(self.get(), || {
+            Deps::from_list(self.get_deps())
+        })
+    }
+}
+impl<Deps, T, F> TransientFactoryContainer<Deps, T, F> {
+    #[inline]
+    pub fn get(&self) -> &F {
+        &self.0
+    }
+}
+
+pub struct SingletonFactoryContainer<Deps, T, F = BoxedFactory<Deps, T>>(
+    F,
+    SingletonCell<T>,
+    PhantomData<fn(Deps)>,
+);
+impl<Deps, T, F> Container for SingletonFactoryContainer<Deps, T, F> {
+    type Data = F;
+
+    fn init(f: F) -> Self {
+        Self(f, SingletonCell::default(), PhantomData)
+    }
+}
+impl<'a, Deps, T, F> ResolveContainer<'a, &'a T, Deps> for SingletonFactoryContainer<Deps, T, F>
+where
+    F: Fn(Deps) -> T,
+{
+    fn resolve_container<G: Fn() -> Deps>(ct: &'a Self, get_deps: G) -> &'a T {
+        ct.1.get_or_init(|| (ct.0)(get_deps()))
+    }
+}
+impl<'this, 'cont, Deps, T, F, SP, Index, Infer>
+    Resolver<
+        'this,
+        T,
+        (
+            &'cont SingletonFactoryContainer<Deps, T, F>,
+            Index,
+            Deps,
+            Infer,
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont SingletonFactoryContainer<Deps, T, F>, Index>
+        + GetDependencies<'this, Deps::List, Infer>,
+    Deps: FactoryArgs,
+    F: Fn(Deps) -> T + 'cont,
+    T: DependencyClone + 'cont,
+    Deps: 'cont,
+{
+    fn resolve(&'this self) -> T {
+        SingletonFactoryContainer::resolve_container(self.get(), || {
+            Deps::from_list(self.get_deps())
+        })
+        .clone()
+    }
+}
+impl<'this, 'cont, Deps, T, F, SP, Index, Infer>
+    Resolver<
+        'this,
+        &'cont T,
+        (
+            &'cont SingletonFactoryContainer<Deps, T, F>,
+            Index,
+            Deps,
+            Infer,
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont SingletonFactoryContainer<Deps, T, F>, Index>
+        + GetDependencies<'this, Deps::List, Infer>,
+    Deps: FactoryArgs,
+    F: Fn(Deps) -> T + 'cont,
+    T: 'cont,
+    Deps: 'cont,
+{
+    fn resolve(&'this self) -> &'cont T {
+        SingletonFactoryContainer::resolve_container(self.get(), || {
+            Deps::from_list(self.get_deps())
+        })
+    }
+}
+impl<Deps, T, F> SingletonFactoryContainer<Deps, T, F> {
+    #[inline]
+    pub fn get(&self) -> &F {
+        &self.0
+    }
+}
+
 pub struct ConvertContainer<Cont, T, U>(Cont, PhantomData<(T, U)>);
 impl<Cont, T, U> Container for ConvertContainer<Cont, T, U>
 where
@@ -166,7 +344,7 @@ where
     }
 }
 impl<'this, 'cont, Cont, T, U, SP, Index, Deps, Infer>
-    Resolver<'this, &'cont ConvertContainer<Cont, T, U>, U, (Index, Deps, Infer)> for SP
+    Resolver<'this, U, (&'cont ConvertContainer<Cont, T, U>, Index, Deps, Infer)> for SP
 where
     SP: SelectContainer<'this, &'cont ConvertContainer<Cont, T, U>, Index>
         + GetDependencies<'this, Deps, Infer>,
@@ -186,3 +364,97 @@ impl<Cont, ContT, T> ConvertContainer<Cont, ContT, T> {
         &self.0
     }
 }
+
+/// Function stored by `MapContainer` by default.
+pub type BoxedMap<T, U> = Box<dyn Fn(T) -> U>;
+
+/// Same as `ConvertContainer`, but converts the service by the function given at the registration
+/// instead of the `Into` trait.
+pub struct MapContainer<Cont, T, U, F = BoxedMap<T, U>>(Cont, F, PhantomData<fn(T) -> U>);
+impl<Cont, T, U, F> Container for MapContainer<Cont, T, U, F>
+where
+    Cont: Container,
+{
+    type Data = (Cont::Data, F);
+
+    fn init((data, f): Self::Data) -> Self {
+        Self(Cont::init(data), f, PhantomData)
+    }
+}
+impl<'a, Cont, T, U, F, Deps> ResolveContainer<'a, U, Deps> for MapContainer<Cont, T, U, F>
+where
+    Cont: ResolveContainer<'a, T, Deps>,
+    F: Fn(T) -> U,
+{
+    fn resolve_container<G: Fn() -> Deps>(ct: &'a Self, deps: G) -> U {
+        (ct.1)(Cont::resolve_container(&ct.0, deps))
+    }
+}
+impl<'this, 'cont, Cont, T, U, F, SP, Index, Deps, Infer>
+    Resolver<'this, U, (&'cont MapContainer<Cont, T, U, F>, Index, Deps, Infer)> for SP
+where
+    SP: SelectContainer<'this, &'cont MapContainer<Cont, T, U, F>, Index>
+        + GetDependencies<'this, Deps, Infer>,
+    MapContainer<Cont, T, U, F>: ResolveContainer<'cont, U, Deps>,
+    Cont: 'cont,
+    T: 'cont,
+    U: 'cont,
+    F: 'cont,
+    Deps: 'cont,
+{
+    fn resolve(&'this self) -> U {
+        MapContainer::resolve_container(self.get(), || self.get_deps())
+    }
+}
+impl<Cont, T, U, F> MapContainer<Cont, T, U, F> {
+    #[inline]
+    pub fn get(&self) -> &Cont {
+        &self.0
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+mod shared {
+    use super::*;
+
+    impl<T> Clone for TransientContainer<T> {
+        fn clone(&self) -> Self {
+            Self(PhantomData)
+        }
+    }
+    impl<T> Clone for SingletonContainer<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: Clone> Clone for InstanceContainer<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: Clone> Clone for InstancesContainer<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<Deps, T, F: Clone> Clone for TransientFactoryContainer<Deps, T, F> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone(), PhantomData)
+        }
+    }
+    impl<Deps, T, F: Clone> Clone for SingletonFactoryContainer<Deps, T, F> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone(), self.1.clone(), PhantomData)
+        }
+    }
+    impl<Cont: Clone, T, U> Clone for ConvertContainer<Cont, T, U> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone(), PhantomData)
+        }
+    }
+    impl<Cont: Clone, T, U, F: Clone> Clone for MapContainer<Cont, T, U, F> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone(), self.1.clone(), PhantomData)
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::container::Container;
+use crate::dependency::DependencyClone;
+use crate::service_provider::SelectContainer;
+use crate::warm_up::WarmUp;
+use crate::Resolver;
+use once_cell::sync::OnceCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Handle to a value that will be available later, after `ServiceProvider::warm_up` will be
+/// called. It lets services that are constructed early hold a handle to a service that is
+/// initialized late, without cycles or `Option` fields.
+///
+/// Register it using `ServiceProvider::add_deferred`. `Deferred` can be cloned, all clones share
+/// the same value. It is a future that completes when the value will be set.
+///
+/// Usage:
+/// ```
+/// use std::rc::Rc;
+/// use teloc::*;
+///
+/// struct Cache { size: usize }
+/// #[inject]
+/// impl Cache {
+///     fn new() -> Self { Cache { size: 64 } }
+/// }
+///
+/// struct Scheduler { cache: Deferred<Rc<Cache>> }
+/// #[inject]
+/// impl Scheduler {
+///     fn new(cache: Deferred<Rc<Cache>>) -> Self { Scheduler { cache } }
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_singleton::<Rc<Cache>>()
+///     .add_deferred::<Rc<Cache>>()
+///     .add_transient::<Scheduler>();
+///
+/// let scheduler: Scheduler = sp.resolve();
+/// assert!(scheduler.cache.get().is_none());
+///
+/// sp.warm_up();
+/// assert_eq!(scheduler.cache.get().unwrap().size, 64);
+/// let cache = futures::executor::block_on(scheduler.cache.clone());
+/// assert_eq!(cache.size, 64);
+/// ```
+pub struct Deferred<T>(Arc<Shared<T>>);
+
+struct Shared<T> {
+    value: OnceCell<T>,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl<T> Deferred<T> {
+    /// Create an empty handle.
+    pub fn new() -> Self {
+        Deferred(Arc::new(Shared {
+            value: OnceCell::new(),
+            wakers: Mutex::new(vec![]),
+        }))
+    }
+
+    /// Get the value if it was already set.
+    pub fn get(&self) -> Option<&T> {
+        self.0.value.get()
+    }
+
+    /// Set the value and wake all tasks waiting for it. Returns the value back if it was already
+    /// set.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.0.value.set(value)?;
+        let wakers = std::mem::take(&mut *self.0.wakers.lock().unwrap_or_else(|e| e.into_inner()));
+        wakers.into_iter().for_each(Waker::wake);
+        Ok(())
+    }
+}
+
+impl<T> Default for Deferred<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Deferred<T> {
+    fn clone(&self) -> Self {
+        Deferred(self.0.clone())
+    }
+}
+
+impl<T> DependencyClone for Deferred<T> {}
+
+impl<T: Clone> Future for Deferred<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        if let Some(value) = self.get() {
+            return Poll::Ready(value.clone());
+        }
+        let mut wakers = self.0.wakers.lock().unwrap_or_else(|e| e.into_inner());
+        // Value can be set between the check above and the lock.
+        match self.get() {
+            Some(value) => Poll::Ready(value.clone()),
+            None => {
+                wakers.push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Container that holds a `Deferred<T>` and sets it during `ServiceProvider::warm_up`.
+pub struct DeferredContainer<T>(Deferred<T>);
+
+impl<T> Container for DeferredContainer<T> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(Deferred::new())
+    }
+}
+
+impl<T> DeferredContainer<T> {
+    #[inline]
+    pub fn get(&self) -> &Deferred<T> {
+        &self.0
+    }
+}
+
+impl<'this, 'cont, T, SP, Index> Resolver<'this, Deferred<T>, (&'cont DeferredContainer<T>, Index)>
+    for SP
+where
+    SP: SelectContainer<'this, &'cont DeferredContainer<T>, Index>,
+    T: 'cont,
+{
+    fn resolve(&'this self) -> Deferred<T> {
+        self.get().get().clone()
+    }
+}
+
+impl<'this, 'cont, T, SP, Index>
+    Resolver<'this, &'cont Deferred<T>, (&'cont DeferredContainer<T>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont DeferredContainer<T>, Index>,
+{
+    fn resolve(&'this self) -> &'cont Deferred<T> {
+        self.get().get()
+    }
+}
+
+impl<'a, SP, T, Infer> WarmUp<'a, SP, Infer> for DeferredContainer<T>
+where
+    SP: Resolver<'a, T, Infer>,
+{
+    fn warm_up(&'a self, sp: &'a SP) {
+        if self.0.get().is_none() {
+            let _ = self.0.set(sp.resolve());
+        }
+    }
+}
@@ -1,16 +1,49 @@
-use std::cell::RefCell;
-use std::rc::Rc;
-use std::sync::Arc;
+use crate::container::Container;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::cell::RefCell;
 
 /// Trait is used to working with `Resolver` trait. If you want that your service can be resolved by
 /// `Resolver`, you may implement this trait for your service. There are three ways:
 /// 1. Implement it by yourself. Not recommended for production code, recommended for library code.
 /// 2. Create a constructor and add `#[inject]` macro. Recommended for production code.
-/// 3. Derive `Teloc` macro, when all of your fields of structs implement `Dependency`.
+/// 3. Derive `Dependency` macro, when all of your fields of structs implement `Dependency`.
 pub trait Dependency<Deps> {
     fn init(deps: Deps) -> Self;
 }
 
+/// Lifetime of a service declared next to the type, used by `ServiceProvider::add_auto`. Usually
+/// it is implemented by `#[derive(Dependency)]` with `#[dependency(lifetime = "singleton")]` or
+/// `#[dependency(lifetime = "transient")]` attribute.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// #[derive(Dependency)]
+/// #[dependency(lifetime = "singleton")]
+/// struct Settings;
+///
+/// #[derive(Dependency)]
+/// #[dependency(lifetime = "transient")]
+/// struct Handler<'a> {
+///     settings: &'a Settings,
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_auto::<Settings>()
+///     .add_auto::<Handler>();
+///
+/// let handler: Handler = sp.resolve();
+/// let settings: &Settings = sp.resolve();
+/// assert!(core::ptr::eq(handler.settings, settings));
+/// ```
+pub trait AutoLifetime {
+    /// Container in which the service is registered, for example `SingletonContainer<Self>`.
+    type Container: Container<Data = ()>;
+}
+
 impl<Deps, D> Dependency<Deps> for Rc<D>
 where
     D: Dependency<Deps>,
@@ -44,13 +77,106 @@ where
     }
 }
 
+/// Trait is used to fill fields of a value that was created outside of the container (deserialized,
+/// created by FFI, etc.). Derive `InjectFields` macro and mark fields with `#[injected]` attribute,
+/// then call `ServiceProvider::inject`. Field of type `Option<T>` is filled with `Some`, other
+/// fields are overwritten.
+///
+/// Example:
+/// ```
+/// use teloc::*;
+///
+/// struct Config { greeting: &'static str }
+///
+/// #[derive(Default, InjectFields)]
+/// struct Handler<'a> {
+///     name: String,
+///     #[injected]
+///     config: Option<&'a Config>,
+/// }
+///
+/// let sp = ServiceProvider::new().add_instance(Config { greeting: "Hello" });
+///
+/// let mut handler = Handler { name: "world".into(), ..Default::default() };
+/// sp.inject(&mut handler);
+/// assert_eq!(handler.config.unwrap().greeting, "Hello");
+/// ```
+pub trait InjectFields<'a, SP, Infer> {
+    fn inject_fields(&mut self, sp: &'a SP);
+}
+
 /// Trait is used to resolve services by cloning. It must be implement only for wrappers that
 /// guarantees that there are only one instance and many references, like `Rc`, `Arc` structs and
-/// immutable reference.
+/// immutable reference, or for small `Copy` values, for which a copy is as good as the original.
+///
+/// It is implemented for `Rc`, `Arc`, references, primitive types, `Duration` and network
+/// addresses. It cannot be implemented for all `Copy` types, because it would conflict with the
+/// implementations for `Rc` and `Arc`, so implement it by yourself for your own `Copy` types.
+///
+/// Example:
+/// ```
+/// use std::net::SocketAddr;
+/// use teloc::*;
+///
+/// #[derive(Clone, Copy)]
+/// struct Port(u16);
+/// impl DependencyClone for Port {}
+///
+/// let sp = ServiceProvider::new()
+///     .add_instance(Port(8080))
+///     .add_instance(3u32)
+///     .add_instance(SocketAddr::from(([127, 0, 0, 1], 8080)));
+///
+/// let port: Port = sp.resolve();
+/// let retries: u32 = sp.resolve();
+/// let addr: SocketAddr = sp.resolve();
+/// assert_eq!((port.0, retries, addr.port()), (8080, 3, 8080));
+/// ```
 pub trait DependencyClone: Clone {}
 
-impl<D> DependencyClone for Rc<D> {}
+impl<D: ?Sized> DependencyClone for Rc<D> {}
 
-impl<D> DependencyClone for Arc<D> {}
+impl<D: ?Sized> DependencyClone for Arc<D> {}
+
+impl<D: ?Sized> DependencyClone for &D {}
+
+macro_rules! impl_dependency_clone {
+    ($($ty:ty),*) => {
+        $(
+        impl DependencyClone for $ty {}
+        )*
+    };
+}
 
-impl<D> DependencyClone for &D {}
+impl_dependency_clone!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    core::num::NonZeroU8,
+    core::num::NonZeroU16,
+    core::num::NonZeroU32,
+    core::num::NonZeroU64,
+    core::num::NonZeroU128,
+    core::num::NonZeroUsize,
+    core::time::Duration,
+    core::net::IpAddr,
+    core::net::Ipv4Addr,
+    core::net::Ipv6Addr,
+    core::net::SocketAddr,
+    core::net::SocketAddrV4,
+    core::net::SocketAddrV6
+);
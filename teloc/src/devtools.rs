@@ -0,0 +1,596 @@
+//! Tools for debugging wiring of services. Enabled by the `devtools` feature.
+//!
+//! `ScopeRecording` captures inputs of a request scope: headers, hash of the body and instances
+//! that can be serialized. Recording can be saved (it implements `Serialize` and `Deserialize`)
+//! and replayed later to build an equivalent scope against the current wiring of the provider.
+//!
+//! `Manifest` lists services registered in a provider with their lifetimes and dependencies, so
+//! wiring of two releases can be compared by deployment tooling.
+
+use crate::async_factory::AsyncTransientFactoryContainer;
+use crate::cleanup::OnDropContainer;
+use crate::conditional::ConditionalSingletonContainer;
+use crate::container::{
+    ConvertContainer, InstanceContainer, InstancesContainer, MapContainer, SingletonContainer,
+    SingletonFactoryContainer, TransientContainer, TransientFactoryContainer,
+};
+use crate::deferred::DeferredContainer;
+use crate::forbid::ForbiddenContainer;
+use crate::get_dependencies::FactoryArgs;
+use crate::graph::{GraphCache, GraphScopedContainer};
+use crate::implementors::ImplementorContainer;
+use crate::keyed::KeyedMapContainer;
+use crate::later::CyclicSingletonContainer;
+use crate::open_generic::OpenGenericContainer;
+use crate::scope_handle::ScopeHandleContainer;
+use crate::startup_order::OrderContainer;
+use crate::validate::ValidateContainer;
+use crate::{Dependency, ServiceProvider};
+use frunk::hlist::HList;
+use frunk::{HCons, HNil};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::any::type_name;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Headers whose values are not recorded by `ScopeRecording::record_header`.
+pub const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Value of recorded headers from `REDACTED_HEADERS`.
+pub const REDACTED: &str = "[redacted]";
+
+/// Recorded inputs of a scope.
+///
+/// Usage:
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use teloc::devtools::ScopeRecording;
+/// use teloc::*;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct UserId(u64);
+///
+/// struct Greeter<'a> { user: &'a UserId }
+/// #[inject]
+/// impl<'a> Greeter<'a> {
+///     fn new(user: &'a UserId) -> Self { Self { user } }
+/// }
+///
+/// let mut recording = ScopeRecording::new();
+/// recording.record_header("x-request-id", "42");
+/// recording.record_body(b"{}");
+/// recording.record_instance(&UserId(7)).unwrap();
+/// let saved = serde_json::to_string(&recording).unwrap();
+///
+/// // Later, possibly on another machine.
+/// let recording: ScopeRecording = serde_json::from_str(&saved).unwrap();
+/// let sp = ServiceProvider::new().add_transient::<Greeter>();
+/// let scope = sp.fork().add_replayed_instance::<UserId>(&recording).unwrap();
+///
+/// let greeter: Greeter = scope.resolve();
+/// assert_eq!(greeter.user.0, 7);
+/// assert!(recording.matches_body(b"{}"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScopeRecording {
+    headers: Vec<(String, String)>,
+    body_hash: Option<u64>,
+    instances: BTreeMap<String, serde_json::Value>,
+}
+
+impl ScopeRecording {
+    /// Create an empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a header of the request. Values of headers with credentials (`REDACTED_HEADERS`)
+    /// are replaced by `REDACTED`, so recordings can be saved and shared.
+    pub fn record_header(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        let name = name.into();
+        let redacted = REDACTED_HEADERS
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(&name));
+        let value = if redacted {
+            String::from(REDACTED)
+        } else {
+            value.into()
+        };
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Record a hash of the request body. The body itself is not stored.
+    pub fn record_body(&mut self, body: &[u8]) -> &mut Self {
+        self.body_hash = Some(hash_body(body));
+        self
+    }
+
+    /// Record a scope instance. Instances are keyed by type name, so recording an instance of the
+    /// same type twice overwrites the previous one.
+    pub fn record_instance<T: Serialize>(
+        &mut self,
+        instance: &T,
+    ) -> Result<&mut Self, serde_json::Error> {
+        let value = serde_json::to_value(instance)?;
+        self.instances.insert(type_name::<T>().to_string(), value);
+        Ok(self)
+    }
+
+    /// Recorded headers in order of recording.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Recorded hash of the body, if any.
+    pub fn body_hash(&self) -> Option<u64> {
+        self.body_hash
+    }
+
+    /// Checks that the body has the same hash as the recorded one.
+    pub fn matches_body(&self, body: &[u8]) -> bool {
+        self.body_hash == Some(hash_body(body))
+    }
+
+    /// Deserialize the recorded instance of type `T`.
+    pub fn instance<T: DeserializeOwned>(&self) -> Result<T, ReplayError> {
+        let value = self
+            .instances
+            .get(type_name::<T>())
+            .ok_or_else(|| ReplayError::Missing(type_name::<T>()))?;
+        T::deserialize(value).map_err(ReplayError::Deserialize)
+    }
+}
+
+/// Error returned when a recorded instance cannot be replayed.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// Instance of the type was not recorded.
+    Missing(&'static str),
+    /// Recorded instance does not match the current definition of the type.
+    Deserialize(serde_json::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Missing(ty) => write!(f, "instance of `{}` was not recorded", ty),
+            ReplayError::Deserialize(e) => write!(f, "cannot deserialize recorded instance: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReplayError::Missing(_) => None,
+            ReplayError::Deserialize(e) => Some(e),
+        }
+    }
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add an instance of `T` deserialized from the recording. For more information see
+    /// `ScopeRecording`.
+    pub fn add_replayed_instance<T>(
+        self,
+        recording: &ScopeRecording,
+    ) -> Result<ServiceProvider<Parent, HCons<InstanceContainer<T>, Conts>>, ReplayError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.add_instance(recording.instance::<T>()?))
+    }
+}
+
+/// Services registered in a provider, created by `ServiceProvider::manifest`. It can be serialized,
+/// for example to JSON, and compared with the manifest of the previous release.
+///
+/// Usage:
+/// ```
+/// use std::rc::Rc;
+/// use teloc::devtools::{LifetimeKind, Manifest, ServiceEntry};
+/// use teloc::*;
+///
+/// #[derive(Dependency)]
+/// struct Config;
+///
+/// #[derive(Dependency)]
+/// struct Repo {
+///     config: Rc<Config>,
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_instance(8080u16)
+///     .add_singleton::<Rc<Config>>()
+///     .add_transient::<Repo>();
+///
+/// let manifest = sp.manifest();
+/// assert_eq!(
+///     manifest.services[2],
+///     ServiceEntry {
+///         type_name: std::any::type_name::<Repo>().to_string(),
+///         lifetime: LifetimeKind::Transient,
+///         dependencies: vec![std::any::type_name::<Rc<Config>>().to_string()],
+///     }
+/// );
+///
+/// let saved = serde_json::to_string(&manifest).unwrap();
+/// let previous: Manifest = serde_json::from_str(&saved).unwrap();
+/// assert_eq!(previous, manifest);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Services in order of registration.
+    pub services: Vec<ServiceEntry>,
+}
+
+/// Service in the `Manifest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceEntry {
+    /// Name of the type of the service, as returned by `std::any::type_name`.
+    pub type_name: String,
+    /// Lifetime of the service.
+    pub lifetime: LifetimeKind,
+    /// Names of the types of the dependencies, in order of declaration.
+    pub dependencies: Vec<String>,
+}
+
+/// Lifetime of a service in the `Manifest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifetimeKind {
+    /// Registered by `add_transient` or `add_transient_factory`.
+    Transient,
+    /// Registered by `add_singleton`, `add_singleton_factory` or `add_singleton_if`.
+    Singleton,
+    /// Registered by `add_instance`.
+    Instance,
+    /// Registered by `add_graph_scoped`.
+    GraphScoped,
+    /// Registered by `add_deferred`.
+    Deferred,
+}
+
+impl ServiceEntry {
+    fn new<T: ?Sized>(lifetime: LifetimeKind, dependencies: Vec<String>) -> Self {
+        ServiceEntry {
+            type_name: type_name::<T>().to_string(),
+            lifetime,
+            dependencies,
+        }
+    }
+}
+
+/// The trait, used by `ServiceProvider::manifest` to describe containers. Implement it for your own
+/// container if you wish that providers with it can be described.
+pub trait Describe<Infer> {
+    fn describe(services: &mut Vec<ServiceEntry>);
+}
+
+/// The trait, used for getting names of dependencies from a `HList` of their types. Do not use it
+/// by yourself.
+pub trait DependencyNames {
+    fn names(names: &mut Vec<String>);
+}
+
+impl DependencyNames for HNil {
+    fn names(_: &mut Vec<String>) {}
+}
+
+impl<H, Tail: DependencyNames> DependencyNames for HCons<H, Tail> {
+    fn names(names: &mut Vec<String>) {
+        names.push(type_name::<H>().to_string());
+        Tail::names(names);
+    }
+}
+
+fn names<Deps: DependencyNames>() -> Vec<String> {
+    let mut names = vec![];
+    Deps::names(&mut names);
+    names
+}
+
+impl Describe<HNil> for HNil {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl<H, Tail, InferH, InferTail> Describe<HCons<InferH, InferTail>> for HCons<H, Tail>
+where
+    H: Describe<InferH>,
+    Tail: Describe<InferTail>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        // The head is the last registered container.
+        Tail::describe(services);
+        H::describe(services);
+    }
+}
+
+impl<T, Deps: DependencyNames> Describe<Deps> for TransientContainer<T>
+where
+    T: Dependency<Deps>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Transient,
+            names::<Deps>(),
+        ));
+    }
+}
+
+impl<T, Deps: DependencyNames> Describe<Deps> for SingletonContainer<T>
+where
+    T: Dependency<Deps>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Singleton,
+            names::<Deps>(),
+        ));
+    }
+}
+
+impl<T, Deps: DependencyNames> Describe<Deps> for CyclicSingletonContainer<T>
+where
+    T: Dependency<Deps>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Singleton,
+            names::<Deps>(),
+        ));
+    }
+}
+
+impl<T, Deps: DependencyNames> Describe<Deps> for GraphScopedContainer<T>
+where
+    T: Dependency<Deps>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::GraphScoped,
+            names::<Deps>(),
+        ));
+    }
+}
+
+#[cfg(feature = "watch")]
+impl<T> Describe<()> for crate::watch::WatchContainer<T> {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<crate::watch::Watch<T>>(
+            LifetimeKind::Instance,
+            vec![],
+        ));
+    }
+}
+
+impl<Dyn: ?Sized, Entries> Describe<()> for KeyedMapContainer<Dyn, Entries> {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<HashMap<String, Box<Dyn>>>(
+            LifetimeKind::Transient,
+            vec![],
+        ));
+    }
+}
+
+// Members of the family are not known, so the family is listed instead.
+impl<Family> Describe<()> for OpenGenericContainer<Family> {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<Family>(LifetimeKind::Transient, vec![]));
+    }
+}
+
+#[cfg(feature = "config")]
+impl Describe<()> for crate::config::ConfigContainer {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<crate::config::ConfigTree>(
+            LifetimeKind::Instance,
+            vec![],
+        ));
+    }
+}
+
+impl<T> Describe<()> for InstanceContainer<T> {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(LifetimeKind::Instance, vec![]));
+    }
+}
+
+impl<T> Describe<()> for InstancesContainer<T> {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<[T]>(LifetimeKind::Instance, vec![]));
+    }
+}
+
+impl<T> Describe<()> for DeferredContainer<T> {
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<crate::Deferred<T>>(
+            LifetimeKind::Deferred,
+            vec![type_name::<T>().to_string()],
+        ));
+    }
+}
+
+impl<Deps, T, F> Describe<()> for TransientFactoryContainer<Deps, T, F>
+where
+    Deps: FactoryArgs,
+    Deps::List: DependencyNames,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Transient,
+            names::<Deps::List>(),
+        ));
+    }
+}
+
+impl<Deps, T, F> Describe<()> for AsyncTransientFactoryContainer<Deps, T, F>
+where
+    Deps: FactoryArgs,
+    Deps::List: DependencyNames,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Transient,
+            names::<Deps::List>(),
+        ));
+    }
+}
+
+impl<Deps, T, F> Describe<()> for SingletonFactoryContainer<Deps, T, F>
+where
+    Deps: FactoryArgs,
+    Deps::List: DependencyNames,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Singleton,
+            names::<Deps::List>(),
+        ));
+    }
+}
+
+impl<DepsA, DepsB, T, A, B> Describe<()> for ConditionalSingletonContainer<DepsA, DepsB, T, A, B>
+where
+    DepsA: FactoryArgs,
+    DepsA::List: DependencyNames,
+    DepsB: FactoryArgs,
+    DepsB::List: DependencyNames,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        // Both factories are listed, the predicate is known only at runtime.
+        let mut dependencies = names::<DepsA::List>();
+        dependencies.extend(names::<DepsB::List>());
+        services.push(ServiceEntry::new::<T>(
+            LifetimeKind::Singleton,
+            dependencies,
+        ));
+    }
+}
+
+impl<Types> Describe<()> for ScopeHandleContainer<Types>
+where
+    Types: FactoryArgs,
+    Types::List: DependencyNames,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        services.push(ServiceEntry::new::<crate::ScopeHandle<'static>>(
+            LifetimeKind::Transient,
+            names::<Types::List>(),
+        ));
+    }
+}
+
+impl<Cont, T, U, Infer> Describe<Infer> for ConvertContainer<Cont, T, U>
+where
+    Cont: Describe<Infer>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        describe_as::<Cont, U, Infer>(services);
+    }
+}
+
+impl<Cont, T, U, F, Infer> Describe<Infer> for MapContainer<Cont, T, U, F>
+where
+    Cont: Describe<Infer>,
+{
+    fn describe(services: &mut Vec<ServiceEntry>) {
+        describe_as::<Cont, U, Infer>(services);
+    }
+}
+
+// Describes the inner container of a conversion, but with the type of the converted service.
+fn describe_as<Cont: Describe<Infer>, U, Infer>(services: &mut Vec<ServiceEntry>) {
+    Cont::describe(services);
+    if let Some(service) = services.last_mut() {
+        service.type_name = type_name::<U>().to_string();
+    }
+}
+
+// Not services, so they are not listed.
+impl<T, Reason> Describe<()> for ForbiddenContainer<T, Reason> {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl Describe<()> for GraphCache {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl<Dyn: ?Sized, T> Describe<()> for ImplementorContainer<Dyn, T> {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl<F: FnOnce()> Describe<()> for OnDropContainer<F> {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl<T, Before> Describe<()> for OrderContainer<T, Before> {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl<T> Describe<()> for ValidateContainer<T> {
+    fn describe(_: &mut Vec<ServiceEntry>) {}
+}
+
+impl<Parent, Conts> ServiceProvider<Parent, Conts> {
+    /// Create a `Manifest` of services registered in this provider. Services of parents (for a
+    /// forked provider) are not listed. For more information see `Manifest`.
+    pub fn manifest<Infer>(&self) -> Manifest
+    where
+        Conts: Describe<Infer>,
+    {
+        let mut services = vec![];
+        Conts::describe(&mut services);
+        Manifest { services }
+    }
+}
+
+#[cfg(feature = "actix-support")]
+mod actix {
+    use super::ScopeRecording;
+    use actix_web::test::TestRequest;
+    use actix_web::HttpRequest;
+
+    impl ScopeRecording {
+        /// Create a recording with headers of the request. Credentials are redacted, see
+        /// `ScopeRecording::record_header`.
+        pub fn from_request(req: &HttpRequest) -> Self {
+            let mut recording = ScopeRecording::new();
+            for (name, value) in req.headers() {
+                recording.record_header(
+                    name.as_str(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                );
+            }
+            recording
+        }
+
+        /// Create a `TestRequest` with recorded headers, which can be used to build an
+        /// `HttpRequest` for a replayed scope.
+        pub fn to_test_request(&self) -> TestRequest {
+            self.headers
+                .iter()
+                .fold(TestRequest::default(), |req, (name, value)| {
+                    req.header(name.as_str(), value.as_str())
+                })
+        }
+    }
+}
+
+// FNV-1a, it is stable between platforms and Rust versions unlike `DefaultHasher`.
+fn hash_body(body: &[u8]) -> u64 {
+    body.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
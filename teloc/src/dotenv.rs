@@ -0,0 +1,319 @@
+//! Loading of instances from `.env` files, see `ServiceProvider::add_from_dotenv`. Enabled by the
+//! `dotenv` feature.
+//!
+//! A file has a `KEY=value` pair on every line. Empty lines and lines that start with `#` are
+//! skipped, a line may start with `export`, and a value may be quoted by `"` or `'`. Values end
+//! before ` #`, so they can have comments.
+//!
+//! Fields of the type are read from variables with the same name in upper case (`database_url`
+//! from `DATABASE_URL`), variables with lower case letters in the name are ignored. Variables of
+//! the process take precedence over the file, so deployments can override it, and variables of
+//! the process that are not valid Unicode are ignored. Values of numbers and booleans are parsed
+//! from strings, `Option` fields are `None` when the variable is missing, and sequences are
+//! separated by commas.
+
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use frunk::hlist::HList;
+use frunk::HCons;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs, io};
+
+/// Error of loading an instance from a `.env` file.
+#[derive(Debug)]
+pub enum DotenvError {
+    /// The file cannot be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The line of the file (starting from 1) is not a `KEY=value` pair.
+    Syntax { line: usize },
+    /// The variable is required, but it is set neither in the file nor in the process.
+    Missing { key: String },
+    /// The value of the variable cannot be parsed to the type of the field.
+    Invalid { key: String, message: String },
+    /// Other error of deserialization.
+    Custom(String),
+}
+
+impl Display for DotenvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DotenvError::Io { path, source } => {
+                write!(f, "cannot read `{}`: {}", path.display(), source)
+            }
+            DotenvError::Syntax { line } => {
+                write!(f, "line {} is not a `KEY=value` pair", line)
+            }
+            DotenvError::Missing { key } => write!(f, "missing environment variable `{}`", key),
+            DotenvError::Invalid { key, message } => {
+                write!(f, "invalid environment variable `{}`: {}", key, message)
+            }
+            DotenvError::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for DotenvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DotenvError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl de::Error for DotenvError {
+    fn custom<T: Display>(msg: T) -> Self {
+        DotenvError::Custom(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        DotenvError::Missing {
+            key: field.to_uppercase(),
+        }
+    }
+}
+
+/// Parse variables of a `.env` file.
+pub fn parse(contents: &str) -> Result<HashMap<String, String>, DotenvError> {
+    let mut vars = HashMap::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => (key.trim(), value.trim()),
+            _ => return Err(DotenvError::Syntax { line: i + 1 }),
+        };
+        let value = unquote(value).ok_or(DotenvError::Syntax { line: i + 1 })?;
+        vars.insert(key.to_string(), value);
+    }
+    Ok(vars)
+}
+
+// Value without quotes and a trailing comment, `None` if a quote is not closed or it is followed
+// by something other than a comment.
+fn unquote(value: &str) -> Option<String> {
+    let quote = match value.chars().next() {
+        Some(quote @ '"') | Some(quote @ '\'') => quote,
+        _ => {
+            return Some(match value.find(" #") {
+                Some(comment) => value[..comment].trim_end().to_string(),
+                None => value.to_string(),
+            })
+        }
+    };
+    let mut inner = String::new();
+    let mut chars = value[1..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => match chars.next() {
+                Some((_, 'n')) => inner.push('\n'),
+                Some((_, c @ '"')) | Some((_, c @ '\\')) => inner.push(c),
+                Some((_, c)) => {
+                    inner.push('\\');
+                    inner.push(c);
+                }
+                None => return None,
+            },
+            c if c == quote => {
+                let rest = value[1 + i + 1..].trim_start();
+                if rest.is_empty() || rest.starts_with('#') {
+                    return Some(inner);
+                }
+                return None;
+            }
+            c => inner.push(c),
+        }
+    }
+    None
+}
+
+/// Deserialize `T` from variables of the `.env` file at the path and of the process, see the
+/// module documentation.
+pub fn from_dotenv<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, DotenvError> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path).map_err(|source| DotenvError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut vars = parse(&contents)?;
+    let process = env::vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)));
+    vars.extend(process);
+    from_vars(vars)
+}
+
+/// Deserialize `T` from variables, see the module documentation. If a variable is repeated, the
+/// last value is used.
+pub fn from_vars<T: DeserializeOwned>(
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<T, DotenvError> {
+    let vars = vars
+        .into_iter()
+        .filter(|(key, _)| *key == key.to_uppercase())
+        .collect::<HashMap<_, _>>();
+    let fields = vars.into_iter().map(|(key, value)| {
+        let field = key.to_lowercase();
+        (field, Var { key, value })
+    });
+    T::deserialize(MapDeserializer::new(fields))
+}
+
+// Value of a variable, that is parsed to the type requested by the visitor.
+struct Var {
+    key: String,
+    value: String,
+}
+
+impl Var {
+    fn parse<T>(&self) -> Result<T, DotenvError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.value
+            .parse()
+            .map_err(|e: T::Err| DotenvError::Invalid {
+                key: self.key.clone(),
+                message: format!("`{}`: {}", self.value, e),
+            })
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DotenvError> for Var {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DotenvError> {
+            visitor.$visit(self.parse()?)
+        }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Var {
+    type Error = DotenvError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DotenvError> {
+        visitor.visit_string(self.value)
+    }
+
+    deserialize_parsed!(
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    );
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DotenvError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DotenvError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DotenvError> {
+        let key = self.key;
+        let items = self
+            .value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| Var {
+                key: key.clone(),
+                value: item.to_string(),
+            })
+            .collect::<Vec<_>>();
+        SeqDeserializer::new(items.into_iter()).deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DotenvError> {
+        let key = self.key;
+        let value: de::value::StringDeserializer<DotenvError> = self.value.into_deserializer();
+        value
+            .deserialize_enum("", &[], visitor)
+            .map_err(|e| match e {
+                DotenvError::Custom(message) => DotenvError::Invalid { key, message },
+                e => e,
+            })
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Load `T` from the `.env` file at the path, merged with variables of the process, and add
+    /// it as an instance. Errors name the variable that is missing or invalid. Variables of the
+    /// process are not changed. See the module documentation for the format.
+    ///
+    /// Usage:
+    /// ```
+    /// use serde::Deserialize;
+    /// use teloc::*;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct AppConfig {
+    ///     teloc_doc_database_url: String,
+    ///     teloc_doc_workers: u32,
+    ///     teloc_doc_debug: Option<bool>,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("teloc-add-from-dotenv.env");
+    /// std::fs::write(&path, "TELOC_DOC_DATABASE_URL=postgres://localhost/app\nTELOC_DOC_WORKERS=4\n")
+    ///     .unwrap();
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_from_dotenv::<AppConfig>(&path)
+    ///     .unwrap();
+    ///
+    /// let config: &AppConfig = sp.resolve();
+    /// assert_eq!(config.teloc_doc_database_url, "postgres://localhost/app");
+    /// assert_eq!(config.teloc_doc_workers, 4);
+    /// assert_eq!(config.teloc_doc_debug, None);
+    /// ```
+    pub fn add_from_dotenv<T>(
+        self,
+        path: impl AsRef<Path>,
+    ) -> Result<ServiceProvider<Parent, HCons<InstanceContainer<T>, Conts>>, DotenvError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.add_instance(from_dotenv(path)?))
+    }
+}
@@ -0,0 +1,801 @@
+//! Dynamic layer: services registered and resolved at runtime by their `TypeId`.
+//!
+//! `ServiceProvider` checks all dependencies at compile time, so every service must be known when
+//! the provider is built. `DynResolver` trades this for runtime checks: services are stored as
+//! type-erased factories and a missing dependency is reported as `DynError`. It is useful for
+//! services that are not known to the crate that builds the provider, for example self-registered
+//! plugins (see `DynResolver::from_inventory`).
+//!
+//! `DynResolver` can be added to a `ServiceProvider` as an instance, so static services can look
+//! up dynamic ones by taking `&DynResolver` as a dependency.
+//!
+//! `DynServiceProvider` has the same builder API as `ServiceProvider`, but stores services in a
+//! `DynResolver`. Types of the provider do not depend on its services, so big applications can use
+//! it to trade a small runtime cost for faster builds and short error messages.
+
+#[cfg(feature = "inventory-support")]
+use crate::container::InstanceContainer;
+use crate::dependency::{Dependency, DependencyClone};
+#[cfg(feature = "inventory-support")]
+use crate::{EmptyServiceProvider, ServiceProvider};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::{type_name, Any, TypeId};
+use core::cell::{Cell, RefCell};
+use core::fmt;
+use frunk::{HCons, HNil};
+#[cfg(feature = "sync")]
+use once_cell::sync::OnceCell;
+#[cfg(not(feature = "sync"))]
+use once_cell::unsync::OnceCell;
+
+/// Error of resolving a service from `DynResolver`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynError {
+    /// Service is not registered.
+    NotRegistered(&'static str),
+    /// Service is registered as transient, so it cannot be borrowed from the resolver.
+    NotSingleton(&'static str),
+    /// Factory registered by `DynResolver::register_factory` returned a value of another type.
+    TypeMismatch(&'static str),
+    /// No service is registered with the type id or the type name, see
+    /// `DynResolver::get_by_type_name`.
+    NotFound(String),
+    /// Error happened while resolving dependencies of services in the path, from the outermost
+    /// one. Returned only when path tracking is enabled, see `DynResolver::with_path_tracking`.
+    Resolving(Vec<&'static str>, Box<DynError>),
+    /// Service depends on itself. Contains the path from the outermost service to the service that
+    /// is resolved again. Returned only when path tracking is enabled.
+    Cycle(Vec<&'static str>),
+    /// Path of services being resolved is longer than the limit, see
+    /// `DynResolver::with_max_depth`. Contains the path from the outermost service. Returned only
+    /// when path tracking is enabled.
+    TooDeep(Vec<&'static str>),
+}
+
+impl DynError {
+    /// Returns the error without the resolution path.
+    pub fn cause(&self) -> &DynError {
+        match self {
+            DynError::Resolving(_, error) => error.cause(),
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for DynError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynError::NotRegistered(name) => write!(f, "service `{}` is not registered", name),
+            DynError::NotSingleton(name) => {
+                write!(
+                    f,
+                    "service `{}` is not a singleton, resolve it by value",
+                    name
+                )
+            }
+            DynError::TypeMismatch(name) => {
+                write!(
+                    f,
+                    "factory of service `{}` returned a value of another type",
+                    name
+                )
+            }
+            DynError::NotFound(key) => write!(f, "no service is registered for `{}`", key),
+            DynError::Resolving(path, error) => {
+                write!(f, "{}, resolution path: {}", error, path.join(" -> "))
+            }
+            DynError::Cycle(path) => {
+                let name = path.last().copied().unwrap_or_default();
+                write!(
+                    f,
+                    "service `{}` depends on itself, resolution path: {}",
+                    name,
+                    path.join(" -> ")
+                )
+            }
+            DynError::TooDeep(path) => {
+                write!(f, "resolution path is too deep: {}", path.join(" -> "))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DynError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DynError::Resolving(_, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+type Factory = fn(&DynResolver) -> Result<Box<dyn Any>, DynError>;
+type CloneAny = fn(&dyn Any) -> Box<dyn Any>;
+type BoxedDynFactory = Box<dyn Fn(&DynResolver) -> Result<Box<dyn Any>, DynError>>;
+
+/// Type-erased factory of a service, see `DynResolver::register_factory`.
+pub type ErasedFactory = Box<dyn Fn(&DynResolver) -> Box<dyn Any>>;
+
+/// Signature of the function that a plugin exports to register its services, see
+/// `export_plugin!` and `DynResolver::load_plugin`.
+pub type PluginRegister = fn(&mut DynResolver);
+
+/// Description of a service for `DynResolver`: its type and how to create it. It can be created
+/// in constant context, so it can be submitted to the global registry by `#[teloc::service]`.
+#[derive(Clone, Copy)]
+pub struct DynService {
+    type_id: fn() -> TypeId,
+    type_name: fn() -> &'static str,
+    factory: Factory,
+    clone: Option<CloneAny>,
+}
+
+impl DynService {
+    /// Service with the `Transient` lifetime: created each time when it resolved. Dependencies of
+    /// the service are resolved from `DynResolver` by value.
+    pub const fn transient<T, Deps>() -> Self
+    where
+        T: Dependency<Deps> + 'static,
+        Deps: DynDependencies,
+    {
+        Self {
+            type_id: TypeId::of::<T>,
+            type_name: type_name::<T>,
+            factory: create::<T, Deps>,
+            clone: None,
+        }
+    }
+
+    /// Service with the `Singleton` lifetime: created once, when it resolved first time. It can be
+    /// got by reference with `DynResolver::get` or by cloning with `DynResolver::resolve`.
+    pub const fn singleton<T, Deps>() -> Self
+    where
+        T: Dependency<Deps> + DependencyClone + 'static,
+        Deps: DynDependencies,
+    {
+        Self {
+            type_id: TypeId::of::<T>,
+            type_name: type_name::<T>,
+            factory: create::<T, Deps>,
+            clone: Some(clone_any::<T>),
+        }
+    }
+}
+
+fn create<T, Deps>(resolver: &DynResolver) -> Result<Box<dyn Any>, DynError>
+where
+    T: Dependency<Deps> + 'static,
+    Deps: DynDependencies,
+{
+    Ok(Box::new(T::init(Deps::resolve(resolver)?)))
+}
+
+fn clone_any<T: Clone + 'static>(value: &dyn Any) -> Box<dyn Any> {
+    Box::new(
+        value
+            .downcast_ref::<T>()
+            .expect("singleton is stored by its type id")
+            .clone(),
+    )
+}
+
+/// The trait, used for resolving dependencies of a service from `DynResolver`. Do not use it by
+/// yourself.
+pub trait DynDependencies: Sized {
+    fn resolve(resolver: &DynResolver) -> Result<Self, DynError>;
+}
+
+impl DynDependencies for HNil {
+    fn resolve(_: &DynResolver) -> Result<Self, DynError> {
+        Ok(HNil)
+    }
+}
+
+impl<H: 'static, T: DynDependencies> DynDependencies for HCons<H, T> {
+    fn resolve(resolver: &DynResolver) -> Result<Self, DynError> {
+        Ok(HCons {
+            head: resolver.resolve::<H>()?,
+            tail: T::resolve(resolver)?,
+        })
+    }
+}
+
+/// Default limit of the resolution path, see `DynResolver::with_max_depth`.
+const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Services that are being resolved, see `DynResolver::with_path_tracking`.
+struct ResolutionPath {
+    services: RefCell<Vec<(TypeId, &'static str)>>,
+    max_depth: usize,
+    reported: Cell<bool>,
+}
+
+impl ResolutionPath {
+    fn new(max_depth: usize) -> Self {
+        ResolutionPath {
+            services: RefCell::new(Vec::new()),
+            max_depth,
+            reported: Cell::new(false),
+        }
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        self.services.borrow().iter().map(|(_, name)| *name).collect()
+    }
+
+    // Returns an error if the service is already being resolved.
+    fn check(&self, registration: &Registration) -> Result<(), DynError> {
+        let services = self.services.borrow();
+        if services.iter().any(|(id, _)| *id == registration.type_id) {
+            let mut path = self.names();
+            path.push(registration.type_name);
+            return Err(DynError::Cycle(path));
+        }
+        Ok(())
+    }
+
+    fn enter(&self, registration: &Registration) -> Result<PathGuard<'_>, DynError> {
+        self.check(registration)?;
+        if self.services.borrow().len() >= self.max_depth {
+            let mut path = self.names();
+            path.push(registration.type_name);
+            return Err(DynError::TooDeep(path));
+        }
+        self.services
+            .borrow_mut()
+            .push((registration.type_id, registration.type_name));
+        Ok(PathGuard(self))
+    }
+}
+
+// Pops the service from the path even if its factory panics.
+struct PathGuard<'a>(&'a ResolutionPath);
+
+impl Drop for PathGuard<'_> {
+    fn drop(&mut self) {
+        // Only the innermost service reports the panic, when the path is full.
+        #[cfg(feature = "log-support")]
+        if std::thread::panicking() && !self.0.reported.replace(true) {
+            log::error!(
+                target: "teloc",
+                "panicked while resolving {}",
+                self.0.names().join(" -> ")
+            );
+        }
+        let mut services = self.0.services.borrow_mut();
+        services.pop();
+        if services.is_empty() {
+            self.0.reported.set(false);
+        }
+    }
+}
+
+struct Registration {
+    type_id: TypeId,
+    type_name: &'static str,
+    factory: BoxedDynFactory,
+    clone: Option<CloneAny>,
+    instance: OnceCell<Box<dyn Any>>,
+}
+
+/// Provider of services registered at runtime. For more information see the module-level
+/// documentation.
+///
+/// Usage:
+/// ```
+/// use std::rc::Rc;
+/// use teloc::dynamic::{DynError, DynResolver, DynService};
+/// use teloc::*;
+///
+/// #[derive(Dependency)]
+/// struct Config;
+///
+/// #[derive(Dependency)]
+/// struct Handler {
+///     config: Rc<Config>,
+/// }
+///
+/// let resolver = DynResolver::new()
+///     .add_service(DynService::singleton::<Rc<Config>, _>())
+///     .add_service(DynService::transient::<Handler, _>());
+///
+/// let handler: Handler = resolver.resolve().unwrap();
+/// let config: &Rc<Config> = resolver.get().unwrap();
+/// assert!(Rc::ptr_eq(&handler.config, config));
+/// assert!(matches!(resolver.resolve::<u8>(), Err(DynError::NotRegistered(_))));
+/// ```
+#[derive(Default)]
+pub struct DynResolver {
+    services: BTreeMap<TypeId, Registration>,
+    path: Option<ResolutionPath>,
+    // Declared after `services`, so the code of plugins is unloaded after their services dropped.
+    #[cfg(feature = "plugins")]
+    libraries: alloc::vec::Vec<libloading::Library>,
+}
+
+impl DynResolver {
+    /// Create an empty `DynResolver`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable the debug mode, in which the resolver tracks the path of services being resolved.
+    /// Errors in dependencies are returned as `DynError::Resolving` with the path from the
+    /// resolved service to the one whose factory failed, and a panic in a factory is logged with
+    /// the path at the error level (with the `log-support` feature). A service that depends on
+    /// itself is reported as `DynError::Cycle` instead of overflowing the stack (or blocking
+    /// forever for singletons), and paths longer than 128 services as `DynError::TooDeep`. It has
+    /// a small runtime cost, so enable it in debug builds or while localizing a failure.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::dynamic::{DynError, DynResolver, DynService};
+    /// use teloc::*;
+    ///
+    /// #[derive(Dependency)]
+    /// struct Pool {
+    ///     url: String,
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Repo {
+    ///     pool: Pool,
+    /// }
+    ///
+    /// let resolver = DynResolver::new()
+    ///     .with_path_tracking()
+    ///     .add_service(DynService::transient::<Pool, _>())
+    ///     .add_service(DynService::transient::<Repo, _>());
+    ///
+    /// let error = resolver.resolve::<Repo>().err().unwrap();
+    /// assert_eq!(error.cause(), &DynError::NotRegistered("alloc::string::String"));
+    /// let path = match &error {
+    ///     DynError::Resolving(path, _) => path,
+    ///     _ => unreachable!(),
+    /// };
+    /// assert_eq!(path, &[std::any::type_name::<Repo>(), std::any::type_name::<Pool>()]);
+    /// // service `alloc::string::String` is not registered, resolution path: crate::Repo -> crate::Pool
+    /// println!("{}", error);
+    /// ```
+    pub fn with_path_tracking(self) -> Self {
+        self.with_max_depth(DEFAULT_MAX_DEPTH)
+    }
+
+    /// Same as `DynResolver::with_path_tracking`, but paths longer than `max_depth` services are
+    /// reported as `DynError::TooDeep`.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.path = Some(ResolutionPath::new(max_depth));
+        self
+    }
+
+    /// Add the service. If a service of the same type was already added, it is replaced.
+    pub fn add_service(mut self, service: DynService) -> Self {
+        self.register_service(service);
+        self
+    }
+
+    /// Same as `DynResolver::add_service`, but by mutable reference, for registering services at
+    /// runtime.
+    pub fn register_service(&mut self, service: DynService) {
+        let factory = service.factory;
+        self.insert(
+            (service.type_id)(),
+            (service.type_name)(),
+            Box::new(factory),
+            service.clone,
+        );
+    }
+
+    /// Register a transient service created by the closure. Unlike `DynService`, the closure can
+    /// capture state, so it is suitable for factories created at runtime (for example, by plugins).
+    ///
+    /// Usage:
+    /// ```
+    /// use std::rc::Rc;
+    /// use teloc::dynamic::DynResolver;
+    ///
+    /// struct Greeting(String);
+    ///
+    /// let mut resolver = DynResolver::new();
+    /// resolver.register_singleton(|_| Ok(Rc::new(String::from("world"))));
+    /// resolver.register_transient(|r| {
+    ///     Ok(Greeting(format!("Hello, {}!", r.get::<Rc<String>>()?)))
+    /// });
+    ///
+    /// let greeting: Greeting = resolver.resolve().unwrap();
+    /// assert_eq!(greeting.0, "Hello, world!");
+    /// ```
+    pub fn register_transient<T, F>(&mut self, factory: F)
+    where
+        T: 'static,
+        F: Fn(&DynResolver) -> Result<T, DynError> + 'static,
+    {
+        self.insert(
+            TypeId::of::<T>(),
+            type_name::<T>(),
+            Box::new(move |r| Ok(Box::new(factory(r)?))),
+            None,
+        );
+    }
+
+    /// Register a singleton service created by the closure, see
+    /// `DynResolver::register_transient`.
+    pub fn register_singleton<T, F>(&mut self, factory: F)
+    where
+        T: DependencyClone + 'static,
+        F: Fn(&DynResolver) -> Result<T, DynError> + 'static,
+    {
+        self.insert(
+            TypeId::of::<T>(),
+            type_name::<T>(),
+            Box::new(move |r| Ok(Box::new(factory(r)?))),
+            Some(clone_any::<T>),
+        );
+    }
+
+    /// Register a transient service by a type-erased factory. The factory must return a value of
+    /// the type with `type_id`, otherwise resolving it returns `DynError::TypeMismatch`. Use it
+    /// when the type is not known to the code that registers the factory.
+    pub fn register_factory(
+        &mut self,
+        type_id: TypeId,
+        type_name: &'static str,
+        factory: ErasedFactory,
+    ) {
+        self.insert(type_id, type_name, Box::new(move |r| Ok(factory(r))), None);
+    }
+
+    /// Register an instance created outside of the resolver. It is resolved like a singleton.
+    pub fn register_instance<T: DependencyClone + 'static>(&mut self, instance: T) {
+        self.insert(
+            TypeId::of::<T>(),
+            type_name::<T>(),
+            Box::new(instance_factory),
+            Some(clone_any::<T>),
+        );
+        let registration = self.services.get(&TypeId::of::<T>()).unwrap();
+        let _ = registration.instance.set(Box::new(instance));
+    }
+
+    fn insert(
+        &mut self,
+        type_id: TypeId,
+        type_name: &'static str,
+        factory: BoxedDynFactory,
+        clone: Option<CloneAny>,
+    ) {
+        self.services.insert(
+            type_id,
+            Registration {
+                type_id,
+                type_name,
+                factory,
+                clone,
+                instance: OnceCell::new(),
+            },
+        );
+    }
+
+    /// Returns `true` if the service `T` is registered.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.services.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Resolve `T` by value: create a transient service or clone a singleton.
+    pub fn resolve<T: 'static>(&self) -> Result<T, DynError> {
+        let registration = self.registration::<T>()?;
+        let value = match registration.clone {
+            Some(clone) => clone(&**self.instance(registration)?),
+            None => self.create(registration)?,
+        };
+        match value.downcast::<T>() {
+            Ok(value) => Ok(*value),
+            Err(_) => Err(DynError::TypeMismatch(type_name::<T>())),
+        }
+    }
+
+    /// Get a singleton `T` by reference.
+    pub fn get<T: 'static>(&self) -> Result<&T, DynError> {
+        let registration = self.registration::<T>()?;
+        if registration.clone.is_none() {
+            return Err(DynError::NotSingleton(type_name::<T>()));
+        }
+        self.instance(registration)?
+            .downcast_ref::<T>()
+            .ok_or_else(|| DynError::TypeMismatch(type_name::<T>()))
+    }
+
+    /// Get a singleton by its `TypeId`, for code that does not know the type statically, like
+    /// admin and debug endpoints.
+    pub fn get_by_type_id(&self, type_id: TypeId) -> Result<&dyn Any, DynError> {
+        let registration = self
+            .services
+            .get(&type_id)
+            .ok_or_else(|| DynError::NotFound(format!("{:?}", type_id)))?;
+        self.get_any(registration)
+    }
+
+    /// Get a singleton by the name of its type, as returned by `core::any::type_name`. See
+    /// `DynResolver::get_by_type_id`.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::any::type_name;
+    /// use std::rc::Rc;
+    /// use teloc::dynamic::DynResolver;
+    ///
+    /// let mut resolver = DynResolver::new();
+    /// resolver.register_instance(Rc::new(String::from("v1.2.0")));
+    ///
+    /// for (_, name) in resolver.services() {
+    ///     let service = resolver.get_by_type_name(name).unwrap();
+    ///     if let Some(version) = service.downcast_ref::<Rc<String>>() {
+    ///         assert_eq!(version.as_str(), "v1.2.0");
+    ///     }
+    /// }
+    /// assert!(resolver.get_by_type_name(type_name::<Rc<String>>()).is_ok());
+    /// ```
+    pub fn get_by_type_name(&self, name: &str) -> Result<&dyn Any, DynError> {
+        let registration = self
+            .services
+            .values()
+            .find(|registration| registration.type_name == name)
+            .ok_or_else(|| DynError::NotFound(name.to_string()))?;
+        self.get_any(registration)
+    }
+
+    /// Iterator over `TypeId`s and type names of registered services.
+    pub fn services(&self) -> impl Iterator<Item = (TypeId, &'static str)> + '_ {
+        self.services
+            .iter()
+            .map(|(type_id, registration)| (*type_id, registration.type_name))
+    }
+
+    fn get_any<'a>(&'a self, registration: &'a Registration) -> Result<&'a dyn Any, DynError> {
+        if registration.clone.is_none() {
+            return Err(DynError::NotSingleton(registration.type_name));
+        }
+        Ok(&**self.instance(registration)?)
+    }
+
+    fn registration<T: 'static>(&self) -> Result<&Registration, DynError> {
+        self.services
+            .get(&TypeId::of::<T>())
+            .ok_or_else(|| DynError::NotRegistered(type_name::<T>()))
+    }
+
+    fn instance<'a>(
+        &'a self,
+        registration: &'a Registration,
+    ) -> Result<&'a Box<dyn Any>, DynError> {
+        // A singleton that is being created would initialize its cell again.
+        if let (Some(path), None) = (&self.path, registration.instance.get()) {
+            path.check(registration)?;
+        }
+        registration
+            .instance
+            .get_or_try_init(|| self.create(registration))
+    }
+
+    fn create(&self, registration: &Registration) -> Result<Box<dyn Any>, DynError> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return (registration.factory)(self),
+        };
+        let _guard = path.enter(registration)?;
+        (registration.factory)(self).map_err(|error| match error {
+            DynError::Resolving(..) | DynError::Cycle(_) | DynError::TooDeep(_) => error,
+            error => DynError::Resolving(path.names(), Box::new(error)),
+        })
+    }
+}
+
+fn instance_factory(_: &DynResolver) -> Result<Box<dyn Any>, DynError> {
+    unreachable!("instances are set when they are registered")
+}
+
+impl fmt::Debug for DynResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.services.values().map(|r| r.type_name))
+            .finish()
+    }
+}
+
+/// Provider of services with the same builder API as `ServiceProvider`, that resolves services at
+/// runtime. All dependencies are resolved by value, so share services using singletons (or
+/// instances) of `Rc<_>`/`Arc<_>`. A missing dependency is reported as `DynError` when the service
+/// is resolved. For more information see the module-level documentation.
+///
+/// Usage:
+/// ```
+/// use std::rc::Rc;
+/// use teloc::dynamic::{DynError, DynServiceProvider};
+/// use teloc::*;
+///
+/// struct Config {
+///     name: &'static str,
+/// }
+///
+/// #[derive(Dependency)]
+/// struct Repo {
+///     config: Rc<Config>,
+/// }
+///
+/// #[derive(Dependency)]
+/// struct Controller {
+///     repo: Rc<Repo>,
+/// }
+///
+/// let sp = DynServiceProvider::new()
+///     .add_instance(Rc::new(Config { name: "app" }))
+///     .add_singleton::<Rc<Repo>, _>()
+///     .add_transient::<Controller, _>();
+///
+/// let controller: Controller = sp.resolve().unwrap();
+/// assert_eq!(controller.repo.config.name, "app");
+/// assert_eq!(
+///     sp.resolve::<u8>().err(),
+///     Some(DynError::NotRegistered("u8")),
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct DynServiceProvider {
+    resolver: DynResolver,
+}
+
+impl DynServiceProvider {
+    /// Create an empty `DynServiceProvider`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable tracking of the resolution path, see `DynResolver::with_path_tracking`.
+    pub fn with_path_tracking(mut self) -> Self {
+        self.resolver = self.resolver.with_path_tracking();
+        self
+    }
+
+    /// Add dependency with the `Transient` lifetime, see `ServiceProvider::add_transient`.
+    pub fn add_transient<T, Deps>(mut self) -> Self
+    where
+        T: Dependency<Deps> + 'static,
+        Deps: DynDependencies,
+    {
+        self.resolver
+            .register_service(DynService::transient::<T, Deps>());
+        self
+    }
+
+    /// Add dependency with the `Singleton` lifetime, see `ServiceProvider::add_singleton`.
+    pub fn add_singleton<T, Deps>(mut self) -> Self
+    where
+        T: Dependency<Deps> + DependencyClone + 'static,
+        Deps: DynDependencies,
+    {
+        self.resolver
+            .register_service(DynService::singleton::<T, Deps>());
+        self
+    }
+
+    /// Add anything instance to provider, see `ServiceProvider::add_instance`.
+    pub fn add_instance<T: DependencyClone + 'static>(mut self, instance: T) -> Self {
+        self.resolver.register_instance(instance);
+        self
+    }
+
+    /// Resolve `T` by value: create a transient service or clone a singleton.
+    pub fn resolve<T: 'static>(&self) -> Result<T, DynError> {
+        self.resolver.resolve()
+    }
+
+    /// Get a singleton or an instance `T` by reference.
+    pub fn get<T: 'static>(&self) -> Result<&T, DynError> {
+        self.resolver.get()
+    }
+
+    /// Returns `DynResolver` that stores services of the provider.
+    pub fn resolver(&self) -> &DynResolver {
+        &self.resolver
+    }
+
+    /// Convert the provider into `DynResolver` that stores its services.
+    pub fn into_resolver(self) -> DynResolver {
+        self.resolver
+    }
+}
+
+#[cfg(feature = "inventory-support")]
+inventory::collect!(DynService);
+
+#[cfg(feature = "inventory-support")]
+impl DynResolver {
+    /// Create a `DynResolver` with all services registered by `#[teloc::service]` attribute in
+    /// all crates linked into the binary. Enabled by the `inventory-support` feature.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::dynamic::DynResolver;
+    /// use teloc::*;
+    ///
+    /// #[teloc::service(singleton)]
+    /// #[derive(Clone, Dependency, DependencyClone)]
+    /// struct MetricsPlugin;
+    ///
+    /// #[teloc::service]
+    /// #[derive(Dependency)]
+    /// struct AuditPlugin {
+    ///     metrics: MetricsPlugin,
+    /// }
+    ///
+    /// let resolver = DynResolver::from_inventory();
+    /// let _: AuditPlugin = resolver.resolve().unwrap();
+    /// let _: &MetricsPlugin = resolver.get().unwrap();
+    /// ```
+    pub fn from_inventory() -> Self {
+        inventory::iter::<DynService>
+            .into_iter()
+            .fold(Self::new(), |resolver, service| {
+                resolver.add_service(*service)
+            })
+    }
+}
+
+#[cfg(feature = "plugins")]
+#[allow(unsafe_code)]
+impl DynResolver {
+    /// Load the dynamic library of a plugin and register its services. The plugin exports its
+    /// registration function using `export_plugin!`. The library stays loaded while the
+    /// `DynResolver` exists. Enabled by the `plugins` feature.
+    ///
+    /// Usage:
+    /// ```no_run
+    /// // In the plugin crate (`crate-type = ["cdylib"]`):
+    /// use teloc::dynamic::DynResolver;
+    ///
+    /// pub struct Greeting(pub String);
+    ///
+    /// fn register(resolver: &mut DynResolver) {
+    ///     resolver.register_transient(|_| Ok(Greeting(String::from("Hello from plugin!"))));
+    /// }
+    /// teloc::export_plugin!(register);
+    ///
+    /// // In the application:
+    /// let mut resolver = DynResolver::new();
+    /// unsafe { resolver.load_plugin("target/debug/libgreeting_plugin.so") }.unwrap();
+    /// let greeting: Greeting = resolver.resolve().unwrap();
+    /// ```
+    ///
+    /// # Safety
+    /// The library must export `PluginRegister` function using `export_plugin!` and must be built
+    /// by the same compiler with the same version of `teloc`, because the function uses Rust ABI
+    /// and services are identified by `TypeId`. Values resolved from the plugin must not outlive
+    /// the `DynResolver`, because their code is unloaded when it is dropped. Loading the library
+    /// runs its initialization routines, see `libloading::Library::new`.
+    pub unsafe fn load_plugin<P: AsRef<std::ffi::OsStr>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), libloading::Error> {
+        let library = libloading::Library::new(path)?;
+        let register = *library.get::<PluginRegister>(b"teloc_plugin_register\0")?;
+        register(self);
+        self.libraries.push(library);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "inventory-support")]
+impl ServiceProvider<EmptyServiceProvider, HNil> {
+    /// Create a `ServiceProvider` with `DynResolver::from_inventory` added as an instance, so
+    /// services registered by `#[teloc::service]` can be looked up by services that take
+    /// `&DynResolver` as a dependency.
+    pub fn from_inventory(
+    ) -> ServiceProvider<EmptyServiceProvider, HCons<InstanceContainer<DynResolver>, HNil>> {
+        ServiceProvider::new().add_instance(DynResolver::from_inventory())
+    }
+}
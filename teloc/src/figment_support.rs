@@ -0,0 +1,61 @@
+//! Support for `figment` crate.
+
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use figment::Figment;
+use frunk::hlist::HList;
+use frunk::HCons;
+use serde::de::DeserializeOwned;
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Extract a typed configuration section from the layered `Figment` and add it as an
+    /// `Instance`. Services that depend on `&T` (or `T`, if it implements `DependencyClone`) will
+    /// get the section without any manual plumbing.
+    ///
+    /// Extraction happens immediately, so misconfiguration is reported when the provider is
+    /// built rather than when the service is first resolved.
+    ///
+    /// Usage:
+    /// ```
+    /// use figment::{providers::{Format, Toml}, Figment};
+    /// use serde::Deserialize;
+    /// use teloc::*;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct DbConfig { url: String, pool_size: u32 }
+    ///
+    /// struct Database<'a> { config: &'a DbConfig }
+    /// #[inject]
+    /// impl<'a> Database<'a> {
+    ///     fn new(config: &'a DbConfig) -> Self { Self { config } }
+    /// }
+    ///
+    /// let figment = Figment::new().merge(Toml::string(r#"
+    ///     [database]
+    ///     url = "postgres://localhost/app"
+    ///     pool_size = 8
+    /// "#));
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_config_section::<DbConfig>(&figment, "database")
+    ///     .unwrap()
+    ///     .add_transient::<Database>();
+    ///
+    /// let db: Database = sp.resolve();
+    /// assert_eq!(db.config.url, "postgres://localhost/app");
+    /// assert_eq!(db.config.pool_size, 8);
+    /// ```
+    // `figment::Error` is large, but returning it as is lets `?` work in `figment::Result` fns.
+    #[allow(clippy::result_large_err)]
+    pub fn add_config_section<T>(
+        self,
+        figment: &Figment,
+        key: &str,
+    ) -> Result<ServiceProvider<Parent, HCons<InstanceContainer<T>, Conts>>, figment::Error>
+    where
+        T: DeserializeOwned,
+    {
+        let section = figment.extract_inner::<T>(key)?;
+        Ok(self.add_instance(section))
+    }
+}
@@ -0,0 +1,215 @@
+//! Flat storage of containers, see `ServiceProvider::flatten`. Enabled by the `flat-storage`
+//! feature.
+
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::ServiceProvider;
+use core::marker::PhantomData;
+use frunk::hlist::{HList, Selector};
+use frunk::HNil;
+
+/// Containers of a `ServiceProvider` stored in a tuple instead of a nested `HCons` list.
+#[derive(Debug)]
+pub struct Flat<T>(T);
+
+/// Index of a container in `Flat` storage. Do not use it by yourself.
+pub struct FlatIndex<const N: usize>;
+
+/// Index of a container in the rest of `Flat` storage with more than 16 containers. Do not use
+/// it by yourself.
+pub struct FlatRest<Index>(PhantomData<Index>);
+
+/// The trait, used for converting `HList` of containers to `Flat` storage. Lists of more than 16
+/// containers are stored in chunks of 16, where the last element of a chunk is the `Flat` storage
+/// of the rest. Do not use it by yourself.
+pub trait IntoFlat {
+    type Flat;
+
+    fn into_flat(self) -> Self::Flat;
+}
+
+impl IntoFlat for HNil {
+    type Flat = Flat<()>;
+
+    fn into_flat(self) -> Flat<()> {
+        Flat(())
+    }
+}
+
+impl HList for Flat<()> {
+    const LEN: usize = 0;
+
+    fn static_len() -> usize {
+        Self::LEN
+    }
+}
+
+impl<'a, SP> WarmUp<'a, SP, ()> for Flat<()> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP> CollectOrder<'a, SP, ()> for Flat<()> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+// Implements traits of `Flat` storage of the tuple of containers `$ty`, followed by the `Flat`
+// storage of the rest of containers `$rest` in chunks.
+macro_rules! impl_flat_tuple {
+    ([$(($ty:ident $infer:ident $idx:tt)),*] $(($rest:ident $rest_infer:ident $rest_idx:tt))?) => {
+        impl<$($ty,)* $($rest: HList)?> HList for Flat<($($ty,)* $($rest,)?)> {
+            const LEN: usize = [$($idx),*].len() $(+ $rest::LEN)?;
+
+            fn static_len() -> usize {
+                Self::LEN
+            }
+        }
+
+        impl_flat_tuple!(@select [$($ty,)* $($rest)?] $(($ty $idx))*);
+
+
+        // Infer parameters are reused as a tuple, one for each element.
+        impl<'a, SP, $($ty,)* $($rest,)? $($infer,)* $($rest_infer)?>
+            WarmUp<'a, SP, ($($infer,)* $($rest_infer,)?)> for Flat<($($ty,)* $($rest,)?)>
+        where
+            $($ty: WarmUp<'a, SP, $infer>,)*
+            $($rest: WarmUp<'a, SP, $rest_infer>,)?
+        {
+            fn warm_up(&'a self, sp: &'a SP) {
+                $((self.0).$idx.warm_up(sp);)*
+                $((self.0).$rest_idx.warm_up(sp);)?
+            }
+        }
+
+        impl<'a, SP, $($ty,)* $($rest,)? $($infer,)* $($rest_infer)?>
+            CollectOrder<'a, SP, ($($infer,)* $($rest_infer,)?)> for Flat<($($ty,)* $($rest,)?)>
+        where
+            $($ty: CollectOrder<'a, SP, $infer>,)*
+            $($rest: CollectOrder<'a, SP, $rest_infer>,)?
+        {
+            fn collect_order(&'a self, order: &mut StartupOrder<'a, SP>) {
+                $((self.0).$idx.collect_order(order);)*
+                $((self.0).$rest_idx.collect_order(order);)?
+            }
+        }
+    };
+    (@select $all:tt $(($ty:ident $idx:tt))*) => {
+        $(impl_flat_tuple!(@select_one $all $ty $idx);)*
+    };
+    (@select_one [$($all:ident),* $(,)?] $ty:ident $idx:tt) => {
+        impl<$($all),*> Selector<$ty, FlatIndex<$idx>> for Flat<($($all,)*)> {
+            fn get(&self) -> &$ty {
+                &(self.0).$idx
+            }
+
+            fn get_mut(&mut self) -> &mut $ty {
+                &mut (self.0).$idx
+            }
+        }
+    };
+}
+
+macro_rules! impl_flat {
+    ($([$(($ty:ident $var:ident $infer:ident $idx:tt)),*]),*) => {
+        $(
+        impl<$($ty),*> IntoFlat for frunk::HList![$($ty),*] {
+            type Flat = Flat<($($ty,)*)>;
+
+            fn into_flat(self) -> Self::Flat {
+                let frunk::hlist_pat![$($var),*] = self;
+                Flat(($($var,)*))
+            }
+        }
+
+        impl_flat_tuple!([$(($ty $infer $idx)),*]);
+        )*
+    };
+}
+
+impl_flat!(
+    [(T0 t0 I0 0)],
+    [(T0 t0 I0 0), (T1 t1 I1 1)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9), (T10 t10 I10 10)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9), (T10 t10 I10 10), (T11 t11 I11 11)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9), (T10 t10 I10 10), (T11 t11 I11 11), (T12 t12 I12 12)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9), (T10 t10 I10 10), (T11 t11 I11 11), (T12 t12 I12 12), (T13 t13 I13 13)],
+    [(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9), (T10 t10 I10 10), (T11 t11 I11 11), (T12 t12 I12 12), (T13 t13 I13 13), (T14 t14 I14 14)]
+);
+
+macro_rules! impl_flat_chunk {
+    ([$(($ty:ident $var:ident $infer:ident $idx:tt)),*] ($rest:ident $rest_infer:ident $rest_idx:tt)) => {
+        impl<$($ty,)* Tail: IntoFlat> IntoFlat for frunk::HList![$($ty,)* ...Tail] {
+            type Flat = Flat<($($ty,)* Tail::Flat,)>;
+
+            fn into_flat(self) -> Self::Flat {
+                let frunk::hlist_pat![$($var,)* ...tail] = self;
+                Flat(($($var,)* tail.into_flat(),))
+            }
+        }
+
+        impl<$($ty,)* $rest, T, Index> Selector<T, FlatRest<Index>> for Flat<($($ty,)* $rest,)>
+        where
+            $rest: Selector<T, Index>,
+        {
+            fn get(&self) -> &T {
+                (self.0).$rest_idx.get()
+            }
+
+            fn get_mut(&mut self) -> &mut T {
+                (self.0).$rest_idx.get_mut()
+            }
+        }
+
+        impl_flat_tuple!([$(($ty $infer $idx)),*] ($rest $rest_infer $rest_idx));
+    };
+}
+
+impl_flat_chunk!([(T0 t0 I0 0), (T1 t1 I1 1), (T2 t2 I2 2), (T3 t3 I3 3), (T4 t4 I4 4), (T5 t5 I5 5), (T6 t6 I6 6), (T7 t7 I7 7), (T8 t8 I8 8), (T9 t9 I9 9), (T10 t10 I10 10), (T11 t11 I11 11), (T12 t12 I12 12), (T13 t13 I13 13), (T14 t14 I14 14), (T15 t15 I15 15)] (Rest IRest 16));
+
+impl<Parent, Conts: IntoFlat> ServiceProvider<Parent, Conts> {
+    /// Store containers in a flat tuple instead of a nested `HCons` list. Types of flattened
+    /// providers are shorter, which shortens error messages, and finding a container does not
+    /// recurse over the whole list, so big providers do not need a higher `recursion_limit`.
+    /// Flattening does not make compile times measurably shorter. Resolving and forking work the
+    /// same way.
+    ///
+    /// Containers added after flattening are stored in a list again, so flatten the provider
+    /// after most services are added.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// #[derive(Dependency)]
+    /// struct Config;
+    ///
+    /// #[derive(Dependency)]
+    /// struct Service<'a> {
+    ///     config: &'a Config,
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_singleton::<Config>()
+    ///     .add_transient::<Service>()
+    ///     .flatten();
+    /// sp.warm_up();
+    ///
+    /// let scope = sp.fork().add_instance(5u8);
+    /// let _: Service = scope.resolve();
+    /// let _: u8 = scope.resolve();
+    /// ```
+    pub fn flatten(self) -> ServiceProvider<Parent, Conts::Flat> {
+        let ServiceProvider { parent, containers } = self;
+        ServiceProvider {
+            parent,
+            containers: containers.into_flat(),
+        }
+    }
+}
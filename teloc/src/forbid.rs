@@ -0,0 +1,87 @@
+//! Support for negative registrations, see `ServiceProvider::forbid`.
+
+use crate::container::Container;
+use crate::service_provider::SelectContainer;
+use crate::warm_up::WarmUp;
+use crate::Resolver;
+use core::marker::PhantomData;
+
+/// Container that forbids resolving `T` (and `&T`) from the `ServiceProvider`. `Reason` explains
+/// why `T` is forbidden, see `ForbidReason`.
+pub struct ForbiddenContainer<T, Reason>(PhantomData<fn() -> (T, Reason)>);
+
+#[cfg(feature = "shared-singletons")]
+impl<T, Reason> Clone for ForbiddenContainer<T, Reason> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T, Reason> Container for ForbiddenContainer<T, Reason> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Reason of a `ServiceProvider::forbid` registration. The compile error of resolving the
+/// forbidden type names the type of the reason and points to its `REASON`, so give the type a
+/// descriptive name.
+pub trait ForbidReason {
+    const REASON: &'static str;
+}
+
+/// Marker trait that is never implemented: it is required by resolving a type forbidden by
+/// `Self`, so the error is reported when the provider is type-checked.
+///
+/// Usage:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// struct DbPool;
+/// struct UseRepository;
+/// impl ForbidReason for UseRepository {
+///     const REASON: &'static str = "use `Repository` instead of `DbPool`";
+/// }
+///
+/// type AppSp = ServiceProvider<
+///     EmptyServiceProvider,
+///     HCons<ForbiddenContainer<DbPool, UseRepository>, HNil>,
+/// >;
+///
+/// // error: `DbPool` is forbidden in this provider by `UseRepository`
+/// check_provider!(AppSp: DbPool);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{T}` is forbidden in this provider by `{Self}`",
+    label = "resolves `{T}`",
+    note = "see `ForbidReason::REASON` of `{Self}`"
+)]
+pub trait Forbidden<T> {}
+
+impl<'this, 'cont, T, Reason, SP, Index>
+    Resolver<'this, T, (&'cont ForbiddenContainer<T, Reason>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont ForbiddenContainer<T, Reason>, Index>,
+    Reason: ForbidReason + Forbidden<T>,
+{
+    fn resolve(&'this self) -> T {
+        unreachable!("`Forbidden` is never implemented")
+    }
+}
+
+impl<'this, 'cont, T, Reason, SP, Index>
+    Resolver<'this, &'cont T, (&'cont ForbiddenContainer<T, Reason>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont ForbiddenContainer<T, Reason>, Index>,
+    Reason: ForbidReason + Forbidden<T>,
+{
+    fn resolve(&'this self) -> &'cont T {
+        unreachable!("`Forbidden` is never implemented")
+    }
+}
+
+impl<'a, SP, T, Reason> WarmUp<'a, SP, ()> for ForbiddenContainer<T, Reason> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
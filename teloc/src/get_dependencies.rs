@@ -6,17 +6,26 @@ pub trait GetDependencies<'a, Deps, Indexes> {
     fn get_deps(&'a self) -> Deps;
 }
 
+/// Arguments of factory closures: a tuple of dependencies (`(A, &B)`) or an `HList` of them.
+/// Dependencies are got from the provider as an `HList` and converted to the arguments. Do not
+/// implement it by yourself.
+pub trait FactoryArgs {
+    type List;
+
+    fn from_list(list: Self::List) -> Self;
+}
+
 mod impls {
-    use crate::get_dependencies::GetDependencies;
+    use crate::get_dependencies::{FactoryArgs, GetDependencies};
     use crate::resolver::Resolver;
     use frunk::hlist::HList;
     use frunk::{HCons, HNil};
 
-    impl<'a, T, TRest, Cont, Infer, InferRest, SP>
-        GetDependencies<'a, HCons<T, TRest>, HCons<(Cont, Infer), InferRest>> for SP
+    impl<'a, T, TRest, Infer, InferRest, SP>
+        GetDependencies<'a, HCons<T, TRest>, HCons<Infer, InferRest>> for SP
     where
         TRest: HList,
-        SP: Resolver<'a, Cont, T, Infer> + GetDependencies<'a, TRest, InferRest>,
+        SP: Resolver<'a, T, Infer> + GetDependencies<'a, TRest, InferRest>,
     {
         fn get_deps(&'a self) -> HCons<T, TRest> {
             GetDependencies::<TRest, InferRest>::get_deps(self).prepend(self.resolve())
@@ -27,4 +36,47 @@ mod impls {
             HNil
         }
     }
+
+    impl FactoryArgs for HNil {
+        type List = HNil;
+
+        fn from_list(list: HNil) -> Self {
+            list
+        }
+    }
+    impl<H, T: HList> FactoryArgs for HCons<H, T> {
+        type List = Self;
+
+        fn from_list(list: Self) -> Self {
+            list
+        }
+    }
+
+    macro_rules! impl_factory_args_for_tuple {
+        ($($arg:ident),*) => {
+            impl<$($arg),*> FactoryArgs for ($($arg,)*) {
+                type List = frunk::HList![$($arg),*];
+
+                #[allow(non_snake_case, clippy::unused_unit)]
+                fn from_list(list: Self::List) -> Self {
+                    let frunk::hlist_pat![$($arg),*] = list;
+                    ($($arg,)*)
+                }
+            }
+        };
+    }
+
+    impl_factory_args_for_tuple!();
+    impl_factory_args_for_tuple!(A1);
+    impl_factory_args_for_tuple!(A1, A2);
+    impl_factory_args_for_tuple!(A1, A2, A3);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6, A7);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+    impl_factory_args_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
 }
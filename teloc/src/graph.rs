@@ -0,0 +1,97 @@
+//! Support for graph-scoped services, see `ServiceProvider::add_graph_scoped`.
+
+use crate::container::Container;
+use crate::dependency::DependencyClone;
+use crate::get_dependencies::GetDependencies;
+use crate::service_provider::SelectContainer;
+use crate::warm_up::WarmUp;
+use crate::{Dependency, Resolver};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::any::{Any, TypeId};
+use core::cell::RefCell;
+use core::marker::PhantomData;
+
+/// Container of a service that is created once per resolved graph, see
+/// `ServiceProvider::add_graph_scoped`.
+#[derive(Debug)]
+pub struct GraphScopedContainer<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "shared-singletons")]
+impl<T> Clone for GraphScopedContainer<T> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Container for GraphScopedContainer<T> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Graph-scoped services created during one resolution, see `ServiceProvider::graph`.
+#[derive(Debug, Default)]
+pub struct GraphCache(RefCell<BTreeMap<TypeId, Box<dyn Any>>>);
+
+impl Container for GraphCache {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self::default()
+    }
+}
+
+impl GraphCache {
+    fn get<T: Clone + 'static>(&self) -> Option<T> {
+        let services = self.0.borrow();
+        let service = services.get(&TypeId::of::<T>())?;
+        service.downcast_ref::<T>().cloned()
+    }
+
+    fn insert<T: 'static>(&self, service: T) {
+        self.0
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(service));
+    }
+}
+
+impl<'this, 'cont, T, SP, Index, CacheIndex, Deps, Infer>
+    Resolver<
+        'this,
+        T,
+        (
+            &'cont GraphScopedContainer<T>,
+            Index,
+            CacheIndex,
+            Deps,
+            Infer,
+        ),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont GraphScopedContainer<T>, Index>
+        + SelectContainer<'this, &'cont GraphCache, CacheIndex>
+        + GetDependencies<'this, Deps, Infer>,
+    T: Dependency<Deps> + DependencyClone + 'static,
+{
+    fn resolve(&'this self) -> T {
+        let cache = SelectContainer::<&GraphCache, CacheIndex>::get(self);
+        if let Some(service) = cache.get::<T>() {
+            return service;
+        }
+        // The cache is not borrowed here, so dependencies can be graph-scoped too.
+        let service = T::init(self.get_deps());
+        cache.insert(service.clone());
+        service
+    }
+}
+
+impl<'a, SP, T> WarmUp<'a, SP, ()> for GraphScopedContainer<T> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP> WarmUp<'a, SP, ()> for GraphCache {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
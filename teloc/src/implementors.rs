@@ -0,0 +1,120 @@
+//! Support for iterating services by a trait they implement, see
+//! `ServiceProvider::iter_implementors`.
+
+use crate::conditional::ConditionalSingletonContainer;
+use crate::container::{
+    Container, ConvertContainer, InstanceContainer, InstancesContainer, MapContainer,
+    SingletonContainer, SingletonFactoryContainer, TransientContainer, TransientFactoryContainer,
+};
+#[cfg(feature = "std")]
+use crate::deferred::DeferredContainer;
+use crate::forbid::ForbiddenContainer;
+use crate::graph::{GraphCache, GraphScopedContainer};
+use crate::scope_handle::ScopeHandleContainer;
+use crate::warm_up::WarmUp;
+use crate::Resolver;
+use alloc::vec::Vec;
+use core::any::Any;
+use frunk::{HCons, HNil};
+
+/// Container that marks the singleton `T` as an implementor of `Dyn` (usually a trait object)
+/// with a priority, see `ServiceProvider::add_implementor` and
+/// `ServiceProvider::add_implementor_with_priority`.
+pub struct ImplementorContainer<Dyn: ?Sized, T> {
+    cast: fn(&T) -> &Dyn,
+    priority: i32,
+}
+
+impl<Dyn: ?Sized, T> Container for ImplementorContainer<Dyn, T> {
+    type Data = (fn(&T) -> &Dyn, i32);
+
+    fn init((cast, priority): (fn(&T) -> &Dyn, i32)) -> Self {
+        Self { cast, priority }
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<Dyn: ?Sized, T> Clone for ImplementorContainer<Dyn, T> {
+    fn clone(&self) -> Self {
+        Self {
+            cast: self.cast,
+            priority: self.priority,
+        }
+    }
+}
+
+impl<'a, SP, Dyn: ?Sized, T> WarmUp<'a, SP, ()> for ImplementorContainer<Dyn, T> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+/// The trait, used by `ServiceProvider::iter_implementors` to collect implementors of `Dyn` with
+/// their priorities from containers, in order of registration. Implement it (as a no-op with `Infer = ()`) for your own container if you wish that
+/// providers with it can be iterated.
+pub trait CollectImplementors<'a, SP, Dyn: ?Sized, Infer> {
+    fn collect(&'a self, sp: &'a SP, implementors: &mut Vec<(i32, &'a Dyn)>);
+}
+
+impl<'a, SP, Dyn: ?Sized> CollectImplementors<'a, SP, Dyn, HNil> for HNil {
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, Dyn, H, Tail, InferH, InferTail>
+    CollectImplementors<'a, SP, Dyn, HCons<InferH, InferTail>> for HCons<H, Tail>
+where
+    Dyn: ?Sized,
+    H: CollectImplementors<'a, SP, Dyn, InferH>,
+    Tail: CollectImplementors<'a, SP, Dyn, InferTail>,
+{
+    fn collect(&'a self, sp: &'a SP, implementors: &mut Vec<(i32, &'a Dyn)>) {
+        // The head is the last registered container.
+        self.tail.collect(sp, implementors);
+        self.head.collect(sp, implementors);
+    }
+}
+
+impl<'a, SP, Dyn, D, T, Infer> CollectImplementors<'a, SP, Dyn, Infer>
+    for ImplementorContainer<D, T>
+where
+    SP: Resolver<'a, &'a T, Infer>,
+    Dyn: ?Sized + 'static,
+    D: ?Sized + 'static,
+    T: 'static,
+{
+    fn collect(&'a self, sp: &'a SP, implementors: &mut Vec<(i32, &'a Dyn)>) {
+        // Type of a cast is `'static`, so it can be compared with the requested one at runtime
+        // without a type-level equality of `D` and `Dyn`.
+        let cast: &dyn Any = &self.cast;
+        if let Some(cast) = cast.downcast_ref::<fn(&T) -> &Dyn>() {
+            implementors.push((self.priority, cast(sp.resolve())));
+        }
+    }
+}
+
+macro_rules! impl_noop_collect {
+    ($($cont:ty => ($($param:ident),*)),*) => {
+        $(
+        impl<'a, SP, Dyn: ?Sized, $($param),*> CollectImplementors<'a, SP, Dyn, ()> for $cont {
+            fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+        }
+        )*
+    };
+}
+
+impl_noop_collect!(
+    TransientContainer<T> => (T),
+    SingletonContainer<T> => (T),
+    InstanceContainer<T> => (T),
+    InstancesContainer<T> => (T),
+    ConvertContainer<Cont, T, U> => (Cont, T, U),
+    MapContainer<Cont, T, U, F> => (Cont, T, U, F),
+    TransientFactoryContainer<Deps, T, F> => (Deps, T, F),
+    SingletonFactoryContainer<Deps, T, F> => (Deps, T, F),
+    ConditionalSingletonContainer<DepsA, DepsB, T, A, B> => (DepsA, DepsB, T, A, B),
+    ForbiddenContainer<T, Reason> => (T, Reason),
+    GraphScopedContainer<T> => (T),
+    GraphCache => (),
+    ScopeHandleContainer<Types> => (Types)
+);
+
+#[cfg(feature = "std")]
+impl_noop_collect!(DeferredContainer<T> => (T));
@@ -0,0 +1,45 @@
+//! Type-erased resolution of services from a request scope, used by integrations where the
+//! extractor does not know the type of the scope.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Request scope with erased type.
+pub(crate) type Scope = Arc<dyn Any + Send + Sync>;
+
+type Resolve = Arc<dyn Fn(&(dyn Any + Send + Sync)) -> Box<dyn Any + Send> + Send + Sync>;
+
+/// Resolvers of the registered types, keyed by `TypeId` of the resolved type. Each resolver gets
+/// the type-erased scope by a reference of any lifetime, downcasts it to the scope type it was
+/// registered for and returns the boxed value.
+#[derive(Clone, Default)]
+pub(crate) struct Injectors(HashMap<TypeId, Resolve>);
+
+impl Injectors {
+    /// Register the function that resolves `T` from the scope. It gets the scope by a reference of
+    /// any lifetime, so `T` cannot borrow services from the scope.
+    pub(crate) fn register<S, T, F>(&mut self, resolve: F)
+    where
+        S: 'static,
+        T: Send + 'static,
+        F: Fn(&S) -> T + Send + Sync + 'static,
+    {
+        let resolve: Resolve = Arc::new(move |scope| {
+            let scope = scope
+                .downcast_ref::<S>()
+                .expect("scope is created by the same integration");
+            Box::new(resolve(scope))
+        });
+        self.0.insert(TypeId::of::<T>(), resolve);
+    }
+
+    /// Resolve `T` from the `scope`, or return `None` if `T` is not registered.
+    pub(crate) fn resolve<T: 'static>(&self, scope: &(dyn Any + Send + Sync)) -> Option<T> {
+        let resolve = self.0.get(&TypeId::of::<T>())?;
+        let value = resolve(scope)
+            .downcast::<T>()
+            .expect("injector is registered for the same type");
+        Some(*value)
+    }
+}
@@ -0,0 +1,147 @@
+//! Scheduled jobs with per-run scopes.
+//!
+//! Jobs are closures that get a scope forked from the `ServiceProvider` and return a boxed future,
+//! that can borrow the scope. Usually a job calls an `async fn` with arguments resolved from the
+//! scope. Each job is registered with a cron expression (with seconds, as parsed by the `cron`
+//! crate). On every tick the `Scheduler` forks a new scope and runs the job with it on the tokio
+//! runtime. The scope is owned by the spawned task and is dropped when the job completes.
+//!
+//! Usage:
+//! ```
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use teloc::jobs::Scheduler;
+//! use teloc::*;
+//!
+//! struct Cleaner(AtomicUsize);
+//! #[inject]
+//! impl Cleaner {
+//!     fn new() -> Self { Cleaner(AtomicUsize::new(0)) }
+//! }
+//!
+//! async fn cleanup(cleaner: Arc<Cleaner>) {
+//!     cleaner.0.fetch_add(1, Ordering::SeqCst);
+//! }
+//!
+//! let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Cleaner>>());
+//! let scheduler = Scheduler::new(sp.clone())
+//!     // Every second.
+//!     .add("* * * * * *", |scope| Box::pin(cleanup(scope.resolve())))
+//!     .unwrap();
+//!
+//! let runtime = tokio::runtime::Builder::new_current_thread()
+//!     .enable_time()
+//!     .start_paused(true)
+//!     .build()
+//!     .unwrap();
+//! // Usually the shutdown future is `tokio::signal::ctrl_c()` or a cancellation token.
+//! runtime.block_on(async {
+//!     scheduler.run(tokio::time::sleep(Duration::from_secs(3))).await;
+//! });
+//!
+//! let cleaner: Arc<Cleaner> = sp.resolve();
+//! assert!(cleaner.0.load(Ordering::SeqCst) >= 2);
+//! ```
+
+use crate::consumer::BoxFuture;
+use crate::{ArcFork, ServiceProvider};
+use chrono::Utc;
+use cron::Schedule;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+pub use cron::error::Error as ScheduleError;
+
+/// Scope that is forked from the `ServiceProvider` for every run of a job.
+pub type JobScope<SP> = ArcFork<SP>;
+
+type RunJob = Box<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+struct Job {
+    schedule: Schedule,
+    run: RunJob,
+}
+
+/// Runs registered jobs by their schedules. See the module documentation.
+pub struct Scheduler<SP> {
+    sp: Arc<SP>,
+    jobs: Vec<Job>,
+}
+
+impl<ParSP, DepsSP> Scheduler<ServiceProvider<ParSP, DepsSP>>
+where
+    ParSP: Send + Sync + 'static,
+    DepsSP: Send + Sync + 'static,
+{
+    /// Creates a `Scheduler` without jobs.
+    pub fn new(sp: Arc<ServiceProvider<ParSP, DepsSP>>) -> Self {
+        Scheduler {
+            sp,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Register a job that runs by the cron `expression`. The job gets a scope forked for every
+    /// run, the returned future can borrow it.
+    pub fn add<F>(mut self, expression: &str, job: F) -> Result<Self, ScheduleError>
+    where
+        F: for<'s> Fn(&'s JobScope<ServiceProvider<ParSP, DepsSP>>) -> BoxFuture<'s, ()>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let schedule = Schedule::from_str(expression)?;
+        let sp = self.sp.clone();
+        let job = Arc::new(job);
+        let run: RunJob = Box::new(move || {
+            let job = job.clone();
+            let scope = sp.fork_arc();
+            Box::pin(async move { job(&scope).await })
+        });
+        self.jobs.push(Job { schedule, run });
+        Ok(self)
+    }
+
+    /// Run jobs until the `shutdown` future completes. After that no new runs are started, and
+    /// the returned future completes when all running jobs complete. Must be called within the
+    /// tokio runtime with the time driver enabled.
+    pub async fn run(self, shutdown: impl Future<Output = ()>) {
+        let mut running = JoinSet::new();
+        // Runs are scheduled by the tokio clock, relative to the wall clock time of the start.
+        let (started, started_at) = (Instant::now(), Utc::now());
+        let mut next: Vec<_> = self
+            .jobs
+            .iter()
+            .map(|job| job.schedule.upcoming(Utc).next())
+            .collect();
+        tokio::pin!(shutdown);
+
+        loop {
+            let nearest = next
+                .iter()
+                .enumerate()
+                .filter_map(|(i, at)| at.map(|at| (i, at)))
+                .min_by_key(|(_, at)| *at);
+            let Some((i, at)) = nearest else {
+                // No more runs are scheduled.
+                break;
+            };
+            let delay = (at - started_at).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep_until(started + delay) => {}
+            }
+
+            running.spawn((self.jobs[i].run)());
+            next[i] = self.jobs[i].schedule.after(&at).next();
+            // Collect completed runs, so the set does not grow.
+            while running.try_join_next().is_some() {}
+        }
+
+        while running.join_next().await.is_some() {}
+    }
+}
@@ -0,0 +1,216 @@
+use crate::container::Container;
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Resolver, ServiceProvider};
+use core::marker::PhantomData;
+use frunk::hlist::HList;
+use frunk::{HCons, HNil};
+use std::collections::HashMap;
+
+/// Implementation `T` of `Dyn` registered under a key, see `KeyedMap::insert`.
+pub struct Keyed<Dyn: ?Sized, T> {
+    key: String,
+    convert: fn(T) -> Box<Dyn>,
+}
+
+impl<Dyn: ?Sized, T> Clone for Keyed<Dyn, T> {
+    fn clone(&self) -> Self {
+        Keyed {
+            key: self.key.clone(),
+            convert: self.convert,
+        }
+    }
+}
+
+/// Implementations of `Dyn` (usually a trait object) under string keys, that are resolved together
+/// as `HashMap<String, Box<Dyn>>`. Register it by `ServiceProvider::add_keyed_map`.
+pub struct KeyedMap<Dyn: ?Sized, Entries = HNil> {
+    entries: Entries,
+    _dyn: PhantomData<fn() -> Box<Dyn>>,
+}
+
+impl<Dyn: ?Sized> KeyedMap<Dyn> {
+    pub fn new() -> Self {
+        KeyedMap {
+            entries: HNil,
+            _dyn: PhantomData,
+        }
+    }
+}
+
+impl<Dyn: ?Sized> Default for KeyedMap<Dyn> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Dyn: ?Sized, Entries> KeyedMap<Dyn, Entries> {
+    /// Add `T` under the `key`. `T` is resolved from the provider when the map is resolved, so it
+    /// must be registered in the provider by itself. `convert` boxes it as `Dyn`, for trait
+    /// objects it is usually `|s| Box::new(s)`. An entry with the same key as an earlier one
+    /// replaces it.
+    pub fn insert<T>(
+        self,
+        key: impl Into<String>,
+        convert: fn(T) -> Box<Dyn>,
+    ) -> KeyedMap<Dyn, HCons<Keyed<Dyn, T>, Entries>> {
+        let entry = Keyed {
+            key: key.into(),
+            convert,
+        };
+        KeyedMap {
+            entries: HCons {
+                head: entry,
+                tail: self.entries,
+            },
+            _dyn: PhantomData,
+        }
+    }
+}
+
+/// The trait, used to resolve entries of `KeyedMap` from the provider `SP` into a map.
+pub trait ResolveKeyed<'a, SP, Dyn: ?Sized, Infer> {
+    fn resolve_keyed(&self, sp: &'a SP, map: &mut HashMap<String, Box<Dyn>>);
+}
+
+impl<'a, SP, Dyn: ?Sized> ResolveKeyed<'a, SP, Dyn, HNil> for HNil {
+    fn resolve_keyed(&self, _: &'a SP, _: &mut HashMap<String, Box<Dyn>>) {}
+}
+
+impl<'a, SP, Dyn, T, Tail, InferT, InferTail> ResolveKeyed<'a, SP, Dyn, (InferT, InferTail)>
+    for HCons<Keyed<Dyn, T>, Tail>
+where
+    Dyn: ?Sized,
+    SP: Resolver<'a, T, InferT>,
+    Tail: ResolveKeyed<'a, SP, Dyn, InferTail>,
+{
+    fn resolve_keyed(&self, sp: &'a SP, map: &mut HashMap<String, Box<Dyn>>) {
+        // The head is the last inserted entry, so it replaces earlier ones with the same key.
+        self.tail.resolve_keyed(sp, map);
+        map.insert(self.head.key.clone(), (self.head.convert)(sp.resolve()));
+    }
+}
+
+/// Container of a `KeyedMap`, see `ServiceProvider::add_keyed_map`.
+pub struct KeyedMapContainer<Dyn: ?Sized, Entries>(KeyedMap<Dyn, Entries>);
+
+impl<Dyn: ?Sized, Entries> Container for KeyedMapContainer<Dyn, Entries> {
+    type Data = KeyedMap<Dyn, Entries>;
+
+    fn init(map: KeyedMap<Dyn, Entries>) -> Self {
+        Self(map)
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<Dyn: ?Sized, Entries: Clone> Clone for KeyedMapContainer<Dyn, Entries> {
+    fn clone(&self) -> Self {
+        Self(KeyedMap {
+            entries: self.0.entries.clone(),
+            _dyn: PhantomData,
+        })
+    }
+}
+
+impl<'this, 'cont, Dyn, Entries, SP, Index, Infer>
+    Resolver<
+        'this,
+        HashMap<String, Box<Dyn>>,
+        (&'cont KeyedMapContainer<Dyn, Entries>, Index, Infer),
+    > for SP
+where
+    SP: SelectContainer<'this, &'cont KeyedMapContainer<Dyn, Entries>, Index>,
+    Dyn: ?Sized + 'cont,
+    Entries: ResolveKeyed<'this, SP, Dyn, Infer> + 'cont,
+{
+    fn resolve(&'this self) -> HashMap<String, Box<Dyn>> {
+        let mut map = HashMap::new();
+        self.get().0.entries.resolve_keyed(self, &mut map);
+        map
+    }
+}
+
+impl<'a, SP, Dyn: ?Sized, Entries> WarmUp<'a, SP, ()> for KeyedMapContainer<Dyn, Entries> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, D: ?Sized, Dyn: ?Sized, Entries> CollectImplementors<'a, SP, D, ()>
+    for KeyedMapContainer<Dyn, Entries>
+{
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a D)>) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, Entries> CollectOrder<'a, SP, ()> for KeyedMapContainer<Dyn, Entries> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add implementations of `Dyn` under string keys, that are resolved together as
+    /// `HashMap<String, Box<Dyn>>`. It lets a service pick an implementation by name at runtime
+    /// (payment providers, export formats). Every resolve creates a new map, and every entry of it
+    /// is resolved from the provider, so the lifetime of an implementation is the lifetime it is
+    /// registered with.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::collections::HashMap;
+    /// use teloc::*;
+    ///
+    /// trait Exporter {
+    ///     fn export(&self, rows: &[u32]) -> String;
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Csv;
+    /// impl Exporter for Csv {
+    ///     fn export(&self, rows: &[u32]) -> String {
+    ///         rows.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+    ///     }
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Json;
+    /// impl Exporter for Json {
+    ///     fn export(&self, rows: &[u32]) -> String { format!("{:?}", rows) }
+    /// }
+    ///
+    /// struct Reports {
+    ///     exporters: HashMap<String, Box<dyn Exporter>>,
+    /// }
+    /// #[inject]
+    /// impl Reports {
+    ///     fn new(exporters: HashMap<String, Box<dyn Exporter>>) -> Self { Reports { exporters } }
+    /// }
+    /// impl Reports {
+    ///     fn export(&self, format: &str, rows: &[u32]) -> Option<String> {
+    ///         self.exporters.get(format).map(|e| e.export(rows))
+    ///     }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_transient::<Csv>()
+    ///     .add_transient::<Json>()
+    ///     .add_keyed_map(
+    ///         KeyedMap::<dyn Exporter>::new()
+    ///             .insert::<Csv>("csv", |e| Box::new(e))
+    ///             .insert::<Json>("json", |e| Box::new(e)),
+    ///     )
+    ///     .add_transient::<Reports>();
+    ///
+    /// let reports: Reports = sp.resolve();
+    /// assert_eq!(reports.export("csv", &[1, 2]).unwrap(), "1,2");
+    /// assert_eq!(reports.export("json", &[1, 2]).unwrap(), "[1, 2]");
+    /// assert!(reports.export("xml", &[1, 2]).is_none());
+    /// ```
+    pub fn add_keyed_map<Dyn, Entries>(
+        self,
+        map: KeyedMap<Dyn, Entries>,
+    ) -> ServiceProvider<Parent, HCons<KeyedMapContainer<Dyn, Entries>, Conts>>
+    where
+        Dyn: ?Sized,
+    {
+        self._add::<KeyedMapContainer<Dyn, Entries>>(map)
+    }
+}
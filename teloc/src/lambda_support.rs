@@ -0,0 +1,116 @@
+//! Support for `lambda_runtime` crate.
+
+use crate::container::InstanceContainer;
+use crate::dependency::DependencyClone;
+use crate::{ArcFork, ServiceProvider};
+use frunk::{HCons, HNil};
+use lambda_runtime::{Context, LambdaEvent};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::Poll;
+use tower_service::Service;
+
+/// Scope that is forked from the `ServiceProvider` for every invocation. It contains the
+/// invocation `Context` and the payload of the event.
+pub type LambdaScope<SP, T> =
+    ArcFork<SP, HCons<InstanceContainer<Context>, HCons<InstanceContainer<T>, HNil>>>;
+
+/// Lambda handler that owns the `ServiceProvider` built during cold start and forks a scope for
+/// every invocation. It implements `tower::Service<LambdaEvent<T>>`, so it can be passed to
+/// `lambda_runtime::run`.
+///
+/// - Scope factory gets the forked scope with the `Context` and the payload, and can add some local
+///   dependencies that will be unique in different invocations.
+/// - Handler function gets the scope by value, so services can be resolved from it inside the
+///   returned future.
+///
+/// Usage:
+/// ```
+/// use lambda_runtime::{Context, LambdaEvent};
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Greeter<'a> {
+///     name: &'a String,
+///     request_id: String,
+/// }
+/// #[inject]
+/// impl<'a> Greeter<'a> {
+///     fn new(name: &'a String, context: Context) -> Self {
+///         Greeter { name, request_id: context.request_id }
+///     }
+/// }
+///
+/// let sp = Arc::new(ServiceProvider::new().add_transient::<Greeter>());
+/// let handler = DiLambdaHandler::new(sp, |scope| scope, |scope: LambdaScope<_, String>| async move {
+///     let greeter: Greeter = scope.resolve();
+///     Ok::<_, lambda_runtime::Error>(format!("Hello, {}! ({})", greeter.name, greeter.request_id))
+/// });
+///
+/// // In `main`: `lambda_runtime::run(handler).await`
+/// # let _ = handler;
+/// ```
+pub struct DiLambdaHandler<SP, T, ScopeFactory, F> {
+    sp: Arc<SP>,
+    scope_factory: ScopeFactory,
+    f: F,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<ParSP, DepsSP, T, ScopeFactory, ScopeResult, F, Fut>
+    DiLambdaHandler<ServiceProvider<ParSP, DepsSP>, T, ScopeFactory, F>
+where
+    ScopeFactory: Fn(LambdaScope<ServiceProvider<ParSP, DepsSP>, T>) -> ScopeResult,
+    F: Fn(ScopeResult) -> Fut,
+    Fut: Future,
+{
+    /// Creates `DiLambdaHandler` with specified `ServiceProvider`, scope factory and handler
+    /// function.
+    pub fn new(sp: Arc<ServiceProvider<ParSP, DepsSP>>, scope_factory: ScopeFactory, f: F) -> Self {
+        DiLambdaHandler {
+            sp,
+            scope_factory,
+            f,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<SP, T, ScopeFactory: Clone, F: Clone> Clone for DiLambdaHandler<SP, T, ScopeFactory, F> {
+    fn clone(&self) -> Self {
+        DiLambdaHandler {
+            sp: self.sp.clone(),
+            scope_factory: self.scope_factory.clone(),
+            f: self.f.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<ParSP, DepsSP, T, ScopeFactory, ScopeResult, F, Fut, R, E> Service<LambdaEvent<T>>
+    for DiLambdaHandler<ServiceProvider<ParSP, DepsSP>, T, ScopeFactory, F>
+where
+    ScopeFactory: Fn(LambdaScope<ServiceProvider<ParSP, DepsSP>, T>) -> ScopeResult,
+    F: Fn(ScopeResult) -> Fut,
+    Fut: Future<Output = Result<R, E>>,
+{
+    type Response = R;
+    type Error = E;
+    type Future = Fut;
+
+    fn poll_ready(&mut self, _: &mut std::task::Context<'_>) -> Poll<Result<(), E>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, event: LambdaEvent<T>) -> Fut {
+        let scope = self
+            .sp
+            .fork_arc()
+            .add_instance(event.payload)
+            .add_instance(event.context);
+        (self.f)((self.scope_factory)(scope))
+    }
+}
+
+impl DependencyClone for Context {}
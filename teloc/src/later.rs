@@ -0,0 +1,167 @@
+//! Support for mutually-referencing singletons, see `ServiceProvider::add_singleton_cyclic`.
+
+use crate::container::Container;
+use crate::dependency::DependencyClone;
+use crate::get_dependencies::GetDependencies;
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Dependency, Resolver};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "sync")]
+use once_cell::sync::OnceCell;
+#[cfg(not(feature = "sync"))]
+use once_cell::unsync::OnceCell;
+
+/// Handle to a singleton registered by `ServiceProvider::add_singleton_cyclic`, that is filled
+/// right after the singleton is created. Services that the singleton depends on take `Later<T>`
+/// instead of `T`, so they can refer back to the singleton without `unsafe` or `Option` fields.
+///
+/// `Later` can be cloned, all clones share the same value. It keeps the singleton alive, so a
+/// cycle of `Rc`/`Arc` through it is not freed until the end of the program. Services must not
+/// resolve `T` itself while `T` is being created.
+///
+/// Usage:
+/// ```
+/// use std::rc::Rc;
+/// use teloc::*;
+///
+/// struct Registry {
+///     plugins: Rc<Plugins>,
+/// }
+/// #[inject]
+/// impl Registry {
+///     fn new(plugins: Rc<Plugins>) -> Self { Registry { plugins } }
+/// }
+///
+/// struct Plugins {
+///     registry: Later<Rc<Registry>>,
+/// }
+/// #[inject]
+/// impl Plugins {
+///     fn new(registry: Later<Rc<Registry>>) -> Self { Plugins { registry } }
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_singleton_cyclic::<Rc<Registry>>()
+///     .add_singleton::<Rc<Plugins>>();
+///
+/// let registry: Rc<Registry> = sp.resolve();
+/// let back = registry.plugins.registry.get().unwrap();
+/// assert!(Rc::ptr_eq(&registry, back));
+/// ```
+pub struct Later<T>(Arc<OnceCell<T>>);
+
+impl<T> Later<T> {
+    /// Returns the singleton, or `None` if it is not created yet (for example, while dependencies
+    /// of the singleton are being created).
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.0.get()
+    }
+}
+
+impl<T> Clone for Later<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> DependencyClone for Later<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Later<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Later").field(&self.get()).finish()
+    }
+}
+
+/// Container of a singleton that can be referred by its dependencies as `Later<T>`, see
+/// `ServiceProvider::add_singleton_cyclic`.
+pub struct CyclicSingletonContainer<T>(Later<T>);
+
+impl<T> Container for CyclicSingletonContainer<T> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(Later(Arc::new(OnceCell::new())))
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<T> Clone for CyclicSingletonContainer<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> CyclicSingletonContainer<T> {
+    #[inline]
+    pub fn get(&self) -> &Later<T> {
+        &self.0
+    }
+
+    fn get_or_init<'a, SP, Deps, Infer>(&self, sp: &'a SP) -> &T
+    where
+        SP: GetDependencies<'a, Deps, Infer>,
+        T: Dependency<Deps>,
+    {
+        // Dependencies take `Later<T>`, which does not need the cell, so it is not reentered.
+        (self.0).0.get_or_init(|| T::init(sp.get_deps()))
+    }
+}
+
+impl<'this, 'cont, T, SP, Index, Deps, Infer>
+    Resolver<'this, T, (&'cont CyclicSingletonContainer<T>, Index, Deps, Infer)> for SP
+where
+    SP: SelectContainer<'this, &'cont CyclicSingletonContainer<T>, Index>
+        + GetDependencies<'this, Deps, Infer>,
+    T: Dependency<Deps> + DependencyClone + 'cont,
+{
+    fn resolve(&'this self) -> T {
+        self.get().get_or_init(self).clone()
+    }
+}
+
+impl<'this, 'cont, T, SP, Index, Deps, Infer>
+    Resolver<'this, &'cont T, (&'cont CyclicSingletonContainer<T>, Index, Deps, Infer)> for SP
+where
+    SP: SelectContainer<'this, &'cont CyclicSingletonContainer<T>, Index>
+        + GetDependencies<'this, Deps, Infer>,
+    T: Dependency<Deps> + 'cont,
+{
+    fn resolve(&'this self) -> &'cont T {
+        self.get().get_or_init(self)
+    }
+}
+
+impl<'this, 'cont, T, SP, Index>
+    Resolver<'this, Later<T>, (&'cont CyclicSingletonContainer<T>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont CyclicSingletonContainer<T>, Index>,
+    T: 'cont,
+{
+    fn resolve(&'this self) -> Later<T> {
+        self.get().get().clone()
+    }
+}
+
+impl<'a, SP, T, Deps, Infer> WarmUp<'a, SP, (Deps, Infer)> for CyclicSingletonContainer<T>
+where
+    SP: GetDependencies<'a, Deps, Infer>,
+    T: Dependency<Deps> + 'a,
+{
+    fn warm_up(&'a self, sp: &'a SP) {
+        self.get_or_init(sp);
+    }
+}
+
+impl<'a, SP, Dyn: ?Sized, T> CollectImplementors<'a, SP, Dyn, ()> for CyclicSingletonContainer<T> {
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, T> CollectOrder<'a, SP, ()> for CyclicSingletonContainer<T> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
@@ -5,7 +5,7 @@
 //! There are four lifetimes for dependencies:
 //! 1. `Transient`. Service will be created when resolves. Can depend on dependencies with anything lifetime.
 //! 2. `Singleton`. Service will be created once at `ServiceProvider` when it resolved (lazy). Can depend on dependencies
-//! with anything lifetime. Cannot depend on services from forked `ServiceProvider` instances.
+//!    with anything lifetime. Cannot depend on services from forked `ServiceProvider` instances.
 //! 3. `Instance`. Dependency was created outside of `ServiceProvider` and can be used by any other dependency.
 //!
 //! How to work:
@@ -57,29 +57,162 @@
 //! ```
 
 #![deny(unsafe_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 #[cfg(feature = "actix-support")]
 mod actix_support;
+mod async_factory;
+#[cfg(feature = "async-graphql-support")]
+mod async_graphql_support;
+#[cfg(feature = "axum-support")]
+pub mod axum_support;
+#[cfg(feature = "clap-support")]
+mod clap_support;
+mod cleanup;
+mod conditional;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "consumer")]
+mod consumer;
 mod container;
+#[cfg(feature = "std")]
+mod deferred;
 mod dependency;
+#[cfg(feature = "devtools")]
+pub mod devtools;
+#[cfg(feature = "dotenv")]
+pub mod dotenv;
+pub mod dynamic;
+#[cfg(feature = "figment-support")]
+mod figment_support;
+#[cfg(feature = "flat-storage")]
+mod flat;
+mod forbid;
 mod get_dependencies;
+mod graph;
+mod implementors;
 mod index;
+#[cfg(any(
+    feature = "tower-support",
+    feature = "rocket-support",
+    feature = "tauri-support"
+))]
+mod injectors;
+#[cfg(feature = "jobs")]
+pub mod jobs;
+#[cfg(feature = "std")]
+mod keyed;
+#[cfg(feature = "lambda-support")]
+mod lambda_support;
+mod later;
+#[cfg(feature = "log-support")]
+pub mod logging;
+mod macros;
+#[cfg(feature = "mockall")]
+pub mod mock;
+mod open_generic;
+mod pool;
+pub mod profile;
+mod projected;
+mod provider;
+#[cfg(feature = "r2d2-support")]
+mod r2d2_support;
+#[cfg(feature = "std")]
+pub mod random;
 mod resolver;
+#[cfg(feature = "rocket-support")]
+pub mod rocket_support;
+mod scope_handle;
 mod service_provider;
+mod shared_parent;
+mod startup_order;
+#[cfg(feature = "tauri-support")]
+pub mod tauri_support;
+#[cfg(feature = "std")]
+pub mod time;
+#[cfg(feature = "tonic-support")]
+mod tonic_support;
+#[cfg(feature = "tower-support")]
+pub mod tower_support;
+#[cfg(feature = "std")]
+mod unit_of_work;
+mod validate;
+mod warm_up;
+#[cfg(feature = "warp-support")]
+pub mod warp_support;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 #[cfg(feature = "actix-support")]
-pub use actix_support::DiActixHandler;
+pub use actix_support::{
+    ActixDataTuple, DiActixHandler, DiGuard, DiTransform, ErrorMapper, Inject, Injector,
+    LocalBoxFuture, LocalFuture, PerApp, PerRequest, PerRequestService, ScopeGuard, SendBoxFuture,
+    SendFuture, SendScopeGuard, SharedService,
+};
+#[cfg(feature = "async-graphql-support")]
+pub use async_graphql_support::DataLoaderFactory;
+#[cfg(feature = "consumer")]
+pub use consumer::{BoxFuture, ConsumeFuture, ConsumerScope, DiConsumer};
+#[cfg(feature = "flat-storage")]
+pub use flat::{Flat, FlatIndex, FlatRest, IntoFlat};
+#[cfg(feature = "lambda-support")]
+pub use lambda_support::{DiLambdaHandler, LambdaScope};
+#[cfg(feature = "r2d2-support")]
+pub use r2d2_support::ConnectionContainer;
+#[cfg(feature = "tonic-support")]
+pub use tonic_support::{DiGrpcService, DiGrpcServiceBuilder, DiInterceptor, RpcScope};
 
 pub use {
+    async_factory::{AsyncResolver, AsyncTransientFactoryContainer, ResolveReady},
+    cleanup::OnDropContainer,
+    conditional::ConditionalSingletonContainer,
     container::*,
-    dependency::{Dependency, DependencyClone},
-    get_dependencies::GetDependencies,
-    resolver::Resolver,
-    service_provider::{EmptyServiceProvider, SelectContainer, ServiceProvider},
-    teloc_macros::{inject, Dependency},
+    dependency::{AutoLifetime, Dependency, DependencyClone, InjectFields},
+    forbid::{ForbidReason, Forbidden, ForbiddenContainer},
+    frunk::{hlist::Selector, HCons, HList, HNil},
+    get_dependencies::{FactoryArgs, GetDependencies},
+    graph::{GraphCache, GraphScopedContainer},
+    implementors::{CollectImplementors, ImplementorContainer},
+    later::{CyclicSingletonContainer, Later},
+    open_generic::{OpenGeneric, OpenGenericContainer},
+    pool::{AsyncPool, PooledResource},
+    projected::Projected,
+    provider::{Provider, ResolveProvider},
+    resolver::{check_provider, ResolveStruct, ResolveTuple, ResolveWrapped, Resolver, TaskSafe},
+    scope_handle::{ResolveAny, ScopeHandle, ScopeHandleContainer},
+    service_provider::{
+        check_unique, ArcFork, EmptyServiceProvider, Fork, RcFork, SelectContainer,
+        ServiceProvider, SpawnSafe, UniqueContainers, UniqueProvider,
+    },
+    shared_parent::{SharedFork, SharedIndex, SharedParent, SharedParentBuilder},
+    startup_order::{CollectOrder, OrderContainer, StartupError, StartupOrder},
+    teloc_macros::{
+        detect_cycles, factory, inject, inject_test, main, scaffold, Dependency, DependencyClone,
+        InjectFields, Resolvable,
+    },
+    validate::{Validate, ValidateContainer, ValidationError},
+    warm_up::WarmUp,
+};
+
+#[cfg(feature = "inventory-support")]
+pub use teloc_macros::service;
+
+#[cfg(feature = "std")]
+pub use {
+    deferred::{Deferred, DeferredContainer},
+    keyed::{Keyed, KeyedMap, KeyedMapContainer, ResolveKeyed},
+    unit_of_work::{Transaction, TransactionGuard, UnitOfWork},
 };
 
 #[doc(hidden)]
 pub mod reexport {
+    pub use alloc::boxed::Box;
     pub use {frunk, frunk::HList};
+
+    #[cfg(feature = "axum-support")]
+    pub use axum;
+    #[cfg(feature = "inventory-support")]
+    pub use inventory;
 }
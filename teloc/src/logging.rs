@@ -0,0 +1,190 @@
+//! Injectable logger that writes to the `log` facade. Enabled by the `log-support` feature.
+//!
+//! Services take `&Logger` (or `&ScopedLogger` in a forked scope) instead of calling the global
+//! `log` macros, so fields of the scope
+//! (request id, user, tenant) are attached to every record without passing them around. The root
+//! logger is registered by `ServiceProvider::with_logger`, a forked scope adds its fields by
+//! `ServiceProvider::with_scoped_logger`, and services of the scope take `&ScopedLogger`.
+//!
+//! Fields are passed as key-values of the `log::Record`, so the logger implementation must
+//! support them (for example `env_logger` with its `kv` feature). `tracing` subscribers receive
+//! records through `tracing-log`.
+//!
+//! Usage:
+//! ```
+//! use teloc::logging::ScopedLogger;
+//! use teloc::*;
+//!
+//! struct Checkout<'a> {
+//!     logger: &'a ScopedLogger,
+//! }
+//! #[inject]
+//! impl<'a> Checkout<'a> {
+//!     fn new(logger: &'a ScopedLogger) -> Self { Checkout { logger } }
+//! }
+//! impl Checkout<'_> {
+//!     fn run(&self) {
+//!         // Written with the `shop` target and the `request_id` field.
+//!         self.logger.info("order placed");
+//!     }
+//! }
+//!
+//! let sp = ServiceProvider::new()
+//!     .with_logger("shop")
+//!     .add_transient::<Checkout>();
+//!
+//! let scope = sp.fork().with_scoped_logger(&[("request_id", &42)]);
+//! let checkout: Checkout = scope.resolve();
+//! checkout.run();
+//! ```
+
+use crate::container::{InstanceContainer, SingletonContainer};
+use crate::dependency::Dependency;
+use crate::ServiceProvider;
+use frunk::hlist::HList;
+use frunk::{HCons, HNil};
+use log::{Level, Metadata, Record};
+use std::fmt::{self, Display};
+use std::ops::Deref;
+use std::panic::Location;
+
+type Fields = Vec<(&'static str, String)>;
+
+/// Logger with a target and fields that are attached to every record.
+#[derive(Debug, Clone)]
+pub struct Logger {
+    target: &'static str,
+    fields: Fields,
+}
+
+impl Logger {
+    pub fn new(target: &'static str) -> Self {
+        Logger {
+            target,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+
+    /// Returns a logger with the same target and fields, and with the new field.
+    pub fn with_field(&self, key: &'static str, value: impl Display) -> Logger {
+        let mut logger = self.clone();
+        logger.fields.push((key, value.to_string()));
+        logger
+    }
+
+    /// Write the message with the level. File and line of the record are of the caller.
+    #[track_caller]
+    pub fn log(&self, level: Level, message: impl Display) {
+        let location = Location::caller();
+        let metadata = Metadata::builder().level(level).target(self.target).build();
+        let logger = log::logger();
+        if level > log::max_level() || !logger.enabled(&metadata) {
+            return;
+        }
+        logger.log(
+            &Record::builder()
+                .metadata(metadata)
+                .args(format_args!("{}", message))
+                .file(Some(location.file()))
+                .line(Some(location.line()))
+                .key_values(&self.fields)
+                .build(),
+        );
+    }
+
+    #[track_caller]
+    pub fn error(&self, message: impl Display) {
+        self.log(Level::Error, message)
+    }
+
+    #[track_caller]
+    pub fn warn(&self, message: impl Display) {
+        self.log(Level::Warn, message)
+    }
+
+    #[track_caller]
+    pub fn info(&self, message: impl Display) {
+        self.log(Level::Info, message)
+    }
+
+    #[track_caller]
+    pub fn debug(&self, message: impl Display) {
+        self.log(Level::Debug, message)
+    }
+
+    #[track_caller]
+    pub fn trace(&self, message: impl Display) {
+        self.log(Level::Trace, message)
+    }
+}
+
+/// Fields of a forked scope, added by `ServiceProvider::with_scoped_logger`.
+pub struct LogFields(Fields);
+
+impl fmt::Debug for LogFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(k, v)| (k, v)))
+            .finish()
+    }
+}
+
+/// `Logger` of a forked scope: the root `Logger` with fields of the scope. It is created once per
+/// scope, see `ServiceProvider::with_scoped_logger`.
+#[derive(Debug)]
+pub struct ScopedLogger(Logger);
+
+impl Deref for ScopedLogger {
+    type Target = Logger;
+
+    fn deref(&self) -> &Logger {
+        &self.0
+    }
+}
+
+impl<'a> Dependency<HCons<&'a Logger, HCons<&'a LogFields, HNil>>> for ScopedLogger {
+    fn init(deps: HCons<&'a Logger, HCons<&'a LogFields, HNil>>) -> Self {
+        let HCons {
+            head: root,
+            tail: HCons { head: fields, .. },
+        } = deps;
+        let mut logger = root.clone();
+        logger.fields.extend(fields.0.iter().cloned());
+        ScopedLogger(logger)
+    }
+}
+
+/// Container of fields registered by `ServiceProvider::with_scoped_logger`.
+pub type LogFieldsContainer = InstanceContainer<LogFields>;
+
+/// Container of the logger registered by `ServiceProvider::with_scoped_logger`.
+pub type ScopedLoggerContainer = SingletonContainer<ScopedLogger>;
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add the root `Logger` with the target as an instance.
+    pub fn with_logger(
+        self,
+        target: &'static str,
+    ) -> ServiceProvider<Parent, HCons<InstanceContainer<Logger>, Conts>> {
+        self.add_instance(Logger::new(target))
+    }
+
+    /// Add `ScopedLogger` with fields of the scope. Call it on a forked scope, whose parent has the
+    /// root `Logger`. See the module documentation for an example.
+    pub fn with_scoped_logger(
+        self,
+        fields: &[(&'static str, &dyn Display)],
+    ) -> ServiceProvider<Parent, HCons<ScopedLoggerContainer, HCons<LogFieldsContainer, Conts>>>
+    {
+        let fields = fields
+            .iter()
+            .map(|(key, value)| (*key, value.to_string()))
+            .collect();
+        self.add_instance(LogFields(fields))
+            .add_singleton::<ScopedLogger>()
+    }
+}
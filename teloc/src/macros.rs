@@ -0,0 +1,150 @@
+/// Register many services at once. Expands to chained `add_auto` calls, so the lifetime of each
+/// service is taken from its `AutoLifetime` implementation (see `ServiceProvider::add_auto`). The
+/// lifetime can be written explicitly by prefixing the type with `transient` or `singleton`.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// #[derive(Dependency)]
+/// #[dependency(lifetime = "singleton")]
+/// struct Settings;
+///
+/// #[derive(Dependency)]
+/// #[dependency(lifetime = "transient")]
+/// struct Repo<'a> {
+///     settings: &'a Settings,
+/// }
+///
+/// #[derive(Dependency)]
+/// struct Controller<'a> {
+///     repo: Repo<'a>,
+/// }
+///
+/// let sp = add_types!(ServiceProvider::new(); Settings, Repo, transient Controller);
+/// let _: Controller = sp.resolve();
+/// ```
+#[macro_export]
+macro_rules! add_types {
+    ($sp:expr; $(,)?) => {
+        $sp
+    };
+    ($sp:expr; transient $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::add_types!($sp.add_transient::<$ty>(); $($($rest)*)?)
+    };
+    ($sp:expr; singleton $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::add_types!($sp.add_singleton::<$ty>(); $($($rest)*)?)
+    };
+    ($sp:expr; $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::add_types!($sp.add_auto::<$ty>(); $($($rest)*)?)
+    };
+}
+
+/// Assert at compile time that every listed type can be resolved from a provider of type `SP`.
+/// Expands to a `const` item with `check_provider` calls, so it can be placed at the module level,
+/// next to the types.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// struct Config;
+/// #[derive(Dependency)]
+/// struct Service<'a> {
+///     config: &'a Config,
+/// }
+///
+/// type AppSp = ServiceProvider<
+///     EmptyServiceProvider,
+///     HCons<TransientContainer<Service<'static>>, HCons<InstanceContainer<Config>, HNil>>,
+/// >;
+///
+/// check_provider!(AppSp: Service, &Config);
+/// ```
+#[macro_export]
+macro_rules! check_provider {
+    ($sp:ty: $($ty:ty),+ $(,)?) => {
+        const _: () = {
+            $($crate::check_provider::<$ty, $sp, _>();)+
+        };
+    };
+}
+
+/// Build a provider for tests. Instead of repeating the production wiring, list only what the test
+/// needs, every section is optional:
+/// - `services` are registered as by `add_types!`;
+/// - `defaults` are registered as instances created by `Default::default()`, usually configs;
+/// - `overrides` bind `Box<dyn Trait>` to a test double, that is created by `Default::default()`
+///   on every resolve.
+///
+/// Do not list the same type in two sections, or its resolution becomes ambiguous.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// trait Mailer {
+///     fn send(&self, to: &str) -> bool;
+/// }
+///
+/// #[derive(Default)]
+/// struct FakeMailer;
+/// impl Mailer for FakeMailer {
+///     fn send(&self, _: &str) -> bool { true }
+/// }
+///
+/// #[derive(Default)]
+/// struct Settings { admin: String }
+///
+/// struct Notifier<'a> {
+///     settings: &'a Settings,
+///     mailer: Box<dyn Mailer>,
+/// }
+/// #[inject]
+/// impl<'a> Notifier<'a> {
+///     fn new(settings: &'a Settings, mailer: Box<dyn Mailer>) -> Self {
+///         Notifier { settings, mailer }
+///     }
+/// }
+///
+/// let sp = test_provider! {
+///     services: { transient Notifier },
+///     defaults: { Settings },
+///     overrides: { Mailer => FakeMailer },
+/// };
+/// let notifier: Notifier = sp.resolve();
+/// assert!(notifier.mailer.send(&notifier.settings.admin));
+/// ```
+#[macro_export]
+macro_rules! test_provider {
+    (
+        $(services: { $($service:tt)* } $(,)?)?
+        $(defaults: { $($default:ty),* $(,)? } $(,)?)?
+        $(overrides: { $($bound:path => $fake:ty),* $(,)? } $(,)?)?
+    ) => {{
+        let sp = $crate::ServiceProvider::new();
+        $(let sp = sp $(.add_instance(<$default as ::core::default::Default>::default()))*;)?
+        $(let sp = sp $(.add_transient_factory(
+            |(): ()| -> $crate::reexport::Box<dyn $bound> {
+                $crate::reexport::Box::new(<$fake as ::core::default::Default>::default())
+            },
+        ))*;)?
+        $(let sp = $crate::add_types!(sp; $($service)*);)?
+        sp
+    }};
+}
+
+/// Export the function that registers services of a plugin, so it can be loaded by
+/// `DynResolver::load_plugin`. The function must have `PluginRegister` signature:
+/// `fn(&mut DynResolver)`. Enabled by the `plugins` feature.
+#[cfg(feature = "plugins")]
+#[macro_export]
+macro_rules! export_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub fn teloc_plugin_register(resolver: &mut $crate::dynamic::DynResolver) {
+            let register: $crate::dynamic::PluginRegister = $register;
+            register(resolver)
+        }
+    };
+}
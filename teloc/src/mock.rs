@@ -0,0 +1,115 @@
+//! Registration of test doubles generated by `mockall` as trait object bindings. Enabled by the
+//! `mockall` feature.
+//!
+//! Connect a trait with its mock once by `mockable!`, then bind `Box<dyn Trait>` to the mock by
+//! `ServiceProvider::mock`. The mock type only needs `Default`, which `#[automock]` and `mock!`
+//! generate, so hand-written mocks work too.
+
+use crate::container::{BoxedFactory, TransientFactoryContainer};
+use crate::ServiceProvider;
+use alloc::boxed::Box;
+use frunk::hlist::HList;
+use frunk::HCons;
+
+/// Trait object that has a mock, usually implemented by `mockable!`.
+pub trait Mockable {
+    type Mock: Default;
+
+    fn boxed(mock: Self::Mock) -> Box<Self>;
+}
+
+/// Implement `Mockable` for trait objects of the traits with their mocks.
+///
+/// Usage:
+/// ```
+/// use teloc::mockable;
+///
+/// trait Mailer {
+///     fn send(&self, to: &str) -> bool;
+/// }
+///
+/// // Usually generated by `#[mockall::automock]`.
+/// #[derive(Default)]
+/// struct MockMailer;
+/// impl Mailer for MockMailer {
+///     fn send(&self, _: &str) -> bool { true }
+/// }
+///
+/// mockable!(Mailer => MockMailer);
+/// ```
+#[macro_export]
+macro_rules! mockable {
+    ($($bound:path => $mock:ty),* $(,)?) => {
+        $(
+        impl $crate::mock::Mockable for dyn $bound {
+            type Mock = $mock;
+
+            fn boxed(mock: $mock) -> $crate::reexport::Box<Self> {
+                $crate::reexport::Box::new(mock)
+            }
+        }
+        )*
+    };
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Bind `Box<Dyn>` to the mock of `Dyn` with the `Transient` lifetime. Every resolve creates a
+    /// new mock and sets expectations on it by `configure`, so expectations are checked for each
+    /// resolved service separately.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::cell::Cell;
+    /// use teloc::*;
+    ///
+    /// trait Mailer {
+    ///     fn send(&self, to: &str) -> bool;
+    /// }
+    ///
+    /// // With `mockall` it is generated by `#[automock]` on the trait.
+    /// #[derive(Default)]
+    /// struct MockMailer {
+    ///     returning: Option<fn(&str) -> bool>,
+    ///     calls: Cell<u32>,
+    /// }
+    /// impl MockMailer {
+    ///     fn expect_send(&mut self) -> &mut Self { self }
+    ///     fn returning(&mut self, f: fn(&str) -> bool) -> &mut Self {
+    ///         self.returning = Some(f);
+    ///         self
+    ///     }
+    /// }
+    /// impl Mailer for MockMailer {
+    ///     fn send(&self, to: &str) -> bool {
+    ///         self.calls.set(self.calls.get() + 1);
+    ///         (self.returning.expect("unexpected call"))(to)
+    ///     }
+    /// }
+    ///
+    /// mockable!(Mailer => MockMailer);
+    ///
+    /// let sp = ServiceProvider::new().mock::<dyn Mailer>(|m| {
+    ///     m.expect_send().returning(|to| to.ends_with("@example.com"));
+    /// });
+    ///
+    /// let mailer: Box<dyn Mailer> = sp.resolve();
+    /// assert!(mailer.send("ann@example.com"));
+    /// assert!(!mailer.send("bob@test"));
+    /// ```
+    pub fn mock<Dyn>(
+        self,
+        configure: impl Fn(&mut Dyn::Mock) + 'static,
+    ) -> ServiceProvider<Parent, HCons<TransientFactoryContainer<(), Box<Dyn>>, Conts>>
+    where
+        Dyn: Mockable + ?Sized + 'static,
+    {
+        let factory = move |()| {
+            let mut mock = Dyn::Mock::default();
+            configure(&mut mock);
+            Dyn::boxed(mock)
+        };
+        self._add::<TransientFactoryContainer<(), Box<Dyn>>>(
+            Box::new(factory) as BoxedFactory<(), Box<Dyn>>
+        )
+    }
+}
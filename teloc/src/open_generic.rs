@@ -0,0 +1,208 @@
+//! Support for open-generic services, see `open_generic!` and `ServiceProvider::add_open_generic`.
+
+use crate::container::Container;
+use crate::get_dependencies::GetDependencies;
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Dependency, Resolver, ServiceProvider};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use frunk::hlist::HList;
+use frunk::HCons;
+
+/// Type that is a member of a family of types of one generic, usually implemented by
+/// `open_generic!`. `Family` is a marker type that is registered in the provider instead of every
+/// member.
+pub trait OpenGeneric {
+    type Family;
+}
+
+/// Declare a marker type of a family of types of one generic, so all of them can be registered in
+/// the provider at once by `ServiceProvider::add_open_generic`. `_` marks the type parameter that
+/// is open, the generic may have a lifetime parameter before it. Bounds after `where _:` restrict
+/// the family to types with parameters that satisfy them, for example to entities of a
+/// repository. Members of a constrained family can be checked at once by `check_open_generic!`.
+///
+/// Usage:
+/// ```
+/// use teloc::open_generic;
+/// use std::marker::PhantomData;
+///
+/// trait Entity {
+///     const TABLE: &'static str;
+/// }
+///
+/// struct Repository<'a, T> {
+///     schema: &'a str,
+///     entity: PhantomData<T>,
+/// }
+///
+/// open_generic!(pub Repositories => Repository<'a, _> where _: Entity + Send);
+/// ```
+#[macro_export]
+macro_rules! open_generic {
+    ($vis:vis $family:ident => $($ty:ident)::+ < $($lt:lifetime,)? _ > $(where _: $($bound:tt)+)?) => {
+        $vis struct $family;
+
+        impl<$($lt,)? T $(: $($bound)+)?> $crate::OpenGeneric for $($ty)::+<$($lt,)? T> {
+            type Family = $family;
+        }
+    };
+}
+
+/// Assert at compile time that every member of a family, whose parameter satisfies the bounds,
+/// can be resolved from a provider of type `SP`. Dependencies of the members are checked once,
+/// against the bounds, instead of at every place where a member is resolved, so a missing
+/// dependency is reported here. The family must be added to `SP` by
+/// `ServiceProvider::add_open_generic`.
+///
+/// Usage:
+/// ```
+/// use std::marker::PhantomData;
+/// use teloc::*;
+///
+/// trait Entity {}
+///
+/// struct Db;
+///
+/// struct Repository<'a, T> {
+///     db: &'a Db,
+///     entity: PhantomData<T>,
+/// }
+/// #[inject]
+/// impl<'a, T: Entity> Repository<'a, T> {
+///     fn new(db: &'a Db) -> Self { Repository { db, entity: PhantomData } }
+/// }
+///
+/// open_generic!(Repositories => Repository<'a, _> where _: Entity);
+///
+/// type AppSp = ServiceProvider<
+///     EmptyServiceProvider,
+///     HCons<OpenGenericContainer<Repositories>, HCons<InstanceContainer<Db>, HNil>>,
+/// >;
+///
+/// check_open_generic!(AppSp: Repository<'a, _> where _: Entity);
+/// ```
+///
+/// Without `Db` in the provider the check fails, even if no member of the family is resolved:
+/// ```compile_fail
+/// # use std::marker::PhantomData;
+/// # use teloc::*;
+/// # trait Entity {}
+/// # struct Db;
+/// # struct Repository<'a, T> {
+/// #     db: &'a Db,
+/// #     entity: PhantomData<T>,
+/// # }
+/// # #[inject]
+/// # impl<'a, T: Entity> Repository<'a, T> {
+/// #     fn new(db: &'a Db) -> Self { Repository { db, entity: PhantomData } }
+/// # }
+/// # open_generic!(Repositories => Repository<'a, _> where _: Entity);
+/// type AppSp = ServiceProvider<EmptyServiceProvider, HCons<OpenGenericContainer<Repositories>, HNil>>;
+///
+/// check_open_generic!(AppSp: Repository<'a, _> where _: Entity);
+/// ```
+#[macro_export]
+macro_rules! check_open_generic {
+    ($sp:ty: $($ty:ident)::+ < $($lt:lifetime,)? _ > $(where _: $($bound:tt)+)?) => {
+        const _: () = {
+            #[allow(dead_code)]
+            fn check<$($lt,)? T $(: $($bound)+)?>() {
+                $crate::check_provider::<$($ty)::+<$($lt,)? T>, $sp, _>();
+            }
+        };
+    };
+}
+
+/// Container of a family of types with the `Transient` lifetime, see
+/// `ServiceProvider::add_open_generic`.
+#[derive(Debug)]
+pub struct OpenGenericContainer<Family>(PhantomData<fn() -> Family>);
+
+#[cfg(feature = "shared-singletons")]
+impl<Family> Clone for OpenGenericContainer<Family> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Family> Container for OpenGenericContainer<Family> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'this, 'cont, T, Family, SP, Index, Deps, Infer>
+    Resolver<'this, T, (&'cont OpenGenericContainer<Family>, Index, Deps, Infer)> for SP
+where
+    SP: SelectContainer<'this, &'cont OpenGenericContainer<Family>, Index>
+        + GetDependencies<'this, Deps, Infer>,
+    T: OpenGeneric<Family = Family> + Dependency<Deps>,
+    Family: 'cont,
+{
+    fn resolve(&'this self) -> T {
+        T::init(self.get_deps())
+    }
+}
+
+impl<'a, SP, Family> WarmUp<'a, SP, ()> for OpenGenericContainer<Family> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, Family> CollectImplementors<'a, SP, Dyn, ()>
+    for OpenGenericContainer<Family>
+{
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, Family> CollectOrder<'a, SP, ()> for OpenGenericContainer<Family> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add all members of the family declared by `open_generic!` with the `Transient` lifetime.
+    /// Every member can be resolved, as long as its own dependencies are in the provider, so
+    /// `Repository<User>` and `Repository<Order>` do not need a registration each.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::marker::PhantomData;
+    /// use teloc::*;
+    ///
+    /// struct Db {
+    ///     name: &'static str,
+    /// }
+    ///
+    /// struct Repository<'a, T> {
+    ///     db: &'a Db,
+    ///     entity: PhantomData<T>,
+    /// }
+    /// #[inject]
+    /// impl<'a, T> Repository<'a, T> {
+    ///     fn new(db: &'a Db) -> Self { Repository { db, entity: PhantomData } }
+    /// }
+    ///
+    /// open_generic!(Repositories => Repository<'a, _>);
+    ///
+    /// struct User;
+    /// struct Order;
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(Db { name: "shop" })
+    ///     .add_open_generic::<Repositories>();
+    ///
+    /// let users: Repository<User> = sp.resolve();
+    /// let orders: Repository<Order> = sp.resolve();
+    /// assert_eq!(users.db.name, orders.db.name);
+    /// ```
+    pub fn add_open_generic<Family>(
+        self,
+    ) -> ServiceProvider<Parent, HCons<OpenGenericContainer<Family>, Conts>> {
+        self._add::<OpenGenericContainer<Family>>(())
+    }
+}
@@ -0,0 +1,127 @@
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use core::future::Future;
+use core::ops::{Deref, DerefMut};
+use frunk::hlist::HList;
+use frunk::HCons;
+
+/// Async pool of resources (connections, channels, clients). Implement it for your pool if you
+/// wish to use it with `ServiceProvider::add_pooled_resource`.
+///
+/// Implemented for `deadpool::managed::Pool` with the `deadpool-support` feature and for
+/// `bb8::Pool` with the `bb8-support` feature.
+pub trait AsyncPool {
+    /// Resource checked out from the pool. It must return itself back to the pool when dropped.
+    type Resource;
+    type Error;
+
+    fn acquire(&self) -> impl Future<Output = Result<Self::Resource, Self::Error>>;
+}
+
+/// Resource checked out from an `AsyncPool` for a scope. It is returned to the pool when the
+/// scope is dropped.
+///
+/// Services of the scope can depend on `&PooledResource<P::Resource>`.
+#[derive(Debug)]
+pub struct PooledResource<R>(R);
+
+impl<R> PooledResource<R> {
+    /// Take the resource out of the wrapper.
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R> Deref for PooledResource<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.0
+    }
+}
+
+impl<R> DerefMut for PooledResource<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        &mut self.0
+    }
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Check out a resource from the pool and add it as a `PooledResource` instance. Register the
+    /// pool itself as an instance of the root provider, and call this method on a forked scope,
+    /// so each scope will hold its own resource.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::future::{ready, Future};
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use teloc::*;
+    ///
+    /// #[derive(Default)]
+    /// struct CounterPool(AtomicU32);
+    /// impl AsyncPool for CounterPool {
+    ///     type Resource = u32;
+    ///     type Error = ();
+    ///     fn acquire(&self) -> impl Future<Output = Result<u32, ()>> {
+    ///         ready(Ok(self.0.fetch_add(1, Ordering::SeqCst)))
+    ///     }
+    /// }
+    ///
+    /// struct Session<'a> { conn: &'a PooledResource<u32> }
+    /// #[inject]
+    /// impl<'a> Session<'a> {
+    ///     fn new(conn: &'a PooledResource<u32>) -> Self { Self { conn } }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(CounterPool::default())
+    ///     .add_transient::<Session>();
+    ///
+    /// futures::executor::block_on(async {
+    ///     let pool: &CounterPool = sp.resolve();
+    ///     let scope = sp.fork().add_pooled_resource(pool).await.unwrap();
+    ///     let session: Session = scope.resolve();
+    ///     assert_eq!(**session.conn, 0);
+    /// });
+    /// ```
+    pub async fn add_pooled_resource<P>(
+        self,
+        pool: &P,
+    ) -> Result<
+        ServiceProvider<Parent, HCons<InstanceContainer<PooledResource<P::Resource>>, Conts>>,
+        P::Error,
+    >
+    where
+        P: AsyncPool,
+    {
+        let resource = pool.acquire().await?;
+        Ok(self.add_instance(PooledResource(resource)))
+    }
+}
+
+#[cfg(feature = "deadpool-support")]
+impl<M, W> AsyncPool for deadpool::managed::Pool<M, W>
+where
+    M: deadpool::managed::Manager,
+    W: From<deadpool::managed::Object<M>>,
+{
+    type Resource = W;
+    type Error = deadpool::managed::PoolError<M::Error>;
+
+    fn acquire(&self) -> impl Future<Output = Result<W, Self::Error>> {
+        self.get()
+    }
+}
+
+#[cfg(feature = "bb8-support")]
+impl<M> AsyncPool for bb8::Pool<M>
+where
+    M: bb8::ManageConnection,
+{
+    type Resource = bb8::PooledConnection<'static, M>;
+    type Error = bb8::RunError<M::Error>;
+
+    fn acquire(&self) -> impl Future<Output = Result<Self::Resource, Self::Error>> {
+        self.get_owned()
+    }
+}
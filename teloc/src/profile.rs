@@ -0,0 +1,233 @@
+//! Environment profiles, see `ServiceProvider::with_profile`.
+
+use crate::container::{
+    Container, ConvertContainer, InstanceContainer, SingletonContainer, TransientContainer,
+};
+use crate::{EmptyServiceProvider, ServiceProvider};
+use core::marker::PhantomData;
+use frunk::hlist::HList;
+use frunk::{HCons, HNil};
+
+/// Development profile.
+#[derive(Debug)]
+pub struct Dev;
+
+/// Test profile.
+#[derive(Debug)]
+pub struct Test;
+
+/// Production profile.
+#[derive(Debug)]
+pub struct Prod;
+
+/// Parent of a root `ServiceProvider` created by `ServiceProvider::with_profile`. Like
+/// `EmptyServiceProvider` it contains no services, and stores the active profile `P` in its type.
+#[derive(Debug)]
+pub struct Profile<P>(PhantomData<fn() -> P>);
+
+#[cfg(feature = "shared-singletons")]
+impl<P> Clone for Profile<P> {
+    fn clone(&self) -> Self {
+        Profile(PhantomData)
+    }
+}
+
+/// Result of `ProfileMatches` when the profile of a registration is the active one.
+pub struct Matched;
+
+/// Result of `ProfileMatches` when the profile of a registration is not the active one.
+pub struct NotMatched;
+
+/// The trait, used for checking that the profile of a registration is the active profile `P`. Do
+/// not use it by yourself.
+pub trait ProfileMatches<P> {
+    type Result;
+}
+
+macro_rules! impl_profile_matches {
+    ($($profile:ty => [$($other:ty),*]),*) => {
+        $(
+        impl ProfileMatches<$profile> for $profile {
+            type Result = Matched;
+        }
+        $(
+        impl ProfileMatches<$other> for $profile {
+            type Result = NotMatched;
+        }
+        )*
+        )*
+    };
+}
+
+impl_profile_matches!(
+    Dev => [Test, Prod],
+    Test => [Dev, Prod],
+    Prod => [Dev, Test]
+);
+
+/// The trait, used for adding a container only when the profile matches. Do not use it by yourself.
+pub trait RegisterIf<Cont: Container, Conts> {
+    type Output;
+
+    fn register(conts: Conts, data: Cont::Data) -> Self::Output;
+}
+
+impl<Cont: Container, Conts: HList> RegisterIf<Cont, Conts> for Matched {
+    type Output = HCons<Cont, Conts>;
+
+    fn register(conts: Conts, data: Cont::Data) -> Self::Output {
+        conts.prepend(Cont::init(data))
+    }
+}
+
+impl<Cont: Container, Conts> RegisterIf<Cont, Conts> for NotMatched {
+    type Output = Conts;
+
+    fn register(conts: Conts, _: Cont::Data) -> Conts {
+        conts
+    }
+}
+
+// Clippy requires to create type aliases
+type ProfileAdd<Active, P, Cont, Conts> = ServiceProvider<
+    Profile<Active>,
+    <<P as ProfileMatches<Active>>::Result as RegisterIf<Cont, Conts>>::Output,
+>;
+
+impl ServiceProvider<EmptyServiceProvider, HNil> {
+    /// Create an empty `ServiceProvider` with the active profile `P` (`Dev`, `Test` or
+    /// `Prod`). Services registered by `add_*_for` methods for other profiles are skipped, so the
+    /// wiring for all environments can be written in one place. The profile is a part of the type
+    /// of the provider, so skipped services cannot be resolved.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::profile::{Prod, Test};
+    /// use teloc::*;
+    ///
+    /// trait UserRepo {
+    ///     fn kind(&self) -> &'static str;
+    /// }
+    ///
+    /// struct PgUserRepo;
+    /// impl UserRepo for PgUserRepo {
+    ///     fn kind(&self) -> &'static str { "postgres" }
+    /// }
+    /// #[inject]
+    /// impl PgUserRepo {
+    ///     fn new() -> Self { PgUserRepo }
+    /// }
+    /// impl From<Box<PgUserRepo>> for Box<dyn UserRepo> {
+    ///     fn from(repo: Box<PgUserRepo>) -> Self { repo }
+    /// }
+    ///
+    /// struct InMemoryUserRepo;
+    /// impl UserRepo for InMemoryUserRepo {
+    ///     fn kind(&self) -> &'static str { "memory" }
+    /// }
+    /// #[inject]
+    /// impl InMemoryUserRepo {
+    ///     fn new() -> Self { InMemoryUserRepo }
+    /// }
+    /// impl From<Box<InMemoryUserRepo>> for Box<dyn UserRepo> {
+    ///     fn from(repo: Box<InMemoryUserRepo>) -> Self { repo }
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Users {
+    ///     repo: Box<dyn UserRepo>,
+    /// }
+    ///
+    /// macro_rules! provider {
+    ///     ($profile:ty) => {
+    ///         ServiceProvider::with_profile::<$profile>()
+    ///             .add_transient_c_for::<Prod, Box<dyn UserRepo>, Box<PgUserRepo>>()
+    ///             .add_transient_c_for::<Test, Box<dyn UserRepo>, Box<InMemoryUserRepo>>()
+    ///             .add_transient::<Users>()
+    ///     };
+    /// }
+    ///
+    /// let users: Users = provider!(Prod).resolve();
+    /// assert_eq!(users.repo.kind(), "postgres");
+    /// let users: Users = provider!(Test).resolve();
+    /// assert_eq!(users.repo.kind(), "memory");
+    /// ```
+    pub fn with_profile<P>() -> ServiceProvider<Profile<P>, HNil> {
+        ServiceProvider {
+            parent: Profile(PhantomData),
+            containers: HNil,
+        }
+    }
+}
+
+impl<Active, Conts: HList> ServiceProvider<Profile<Active>, Conts> {
+    /// Same as `ServiceProvider::_add`, but adds the container only when `P` is the active
+    /// profile.
+    pub fn _add_for<P, Cont>(self, data: Cont::Data) -> ProfileAdd<Active, P, Cont, Conts>
+    where
+        Cont: Container,
+        P: ProfileMatches<Active>,
+        P::Result: RegisterIf<Cont, Conts>,
+    {
+        let ServiceProvider { parent, containers } = self;
+        ServiceProvider {
+            parent,
+            containers: <P::Result as RegisterIf<Cont, Conts>>::register(containers, data),
+        }
+    }
+
+    /// Same as `ServiceProvider::add_transient`, but only for the profile `P`.
+    pub fn add_transient_for<P, T>(self) -> ProfileAdd<Active, P, TransientContainer<T>, Conts>
+    where
+        P: ProfileMatches<Active>,
+        P::Result: RegisterIf<TransientContainer<T>, Conts>,
+    {
+        self._add_for::<P, TransientContainer<T>>(())
+    }
+
+    /// Same as `ServiceProvider::add_singleton`, but only for the profile `P`.
+    pub fn add_singleton_for<P, T>(self) -> ProfileAdd<Active, P, SingletonContainer<T>, Conts>
+    where
+        P: ProfileMatches<Active>,
+        P::Result: RegisterIf<SingletonContainer<T>, Conts>,
+    {
+        self._add_for::<P, SingletonContainer<T>>(())
+    }
+
+    /// Same as `ServiceProvider::add_instance`, but only for the profile `P`. The instance is
+    /// dropped for other profiles.
+    pub fn add_instance_for<P, T>(
+        self,
+        instance: T,
+    ) -> ProfileAdd<Active, P, InstanceContainer<T>, Conts>
+    where
+        P: ProfileMatches<Active>,
+        P::Result: RegisterIf<InstanceContainer<T>, Conts>,
+    {
+        self._add_for::<P, InstanceContainer<T>>(instance)
+    }
+
+    /// Same as `ServiceProvider::add_transient_c`, but only for the profile `P`.
+    pub fn add_transient_c_for<P, U, T>(
+        self,
+    ) -> ProfileAdd<Active, P, ConvertContainer<TransientContainer<T>, T, U>, Conts>
+    where
+        T: Into<U>,
+        P: ProfileMatches<Active>,
+        P::Result: RegisterIf<ConvertContainer<TransientContainer<T>, T, U>, Conts>,
+    {
+        self._add_for::<P, ConvertContainer<TransientContainer<T>, T, U>>(())
+    }
+
+    /// Same as `ServiceProvider::add_singleton_c`, but only for the profile `P`.
+    pub fn add_singleton_c_for<P, U, T>(
+        self,
+    ) -> ProfileAdd<Active, P, ConvertContainer<SingletonContainer<T>, T, U>, Conts>
+    where
+        T: Into<U>,
+        P: ProfileMatches<Active>,
+        P::Result: RegisterIf<ConvertContainer<SingletonContainer<T>, T, U>, Conts>,
+    {
+        self._add_for::<P, ConvertContainer<SingletonContainer<T>, T, U>>(())
+    }
+}
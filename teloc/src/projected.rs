@@ -0,0 +1,88 @@
+use crate::dependency::DependencyClone;
+use crate::get_dependencies::GetDependencies;
+use crate::Resolver;
+use frunk::hlist::Selector;
+use frunk::Generic;
+
+/// Fixed set of services, resolved once from a `ServiceProvider` by `ServiceProvider::project`.
+/// `T` is a tuple of the services, e.g. `(Config, &Repository, Arc<Cache>)`.
+///
+/// `Projected` implements `Resolver` only for the services from the tuple, so it can be handed to
+/// a subsystem instead of the whole provider. Each service `S` from the tuple can be resolved as
+/// `&S`, and as `S` if it implements `DependencyClone`. `Projected` is `Send` and `Sync` when all
+/// of the services are.
+///
+/// Usage:
+/// ```
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Config { name: &'static str }
+/// struct Cache;
+/// #[inject]
+/// impl Cache {
+///     fn new() -> Self { Cache }
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_instance(Config { name: "app" })
+///     .add_singleton::<Arc<Cache>>();
+///
+/// let projected: Projected<(&Config, Arc<Cache>)> = sp.project();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         let config: &Config = projected.resolve();
+///         let _cache: Arc<Cache> = projected.resolve();
+///         assert_eq!(config.name, "app");
+///     });
+/// });
+/// ```
+pub struct Projected<T: Generic> {
+    services: T::Repr,
+}
+
+impl<T: Generic> Projected<T> {
+    /// Resolve the services from the provider. Same as `ServiceProvider::project`, but the tuple
+    /// can be specified with turbofish: `Projected::<(A, &B)>::new(&sp)`.
+    pub fn new<'a, SP, Infer>(sp: &'a SP) -> Self
+    where
+        SP: GetDependencies<'a, T::Repr, Infer>,
+    {
+        Projected {
+            services: sp.get_deps(),
+        }
+    }
+
+    /// Returns the tuple of the services.
+    pub fn into_inner(self) -> T {
+        T::from(self.services)
+    }
+}
+
+/// Marker of `Resolver` implementation that clones a service from the `Projected`.
+pub struct ProjectedClone;
+
+/// Marker of `Resolver` implementation that borrows a service from the `Projected`.
+pub struct ProjectedRef;
+
+impl<'a, T, S, Index> Resolver<'a, S, (ProjectedClone, Index)> for Projected<T>
+where
+    T: Generic,
+    T::Repr: Selector<S, Index>,
+    S: DependencyClone,
+{
+    fn resolve(&'a self) -> S {
+        self.services.get().clone()
+    }
+}
+
+impl<'a, T, S, Index> Resolver<'a, &'a S, (ProjectedRef, Index)> for Projected<T>
+where
+    T: Generic,
+    T::Repr: Selector<S, Index>,
+{
+    fn resolve(&'a self) -> &'a S {
+        self.services.get()
+    }
+}
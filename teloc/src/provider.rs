@@ -0,0 +1,70 @@
+use crate::{Resolver, ServiceProvider};
+use alloc::boxed::Box;
+
+/// Factory of `T` that resolves a fresh `T` from the `ServiceProvider` on each call of
+/// `Provider::get`. Take it as a dependency when a service needs to create many instances of
+/// another service over its lifetime, for example a singleton creating transient sessions.
+///
+/// `Provider<T>` does not need to be registered: it can be resolved from a `ServiceProvider`
+/// whenever `T` can be resolved from it. `Provider` borrows the `ServiceProvider` it was resolved
+/// from, so a singleton can hold it only when the `ServiceProvider` is `'static` (for example,
+/// leaked with `Box::leak` at the start of the application).
+///
+/// Usage:
+/// ```
+/// use std::cell::Cell;
+/// use teloc::*;
+///
+/// struct UserSession { id: u32 }
+/// #[inject]
+/// impl UserSession {
+///     fn new(ids: &Cell<u32>) -> Self {
+///         ids.set(ids.get() + 1);
+///         UserSession { id: ids.get() }
+///     }
+/// }
+///
+/// struct SessionManager<'a> {
+///     sessions: Provider<'a, UserSession>,
+/// }
+/// #[inject]
+/// impl<'a> SessionManager<'a> {
+///     fn new(sessions: Provider<'a, UserSession>) -> Self { SessionManager { sessions } }
+/// }
+///
+/// let ids = Cell::new(0);
+/// let sp = ServiceProvider::new()
+///     .add_instance(&ids)
+///     .add_transient::<UserSession>()
+///     .add_transient::<SessionManager>();
+///
+/// let manager: SessionManager = sp.resolve();
+/// assert_eq!(manager.sessions.get().id, 1);
+/// assert_eq!(manager.sessions.get().id, 2);
+/// ```
+pub struct Provider<'a, T> {
+    resolve: Box<dyn Fn() -> T + 'a>,
+}
+
+impl<T> Provider<'_, T> {
+    /// Resolve a new `T`.
+    pub fn get(&self) -> T {
+        (self.resolve)()
+    }
+}
+
+/// Marker of `Resolver` implementation that creates a `Provider`.
+pub struct ResolveProvider;
+
+impl<'a, Parent, Conts, T, Infer> Resolver<'a, Provider<'a, T>, (ResolveProvider, Infer)>
+    for ServiceProvider<Parent, Conts>
+where
+    Self: Resolver<'a, T, Infer>,
+    T: 'a,
+{
+    fn resolve(&'a self) -> Provider<'a, T> {
+        Provider {
+            resolve: Box::new(move || Resolver::<T, Infer>::resolve(self)),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Support for `r2d2` crate.
+
+use crate::container::Container;
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Resolver, ServiceProvider};
+use frunk::hlist::HList;
+use frunk::HCons;
+use r2d2::{ManageConnection, Pool, PooledConnection};
+
+/// Container that holds a connection checked out from `r2d2::Pool`. The connection is returned
+/// to the pool when the container (and so the scope) is dropped.
+///
+/// Can be resolved as `&M::Connection`.
+pub struct ConnectionContainer<M: ManageConnection>(PooledConnection<M>);
+
+impl<M: ManageConnection> Container for ConnectionContainer<M> {
+    type Data = PooledConnection<M>;
+
+    fn init(conn: PooledConnection<M>) -> Self {
+        Self(conn)
+    }
+}
+
+impl<M: ManageConnection> ConnectionContainer<M> {
+    #[inline]
+    pub fn get(&self) -> &M::Connection {
+        &self.0
+    }
+}
+
+impl<'this, 'cont, M, SP, Index>
+    Resolver<'this, &'cont M::Connection, (&'cont ConnectionContainer<M>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont ConnectionContainer<M>, Index>,
+    M: ManageConnection,
+{
+    fn resolve(&'this self) -> &'cont M::Connection {
+        self.get().get()
+    }
+}
+
+impl<'a, SP, M: ManageConnection> WarmUp<'a, SP, ()> for ConnectionContainer<M> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, M: ManageConnection> CollectImplementors<'a, SP, Dyn, ()>
+    for ConnectionContainer<M>
+{
+    fn collect(&'a self, _: &'a SP, _: &mut alloc::vec::Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, M: ManageConnection> CollectOrder<'a, SP, ()> for ConnectionContainer<M> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Check out a connection from the pool and add it to the provider. Use it on a forked scope,
+    /// so each scope will get its own connection and return it back when the scope is dropped.
+    /// Services of the scope can depend on `&M::Connection`.
+    ///
+    /// Usage:
+    /// ```
+    /// use r2d2::{ManageConnection, Pool};
+    /// use teloc::*;
+    ///
+    /// struct Connection { id: u32 }
+    /// struct Manager;
+    /// impl ManageConnection for Manager {
+    ///     type Connection = Connection;
+    ///     type Error = std::io::Error;
+    ///     fn connect(&self) -> Result<Connection, Self::Error> { Ok(Connection { id: 1 }) }
+    ///     fn is_valid(&self, _: &mut Connection) -> Result<(), Self::Error> { Ok(()) }
+    ///     fn has_broken(&self, _: &mut Connection) -> bool { false }
+    /// }
+    ///
+    /// struct UserRepository<'a> { conn: &'a Connection }
+    /// #[inject]
+    /// impl<'a> UserRepository<'a> {
+    ///     fn new(conn: &'a Connection) -> Self { Self { conn } }
+    /// }
+    ///
+    /// let pool = Pool::builder().max_size(1).build(Manager).unwrap();
+    /// let sp = ServiceProvider::new().add_transient::<UserRepository>();
+    ///
+    /// let scope = sp.fork().add_pooled_connection(&pool).unwrap();
+    /// let repository: UserRepository = scope.resolve();
+    /// assert_eq!(repository.conn.id, 1);
+    /// ```
+    pub fn add_pooled_connection<M>(
+        self,
+        pool: &Pool<M>,
+    ) -> Result<ServiceProvider<Parent, HCons<ConnectionContainer<M>, Conts>>, r2d2::Error>
+    where
+        M: ManageConnection,
+    {
+        Ok(self._add::<ConnectionContainer<M>>(pool.get()?))
+    }
+}
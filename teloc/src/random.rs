@@ -0,0 +1,126 @@
+//! Injectable source of random numbers, that is deterministic in tests.
+//!
+//! Services take `&Rng` as a dependency. The application registers a generator seeded from the
+//! system by `ServiceProvider::with_rng`, tests register a generator with a fixed seed by
+//! `ServiceProvider::with_seeded_rng`, so every run gets the same numbers.
+//!
+//! `Rng` is a small non-cryptographic generator (SplitMix64). Do not use it for keys, tokens or
+//! anything else that must not be guessed.
+//!
+//! Usage:
+//! ```
+//! use teloc::random::Rng;
+//! use teloc::*;
+//!
+//! struct Dice<'a> {
+//!     rng: &'a Rng,
+//! }
+//! #[inject]
+//! impl<'a> Dice<'a> {
+//!     fn new(rng: &'a Rng) -> Self { Dice { rng } }
+//! }
+//! impl Dice<'_> {
+//!     fn roll(&self) -> u64 { self.rng.gen_range(1..7) }
+//! }
+//!
+//! let rolls = |sp: &ServiceProvider<_, _>| -> Vec<u64> {
+//!     let dice: Dice = sp.resolve();
+//!     (0..5).map(|_| dice.roll()).collect()
+//! };
+//!
+//! let first = ServiceProvider::new().with_seeded_rng(42).add_transient::<Dice>();
+//! let second = ServiceProvider::new().with_seeded_rng(42).add_transient::<Dice>();
+//! assert_eq!(rolls(&first), rolls(&second));
+//! ```
+
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use core::ops::Range;
+use core::sync::atomic::{AtomicU64, Ordering};
+use frunk::hlist::HList;
+use frunk::HCons;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::SystemTime;
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Generator of random numbers, that can be shared between threads. Numbers drawn by different
+/// threads do not repeat each other, but their order depends on scheduling, so draw from one
+/// thread when the sequence must be reproduced.
+#[derive(Debug)]
+pub struct Rng(AtomicU64);
+
+impl Rng {
+    /// Generator that returns the same sequence for the same seed.
+    pub fn seeded(seed: u64) -> Self {
+        Rng(AtomicU64::new(seed))
+    }
+
+    /// Generator seeded from the random state of the process and the system time.
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        if let Ok(elapsed) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            hasher.write_u128(elapsed.as_nanos());
+        }
+        Rng::seeded(hasher.finish())
+    }
+
+    pub fn next_u64(&self) -> u64 {
+        let mut z = self
+            .0
+            .fetch_add(GOLDEN_GAMMA, Ordering::Relaxed)
+            .wrapping_add(GOLDEN_GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u32(&self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a number in `[0, 1)`.
+    pub fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns `true` with the probability `p`.
+    pub fn gen_bool(&self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Returns a number from the range, every number is equally likely.
+    ///
+    /// # Panics
+    /// Panics if the range is empty.
+    pub fn gen_range(&self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "cannot sample an empty range");
+        let span = range.end - range.start;
+        // Lemire's method: values of the low half below `threshold` would make some numbers more
+        // likely than others, so they are drawn again.
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let m = u128::from(self.next_u64()) * u128::from(span);
+            if m as u64 >= threshold {
+                return range.start + (m >> 64) as u64;
+            }
+        }
+    }
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add `Rng` seeded by `Rng::from_entropy` as an instance.
+    pub fn with_rng(self) -> ServiceProvider<Parent, HCons<InstanceContainer<Rng>, Conts>> {
+        self.add_instance(Rng::from_entropy())
+    }
+
+    /// Add `Rng` with the fixed seed as an instance, so code that uses it is deterministic. See the
+    /// module documentation for an example.
+    pub fn with_seeded_rng(
+        self,
+        seed: u64,
+    ) -> ServiceProvider<Parent, HCons<InstanceContainer<Rng>, Conts>> {
+        self.add_instance(Rng::seeded(seed))
+    }
+}
@@ -1,3 +1,8 @@
+use crate::ServiceProvider;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
 /// This trait is used to resolve some object from service provider. Generic `T` used only to avoid
 /// absence of specialization and for working of type inference. You must implement it yourself
 /// only when you implement your own version of container.
@@ -24,7 +29,225 @@
 ///
 /// assert_eq!(foo.0, 5)
 /// ```
-// Container is a local-crate type to avoid orphan rules. It must be _concrete_, __unique__ type when impl.
-pub trait Resolver<'a, Cont, T, Infer> {
+///
+/// `Infer` is inferred by the compiler and is never written by hand: it encodes where the service
+/// was found and how its dependencies were resolved. When you need a bound on a provider, make it
+/// a generic parameter:
+///
+/// ```
+/// use teloc::*;
+///
+/// struct Config { port: u16 }
+///
+/// fn port<'a, SP: Resolver<'a, &'a Config, Infer>, Infer>(sp: &'a SP) -> u16 {
+///     sp.resolve().port
+/// }
+///
+/// let sp = ServiceProvider::new().add_instance(Config { port: 8080 });
+/// assert_eq!(port(&sp), 8080);
+/// ```
+///
+/// When `T` can be resolved by ownership from `ServiceProvider`, `Box<T>`, `Rc<T>` and `Arc<T>`
+/// can be resolved too, each time with a new `T` inside. Do not register both `T` and the wrapper
+/// in the same provider, or resolution of the wrapper becomes ambiguous:
+///
+/// ```
+/// use std::rc::Rc;
+/// use teloc::*;
+///
+/// struct Service;
+/// #[inject]
+/// impl Service {
+///     fn new() -> Self { Service }
+/// }
+///
+/// let sp = ServiceProvider::new().add_transient::<Service>();
+/// let _boxed: Box<Service> = sp.resolve();
+/// let _shared: Rc<Service> = sp.resolve();
+/// ```
+///
+/// Tuples of services (up to 12 elements) can be resolved from `ServiceProvider` in one call,
+/// every element is resolved as if it was resolved alone:
+///
+/// ```
+/// use teloc::*;
+///
+/// struct Config { port: u16 }
+///
+/// let sp = ServiceProvider::new()
+///     .add_instance(Config { port: 8080 })
+///     .add_instance("app");
+///
+/// let (config, name): (&Config, &str) = sp.resolve();
+/// assert_eq!((config.port, name), (8080, "app"));
+/// ```
+// First element of `Infer` in every implementation is a reference to the container that resolves
+// the service. Container is a local-crate type, so it must be _concrete_, __unique__ type when impl.
+pub trait Resolver<'a, T, Infer> {
     fn resolve(&'a self) -> T;
+
+    /// Same as `resolve`, but only compiles when the resolved value can be moved into a spawned
+    /// task (`tokio::spawn`, `std::thread::spawn`), i.e. it is owned, `Send` and `'static`.
+    /// Register such services as `Arc<T>` singletons or instances to get a shared handle instead
+    /// of a borrow from the provider.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use teloc::*;
+    ///
+    /// struct Repository;
+    /// #[inject]
+    /// impl Repository {
+    ///     fn new() -> Self { Repository }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new().add_singleton::<Arc<Repository>>();
+    ///
+    /// let repository: Arc<Repository> = sp.resolve_owned_for_task();
+    /// std::thread::spawn(move || {
+    ///     let _repository = repository;
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// ```
+    ///
+    /// Borrows from the provider are rejected:
+    ///
+    /// ```compile_fail
+    /// use std::sync::Arc;
+    /// use teloc::*;
+    ///
+    /// struct Repository;
+    /// #[inject]
+    /// impl Repository {
+    ///     fn new() -> Self { Repository }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new().add_singleton::<Repository>();
+    ///
+    /// let repository: &Repository = sp.resolve_owned_for_task();
+    /// std::thread::spawn(move || {
+    ///     let _repository = repository;
+    /// });
+    /// ```
+    fn resolve_owned_for_task(&'a self) -> T
+    where
+        T: TaskSafe,
+    {
+        self.resolve()
+    }
+}
+
+/// Marker for values that can be moved into a spawned task. It is implemented for every
+/// `Send + 'static` type, you never need to implement it by yourself.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be moved into a spawned task",
+    label = "resolved value must be `Send + 'static`",
+    note = "register the service as `Arc<_>` so it can be resolved as an owned shared handle"
+)]
+pub trait TaskSafe: Send + 'static {}
+
+impl<T: Send + 'static> TaskSafe for T {}
+
+/// Compiles only when `T` can be resolved from a provider of type `SP`, and does nothing. It is a
+/// `const fn`, so the check can be placed in a `const` item next to the types of a library, and
+/// wiring regressions are caught by `cargo check`. `Infer` is always `_`. See also the
+/// `check_provider!` macro.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// struct Config;
+/// struct Repo<'a> { config: &'a Config }
+/// #[inject]
+/// impl<'a> Repo<'a> {
+///     fn new(config: &'a Config) -> Self { Repo { config } }
+/// }
+///
+/// type AppSp = ServiceProvider<
+///     EmptyServiceProvider,
+///     HCons<TransientContainer<Repo<'static>>, HCons<InstanceContainer<Config>, HNil>>,
+/// >;
+///
+/// const _: () = check_provider::<Repo, AppSp, _>();
+/// const _: () = check_provider::<&Config, AppSp, _>();
+/// ```
+///
+/// ```compile_fail
+/// use teloc::*;
+///
+/// struct Config;
+/// type AppSp = ServiceProvider<EmptyServiceProvider, HNil>;
+///
+/// const _: () = check_provider::<&Config, AppSp, _>();
+/// ```
+pub const fn check_provider<'a, T, SP, Infer>()
+where
+    SP: Resolver<'a, T, Infer>,
+{
+}
+
+/// Marker of `Resolver` implementation that resolves a tuple of services from the `ServiceProvider`.
+pub struct ResolveTuple;
+
+/// Marker of `Resolver` implementation that wraps a service into `Box`, `Rc` or `Arc`.
+pub struct ResolveWrapped;
+
+macro_rules! impl_resolver_for_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+        impl<'a, Parent, Conts, T, Infer> Resolver<'a, $wrapper<T>, (ResolveWrapped, Infer)>
+            for ServiceProvider<Parent, Conts>
+        where
+            Self: Resolver<'a, T, Infer>,
+        {
+            fn resolve(&'a self) -> $wrapper<T> {
+                $wrapper::new(self.resolve())
+            }
+        }
+        )*
+    };
+}
+
+impl_resolver_for_wrapper!(Box, Rc, Arc);
+
+/// Marker of `Resolver` implementation generated by the `Resolvable` derive macro.
+pub struct ResolveStruct;
+
+macro_rules! impl_resolver_for_tuple {
+    ($($arg:ident, $infer:ident),*) => {
+        impl<'a, Parent, Conts, $($arg, $infer),*>
+            Resolver<'a, ($($arg,)*), (ResolveTuple, ($($infer,)*))>
+            for ServiceProvider<Parent, Conts>
+        where
+            Self: $(Resolver<'a, $arg, $infer> +)*,
+        {
+            fn resolve(&'a self) -> ($($arg,)*) {
+                ($(Resolver::<$arg, $infer>::resolve(self),)*)
+            }
+        }
+    };
 }
+
+impl_resolver_for_tuple!(A1, I1);
+impl_resolver_for_tuple!(A1, I1, A2, I2);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3, A4, I4);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8);
+impl_resolver_for_tuple!(A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8, A9, I9);
+impl_resolver_for_tuple!(
+    A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8, A9, I9, A10, I10
+);
+impl_resolver_for_tuple!(
+    A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8, A9, I9, A10, I10, A11, I11
+);
+impl_resolver_for_tuple!(
+    A1, I1, A2, I2, A3, I3, A4, I4, A5, I5, A6, I6, A7, I7, A8, I8, A9, I9, A10, I10, A11, I11,
+    A12, I12
+);
@@ -0,0 +1,183 @@
+//! Support for `rocket` crate.
+//!
+//! `TelocFairing` attaches the `ServiceProvider` to the managed state of the application, and
+//! `Inject<T>` request guard resolves `T` from a scope that is forked once per request.
+//!
+//! Usage:
+//! ```
+//! use rocket::{get, routes};
+//! use std::sync::Arc;
+//! use teloc::rocket_support::{Inject, TelocFairing};
+//! use teloc::*;
+//!
+//! struct Repository;
+//! #[inject]
+//! impl Repository {
+//!     fn new() -> Self { Repository }
+//! }
+//!
+//! struct Controller {
+//!     repository: Arc<Repository>,
+//!     path: Arc<String>,
+//! }
+//! #[inject]
+//! impl Controller {
+//!     fn new(repository: Arc<Repository>, path: Arc<String>) -> Self {
+//!         Controller { repository, path }
+//!     }
+//! }
+//!
+//! #[get("/")]
+//! fn index(controller: Inject<Controller>) -> String {
+//!     controller.path.to_string()
+//! }
+//!
+//! let sp = Arc::new(
+//!     ServiceProvider::new()
+//!         .add_singleton::<Arc<Repository>>()
+//!         .add_transient::<Controller>(),
+//! );
+//!
+//! let rocket = rocket::build().mount("/", routes![index]).attach(
+//!     TelocFairing::new(sp, |scope, req| {
+//!         scope.add_instance(Arc::new(req.uri().path().to_string()))
+//!     })
+//!     .inject::<Controller, _>(|scope| scope.resolve()),
+//! );
+//! ```
+
+use crate::injectors::{Injectors, Scope};
+use crate::ServiceProvider;
+use frunk::HNil;
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::{Build, Rocket};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+type CreateScope = Arc<dyn Fn(&Request<'_>) -> Scope + Send + Sync>;
+
+/// Fairing that attaches the `ServiceProvider` to the managed state, so handlers can get services
+/// using the `Inject<T>` request guard.
+///
+/// - Scope factory gets the forked scope and the request, and can add some local dependencies that
+///   will be unique in different requests.
+/// - Every type that is extracted using `Inject` must be registered using `TelocFairing::inject`,
+///   otherwise request will fail with `500 Internal Server Error`.
+pub struct TelocFairing<ScopeResult> {
+    create_scope: CreateScope,
+    injectors: Injectors,
+    phantom: PhantomData<fn() -> ScopeResult>,
+}
+
+impl<ScopeResult: Send + Sync + 'static> TelocFairing<ScopeResult> {
+    /// Creates `TelocFairing` with specified `ServiceProvider` and scope factory.
+    pub fn new<ParSP, DepsSP, ScopeFactory>(
+        sp: Arc<ServiceProvider<ParSP, DepsSP>>,
+        scope_factory: ScopeFactory,
+    ) -> Self
+    where
+        ParSP: Send + Sync + 'static,
+        DepsSP: Send + Sync + 'static,
+        ScopeFactory: Fn(
+                ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HNil>,
+                &Request<'_>,
+            ) -> ScopeResult
+            + Send
+            + Sync
+            + 'static,
+    {
+        TelocFairing {
+            create_scope: Arc::new(move |req| Arc::new(scope_factory(sp.fork_arc(), req))),
+            injectors: Injectors::default(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Allow to extract `T` from the request scope using `Inject<T>`. `resolve` is usually
+    /// `|scope| scope.resolve()`: `.inject::<Controller, _>(|scope| scope.resolve())`. `T` cannot
+    /// borrow services from the scope.
+    pub fn inject<T, F>(mut self, resolve: F) -> Self
+    where
+        F: Fn(&ScopeResult) -> T + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        self.injectors.register(resolve);
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl<ScopeResult: 'static> Fairing for TelocFairing<ScopeResult> {
+    fn info(&self) -> Info {
+        Info {
+            name: "teloc",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(TelocState {
+            create_scope: self.create_scope.clone(),
+            injectors: Arc::new(self.injectors.clone()),
+        }))
+    }
+}
+
+struct TelocState {
+    create_scope: CreateScope,
+    injectors: Arc<Injectors>,
+}
+
+struct RequestScope(Scope);
+
+/// Request guard that resolves `T` from the request scope created by `TelocFairing`. The value is
+/// accessed through `Deref` and `DerefMut`. `T` must be registered using `TelocFairing::inject`,
+/// otherwise the request fails with `500 Internal Server Error`.
+pub struct Inject<T>(T);
+
+impl<T> std::ops::Deref for Inject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Inject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, T: Send + 'static> FromRequest<'r> for Inject<T> {
+    type Error = String;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let state = match req.rocket().state::<TelocState>() {
+            Some(state) => state,
+            None => {
+                return Outcome::Error((
+                    Status::InternalServerError,
+                    "`TelocFairing` is not attached".to_string(),
+                ))
+            }
+        };
+        // Scope is created once per request and shared between all `Inject` guards.
+        let scope = &req
+            .local_cache(|| RequestScope((state.create_scope)(req)))
+            .0;
+        match state.injectors.resolve(&**scope) {
+            Some(value) => Outcome::Success(Inject(value)),
+            None => Outcome::Error((
+                Status::InternalServerError,
+                format!(
+                    "`{}` is not registered in the `TelocFairing`",
+                    std::any::type_name::<T>()
+                ),
+            )),
+        }
+    }
+}
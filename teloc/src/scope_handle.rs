@@ -0,0 +1,119 @@
+//! Type-erased access to the scope from services, see `ServiceProvider::add_scope_handle`.
+
+use crate::container::Container;
+use crate::get_dependencies::FactoryArgs;
+use crate::service_provider::SelectContainer;
+use crate::warm_up::WarmUp;
+use crate::Resolver;
+use alloc::boxed::Box;
+use core::any::{Any, TypeId};
+use core::marker::PhantomData;
+use frunk::{HCons, HNil};
+
+/// Handle to the scope from which it was resolved. It can resolve only the types listed in
+/// `ServiceProvider::add_scope_handle`, and they must be owned and `'static` (usually `Rc<_>` or
+/// `Arc<_>` services).
+///
+/// Prefer declaring dependencies in constructors: use the handle only for genuinely dynamic cases,
+/// when a service does not know ahead which services it needs (for example, dispatching to plugins
+/// by name).
+pub struct ScopeHandle<'a> {
+    scope: Box<dyn ErasedScope + 'a>,
+}
+
+impl<'a> ScopeHandle<'a> {
+    /// Resolve `T` from the scope, or return `None` if `T` is not listed in the handle.
+    pub fn resolve<T: 'static>(&self) -> Option<T> {
+        let value = self.scope.resolve_any(TypeId::of::<T>())?;
+        Some(
+            *value
+                .downcast::<T>()
+                .expect("value is resolved by its type id"),
+        )
+    }
+}
+
+trait ErasedScope {
+    fn resolve_any(&self, id: TypeId) -> Option<Box<dyn Any>>;
+}
+
+struct Erased<'a, SP, Types, Infer> {
+    sp: &'a SP,
+    phantom: PhantomData<fn() -> (Types, Infer)>,
+}
+
+impl<'a, SP, Types, Infer> ErasedScope for Erased<'a, SP, Types, Infer>
+where
+    Types: ResolveAny<'a, SP, Infer>,
+{
+    fn resolve_any(&self, id: TypeId) -> Option<Box<dyn Any>> {
+        Types::resolve_any(self.sp, id)
+    }
+}
+
+/// The trait, used for resolving one of the listed types by its `TypeId`. Do not use it by
+/// yourself.
+pub trait ResolveAny<'a, SP, Infer> {
+    fn resolve_any(sp: &'a SP, id: TypeId) -> Option<Box<dyn Any>>;
+}
+
+impl<'a, SP> ResolveAny<'a, SP, HNil> for HNil {
+    fn resolve_any(_: &'a SP, _: TypeId) -> Option<Box<dyn Any>> {
+        None
+    }
+}
+
+impl<'a, SP, H, T, InferH, InferT> ResolveAny<'a, SP, HCons<InferH, InferT>> for HCons<H, T>
+where
+    SP: Resolver<'a, H, InferH>,
+    H: 'static,
+    T: ResolveAny<'a, SP, InferT>,
+{
+    fn resolve_any(sp: &'a SP, id: TypeId) -> Option<Box<dyn Any>> {
+        if id == TypeId::of::<H>() {
+            Some(Box::new(sp.resolve()))
+        } else {
+            T::resolve_any(sp, id)
+        }
+    }
+}
+
+/// Container of `ScopeHandle` that can resolve `Types` (a tuple of services).
+pub struct ScopeHandleContainer<Types>(PhantomData<fn() -> Types>);
+
+#[cfg(feature = "shared-singletons")]
+impl<Types> Clone for ScopeHandleContainer<Types> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Types> Container for ScopeHandleContainer<Types> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'this, 'cont, Types, SP, Index, Infer>
+    Resolver<'this, ScopeHandle<'this>, (&'cont ScopeHandleContainer<Types>, Index, Infer)> for SP
+where
+    SP: SelectContainer<'this, &'cont ScopeHandleContainer<Types>, Index>,
+    Types: FactoryArgs + 'this,
+    Types::List: ResolveAny<'this, SP, Infer> + 'this,
+    Infer: 'this,
+{
+    fn resolve(&'this self) -> ScopeHandle<'this> {
+        ScopeHandle {
+            scope: Box::new(Erased::<SP, Types::List, Infer> {
+                sp: self,
+                phantom: PhantomData,
+            }),
+        }
+    }
+}
+
+impl<'a, SP, Types> WarmUp<'a, SP, ()> for ScopeHandleContainer<Types> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
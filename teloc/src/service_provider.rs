@@ -1,11 +1,29 @@
+use crate::async_factory::AsyncTransientFactoryContainer;
+use crate::cleanup::OnDropContainer;
+use crate::conditional::ConditionalSingletonContainer;
 use crate::container::{
-    Container, ConvertContainer, InstanceContainer, SingletonContainer, TransientContainer,
+    BoxedFactory, Container, ConvertContainer, InstanceContainer, InstancesContainer, MapContainer,
+    SingletonContainer, SingletonFactoryContainer, TransientContainer, TransientFactoryContainer,
 };
+#[cfg(feature = "std")]
+use crate::deferred::DeferredContainer;
+use crate::dependency::{AutoLifetime, InjectFields};
+use crate::forbid::ForbiddenContainer;
+use crate::get_dependencies::GetDependencies;
+use crate::graph::{GraphCache, GraphScopedContainer};
+use crate::implementors::{CollectImplementors, ImplementorContainer};
 use crate::index::{ParentIndex, SelfIndex};
-use frunk::hlist::{HList, Selector};
-use frunk::{HCons, HNil};
-use std::rc::Rc;
-use std::sync::Arc;
+use crate::later::CyclicSingletonContainer;
+use crate::projected::Projected;
+use crate::scope_handle::ScopeHandleContainer;
+use crate::startup_order::{CollectOrder, OrderContainer, StartupError, StartupOrder};
+use crate::warm_up::WarmUp;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::future::Future;
+use frunk::hlist::{HList, Plucker, Selector};
+use frunk::{Generic, HCons, HNil};
 
 /// `ServiceProvider` struct is used as an IoC-container in which you declare your dependencies.
 ///
@@ -20,6 +38,12 @@ use std::sync::Arc;
 ///
 /// [manual]: https://github.com/p0lunin/teloc/blob/master/HOW-TO-READ-ERRORS.md
 ///
+/// With the `shared-singletons` feature `ServiceProvider` implements `Clone` when all of its
+/// containers do. Clones share singletons: a singleton created through one clone is returned by
+/// all others, and instances are cloned. It is useful for frameworks that clone a handler per
+/// worker (warp filters, tower services). Factories registered by `add_*_factory` are boxed and
+/// cannot be cloned, use `add_*_factory_unboxed` with a cloneable closure instead.
+///
 /// Example of usage `ServiceProvider`:
 /// ```
 /// use std::rc::Rc;
@@ -49,12 +73,14 @@ use std::sync::Arc;
 /// assert_eq!(*controller.number_service.number, 10);
 /// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "shared-singletons", derive(Clone))]
 pub struct ServiceProvider<Parent, Conts> {
     pub(crate) parent: Parent,
     pub(crate) containers: Conts,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "shared-singletons", derive(Clone, Copy))]
 pub struct EmptyServiceProvider;
 
 impl ServiceProvider<EmptyServiceProvider, HNil> {
@@ -78,9 +104,197 @@ impl<Parent, Conts> ServiceProvider<Parent, Conts> {
         &self.containers
     }
 
+    /// Initialize the provider ahead of time: create all singletons and set values of all
+    /// `Deferred` handles. Only containers of this provider are warmed up, not of the parent.
+    /// Singletons constrained by `ServiceProvider::depends_on` are created first, in order that
+    /// satisfies the constraints.
+    ///
+    /// It does not compile if some singleton or deferred value cannot be resolved from this
+    /// provider.
+    ///
+    /// # Panics
+    /// Panics if constraints added by `ServiceProvider::depends_on` contradict each other, or with
+    /// all failures if validations added by `ServiceProvider::add_validation` fail. Use
+    /// `ServiceProvider::try_warm_up` to handle these errors.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::cell::Cell;
+    /// use teloc::*;
+    ///
+    /// struct Heavy;
+    /// #[inject]
+    /// impl Heavy {
+    ///     fn new(created: &Cell<bool>) -> Self {
+    ///         created.set(true);
+    ///         Heavy
+    ///     }
+    /// }
+    ///
+    /// let created = Cell::new(false);
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(&created)
+    ///     .add_singleton::<Heavy>();
+    ///
+    /// sp.warm_up();
+    /// assert!(created.get());
+    /// ```
+    pub fn warm_up<'a, Infer, InferOrder>(&'a self)
+    where
+        Conts: WarmUp<'a, Self, Infer> + CollectOrder<'a, Self, InferOrder>,
+    {
+        if let Err(e) = self.try_warm_up::<Infer, InferOrder>() {
+            panic!("{}", e);
+        }
+    }
+
+    /// Same as `ServiceProvider::warm_up`, but returns errors instead of panicking. Validations
+    /// added by `ServiceProvider::add_validation` run first, and if any of them fails, the error
+    /// lists failures of all of them, so the application can report them at startup. Only types
+    /// added by `ServiceProvider::add_validation` are validated, other services that implement
+    /// `Validate` are not. If constraints added by `ServiceProvider::depends_on` contradict each
+    /// other, the error contains the cycle. In both cases nothing is initialized.
+    pub fn try_warm_up<'a, Infer, InferOrder>(&'a self) -> Result<(), StartupError>
+    where
+        Conts: WarmUp<'a, Self, Infer> + CollectOrder<'a, Self, InferOrder>,
+    {
+        let mut order = StartupOrder::new();
+        self.containers.collect_order(&mut order);
+        order.run_validations(self)?;
+        order.run(self)?;
+        self.containers.warm_up(self);
+        Ok(())
+    }
+
+    /// Resolve a fixed tuple of services once into a lightweight `Projected`, that can be handed to
+    /// subsystems that need only these services. Tuple is specified with a type annotation:
+    /// `let p: Projected<(A, &B, Arc<C>)> = sp.project();`. For more information see `Projected`.
+    pub fn project<'a, T, Infer>(&'a self) -> Projected<T>
+    where
+        T: Generic,
+        Self: GetDependencies<'a, T::Repr, Infer>,
+    {
+        Projected::new(self)
+    }
+
+    /// Fill fields of an existing value, marked with `#[injected]` attribute, by services from the
+    /// provider. For more information see `InjectFields`.
+    pub fn inject<'a, T, Infer>(&'a self, target: &mut T)
+    where
+        T: InjectFields<'a, Self, Infer>,
+    {
+        target.inject_fields(self)
+    }
+
+    /// Iterate all services marked by `ServiceProvider::add_implementor` as implementors of `Dyn`,
+    /// in order of registration. Services marked by `ServiceProvider::add_implementor_with_priority`
+    /// are sorted by priority first, lower ones come first, and `add_implementor` uses priority `0`.
+    /// Only containers of this provider are iterated, not of the parent.
+    /// It lets cross-cutting passes (run all migrations, flush all caches) be written once.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::cell::Cell;
+    /// use teloc::*;
+    ///
+    /// trait Startup {
+    ///     fn start(&self);
+    /// }
+    ///
+    /// struct Migrations {
+    ///     applied: Cell<bool>,
+    /// }
+    /// #[inject]
+    /// impl Migrations {
+    ///     fn new() -> Self { Migrations { applied: Cell::new(false) } }
+    /// }
+    /// impl Startup for Migrations {
+    ///     fn start(&self) { self.applied.set(true) }
+    /// }
+    ///
+    /// struct Poller {
+    ///     running: Cell<bool>,
+    /// }
+    /// #[inject]
+    /// impl Poller {
+    ///     fn new() -> Self { Poller { running: Cell::new(false) } }
+    /// }
+    /// impl Startup for Poller {
+    ///     fn start(&self) { self.running.set(true) }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_singleton::<Migrations>()
+    ///     .add_implementor::<dyn Startup, Migrations>(|m| m)
+    ///     .add_singleton::<Poller>()
+    ///     .add_implementor::<dyn Startup, Poller>(|p| p);
+    ///
+    /// sp.iter_implementors::<dyn Startup, _>().for_each(|s| s.start());
+    ///
+    /// let migrations: &Migrations = sp.resolve();
+    /// let poller: &Poller = sp.resolve();
+    /// assert!(migrations.applied.get() && poller.running.get());
+    /// ```
+    pub fn iter_implementors<'a, Dyn, Infer>(
+        &'a self,
+    ) -> impl DoubleEndedIterator<Item = &'a Dyn> + ExactSizeIterator
+    where
+        Dyn: ?Sized + 'a,
+        Conts: CollectImplementors<'a, Self, Dyn, Infer>,
+    {
+        let mut implementors = alloc::vec::Vec::new();
+        self.containers.collect(self, &mut implementors);
+        // The sort is stable, so implementors with the same priority stay in order of registration.
+        implementors.sort_by_key(|(priority, _)| *priority);
+        implementors
+            .into_iter()
+            .map(|(_, implementor)| implementor)
+    }
+
+    /// Append instances to the collection added by `ServiceProvider::add_instances`. They come
+    /// after the instances that are already in the collection.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct Route {
+    ///     path: &'static str,
+    /// }
+    ///
+    /// // A module of the application adds its routes to any provider with routes.
+    /// fn admin_routes<P, C, I>(sp: ServiceProvider<P, C>) -> ServiceProvider<P, C>
+    /// where
+    ///     C: Selector<InstancesContainer<Route>, I>,
+    /// {
+    ///     sp.extend_instances(vec![Route { path: "/admin" }])
+    /// }
+    ///
+    /// let sp = ServiceProvider::new().add_instances(vec![Route { path: "/" }]);
+    /// let sp = admin_routes(sp);
+    ///
+    /// let routes: &[Route] = sp.resolve();
+    /// let paths: Vec<_> = routes.iter().map(|r| r.path).collect();
+    /// assert_eq!(paths, ["/", "/admin"]);
+    /// ```
+    pub fn extend_instances<T, Index>(mut self, instances: impl IntoIterator<Item = T>) -> Self
+    where
+        Conts: Selector<InstancesContainer<T>, Index>,
+    {
+        self.containers.get_mut().extend(instances);
+        self
+    }
+
+    /// Fork `ServiceProvider` for resolution of one graph of services: services added by
+    /// `ServiceProvider::add_graph_scoped` are created once in the fork and shared by all services
+    /// resolved from it. Call it for every resolution: `let root: Root = sp.graph().resolve();`.
+    pub fn graph(&self) -> ServiceProvider<&Self, HCons<GraphCache, HNil>> {
+        self.fork()._add::<GraphCache>(())
+    }
+
     /// Forking `ServiceProvider` creates a new `ServiceProvider` with reference to the parent.
     /// `resolve` method on forked `ServiceProvider` will find dependencies form self and parent.
-    pub fn fork(&self) -> ServiceProvider<&Self, HNil> {
+    pub fn fork(&self) -> Fork<'_, Self> {
         ServiceProvider {
             parent: self,
             containers: HNil,
@@ -89,7 +303,7 @@ impl<Parent, Conts> ServiceProvider<Parent, Conts> {
 
     /// Forking `ServiceProvider` creates a new `ServiceProvider` with reference to the parent.
     /// `resolve` method on forked `ServiceProvider` will find dependencies form self and parent.
-    pub fn fork_rc(self: &Rc<ServiceProvider<Parent, Conts>>) -> ServiceProvider<Rc<Self>, HNil> {
+    pub fn fork_rc(self: &Rc<ServiceProvider<Parent, Conts>>) -> RcFork<Self> {
         ServiceProvider {
             parent: self.clone(),
             containers: HNil,
@@ -98,16 +312,100 @@ impl<Parent, Conts> ServiceProvider<Parent, Conts> {
 
     /// Forking `ServiceProvider` creates a new `ServiceProvider` with reference to the parent.
     /// `resolve` method on forked `ServiceProvider` will find dependencies form self and parent.
-    pub fn fork_arc(
-        self: &Arc<ServiceProvider<Parent, Conts>>,
-    ) -> ServiceProvider<Arc<Self>, HNil> {
+    pub fn fork_arc(self: &Arc<ServiceProvider<Parent, Conts>>) -> ArcFork<Self> {
         ServiceProvider {
             parent: self.clone(),
             containers: HNil,
         }
     }
+
+    /// Same as `ServiceProvider::fork_arc`, but also checks that the scope is `'static` and can be
+    /// moved into a spawned task (for example, `tokio::spawn` or `std::thread::spawn`). The scope
+    /// owns a reference-counted handle to the provider instead of borrowing it, so it is usual to
+    /// fork it at the start of background work for a request.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use teloc::*;
+    ///
+    /// #[derive(Dependency)]
+    /// struct Job<'a> {
+    ///     id: &'a u32,
+    /// }
+    ///
+    /// let sp = Arc::new(ServiceProvider::new().add_transient::<Job>());
+    /// let scope = sp.fork_owned().add_instance(7u32);
+    ///
+    /// let id = thread::spawn(move || {
+    ///     let job: Job = scope.resolve();
+    ///     *job.id
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// assert_eq!(id, 7);
+    /// ```
+    pub fn fork_owned(self: &Arc<ServiceProvider<Parent, Conts>>) -> ArcFork<Self>
+    where
+        Self: SpawnSafe,
+    {
+        self.fork_arc()
+    }
 }
 
+/// Marker for providers that can be shared with spawned tasks, see `ServiceProvider::fork_owned`.
+/// It is implemented for every `Send + Sync + 'static` type, you never need to implement it by
+/// yourself.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be shared with spawned tasks",
+    label = "provider must be `Send + Sync + 'static`",
+    note = "services and instances must not borrow local data; register shared services as `Arc<_>`"
+)]
+pub trait SpawnSafe: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> SpawnSafe for T {}
+
+/// Scope forked from the provider `SP` by `ServiceProvider::fork`, with containers `Conts` added
+/// to it. Forks keep the type of the parent, so services of the parent are still checked at compile
+/// time, and creating a fork only copies a pointer. Use these aliases to keep types of scopes short
+/// in signatures, for example `ArcFork<AppProvider>` instead of
+/// `ServiceProvider<Arc<ServiceProvider<EmptyServiceProvider, HCons<...>>>, HNil>`. If the type of
+/// scopes must not change when services are added to the parent, fork them from `SharedParent`,
+/// which lists only the services shared with scopes.
+///
+/// Usage:
+/// ```
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Config;
+///
+/// #[derive(Dependency)]
+/// struct Handler<'a> {
+///     config: &'a Config,
+///     request: &'a u32,
+/// }
+///
+/// type AppProvider = ServiceProvider<EmptyServiceProvider, HList![InstanceContainer<Config>]>;
+///
+/// fn handle(scope: ArcFork<AppProvider, HList![InstanceContainer<u32>]>) -> u32 {
+///     let scope = scope.add_transient::<Handler>();
+///     let handler: Handler = scope.resolve();
+///     *handler.request
+/// }
+///
+/// let sp: Arc<AppProvider> = Arc::new(ServiceProvider::new().add_instance(Config));
+/// assert_eq!(handle(sp.fork_arc().add_instance(5)), 5);
+/// ```
+pub type Fork<'a, SP, Conts = HNil> = ServiceProvider<&'a SP, Conts>;
+
+/// Scope forked from the provider `SP` by `ServiceProvider::fork_rc`, see `Fork`.
+pub type RcFork<SP, Conts = HNil> = ServiceProvider<Rc<SP>, Conts>;
+
+/// Scope forked from the provider `SP` by `ServiceProvider::fork_arc`, see `Fork`.
+pub type ArcFork<SP, Conts = HNil> = ServiceProvider<Arc<SP>, Conts>;
+
 // Clippy requires to create type aliases
 type ContainerTransientAddConvert<Parent, T, U, Conts> =
     ServiceProvider<Parent, HCons<ConvertContainer<TransientContainer<T>, T, U>, Conts>>;
@@ -115,6 +413,8 @@ type ContainerSingletonAddConvert<Parent, T, U, Conts> =
     ServiceProvider<Parent, HCons<ConvertContainer<SingletonContainer<T>, T, U>, Conts>>;
 type ContainerInstanceAddConvert<Parent, T, U, Conts> =
     ServiceProvider<Parent, HCons<ConvertContainer<InstanceContainer<T>, T, U>, Conts>>;
+type ContainerTransientAddMap<Parent, T, U, Conts> =
+    ServiceProvider<Parent, HCons<MapContainer<TransientContainer<T>, T, U>, Conts>>;
 
 impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
     /// Method used primary for internal actions. In common usage you don't need to use it. It add dependencies to the store. You need
@@ -142,6 +442,96 @@ impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
         }
     }
 
+    /// Remove the container `Cont` from the provider, so the service can be registered again with
+    /// another implementation or lifetime. A service cannot be registered twice: resolving it
+    /// fails to compile, because the container to use is ambiguous, and
+    /// `ServiceProvider::check_unique` rejects it even if it is never resolved. Use this method to
+    /// override registrations made by a library explicitly.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct Mailer(&'static str);
+    /// #[inject]
+    /// impl Mailer {
+    ///     fn new() -> Self { Mailer("smtp") }
+    /// }
+    ///
+    /// fn defaults() -> ServiceProvider<EmptyServiceProvider, HList![TransientContainer<Mailer>]> {
+    ///     ServiceProvider::new().add_transient::<Mailer>()
+    /// }
+    ///
+    /// let sp = defaults()
+    ///     .remove::<TransientContainer<Mailer>, _>()
+    ///     .add_instance(Mailer("fake"));
+    ///
+    /// let mailer: &Mailer = sp.resolve();
+    /// assert_eq!(mailer.0, "fake");
+    /// ```
+    ///
+    /// Without removing, the service cannot be resolved:
+    /// ```compile_fail
+    /// use teloc::*;
+    ///
+    /// struct Mailer(&'static str);
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(Mailer("smtp"))
+    ///     .add_instance(Mailer("fake"));
+    ///
+    /// let mailer: &Mailer = sp.resolve();
+    /// ```
+    pub fn remove<Cont, Index>(
+        self,
+    ) -> ServiceProvider<Parent, <Conts as Plucker<Cont, Index>>::Remainder>
+    where
+        Conts: Plucker<Cont, Index>,
+    {
+        let ServiceProvider { parent, containers } = self;
+        ServiceProvider {
+            parent,
+            containers: containers.pluck().1,
+        }
+    }
+
+    /// Compiles only when no container is registered twice, and returns the provider unchanged.
+    /// Call it after all services are added to catch duplicate registrations even if the service is
+    /// never resolved. To replace a registration on purpose, remove it first with
+    /// `ServiceProvider::remove`. See also `check_unique` for checking the type of a provider.
+    ///
+    /// Containers are compared by type, so a service registered with two different lifetimes (for
+    /// example `add_singleton::<T>()` and `add_instance(T)`) is only rejected when it is resolved.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct Mailer(&'static str);
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(Mailer("smtp"))
+    ///     .add_instance(5u8)
+    ///     .check_unique();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use teloc::*;
+    ///
+    /// struct Mailer(&'static str);
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(Mailer("smtp"))
+    ///     .add_instance(Mailer("fake"))
+    ///     .check_unique();
+    /// ```
+    pub fn check_unique<Infer>(self) -> Self
+    where
+        Conts: UniqueContainers<Infer>,
+    {
+        self
+    }
+
     /// Add dependency with the `Transient` lifetime. Transient services will be created each time
     /// when it called. Use this lifetime for lightweight stateless services.
     ///
@@ -229,6 +619,17 @@ impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
         self._add::<SingletonContainer<T>>(())
     }
 
+    /// Add dependency with the lifetime declared by the type itself in the `AutoLifetime` trait,
+    /// usually using `#[dependency(lifetime = "...")]` attribute of `#[derive(Dependency)]`. So the
+    /// lifetime is written once next to the type instead of in every provider. For more
+    /// information see `AutoLifetime` trait.
+    pub fn add_auto<T>(self) -> ServiceProvider<Parent, HCons<T::Container, Conts>>
+    where
+        T: AutoLifetime,
+    {
+        self._add::<T::Container>(())
+    }
+
     /// Add anything instance to provider. It likes singleton, but it cannot get dependencies from
     /// the provider. Use it for adding single objects like configs.
     ///
@@ -271,6 +672,41 @@ impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
         self._add::<InstanceContainer<T>>(data)
     }
 
+    /// Add a collection of instances, that services depend on as `&[T]` (a list of routes or
+    /// validators, for example). More instances can be appended later by
+    /// `ServiceProvider::extend_instances`, so every module of an application can add its own.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct Route {
+    ///     path: &'static str,
+    /// }
+    ///
+    /// struct Router<'a> {
+    ///     routes: &'a [Route],
+    /// }
+    /// #[inject]
+    /// impl<'a> Router<'a> {
+    ///     fn new(routes: &'a [Route]) -> Self { Router { routes } }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instances(vec![Route { path: "/" }, Route { path: "/login" }])
+    ///     .add_transient::<Router>();
+    ///
+    /// let router: Router = sp.resolve();
+    /// let paths: Vec<_> = router.routes.iter().map(|r| r.path).collect();
+    /// assert_eq!(paths, ["/", "/login"]);
+    /// ```
+    pub fn add_instances<T>(
+        self,
+        instances: impl IntoIterator<Item = T>,
+    ) -> ServiceProvider<Parent, HCons<InstancesContainer<T>, Conts>> {
+        self._add::<InstancesContainer<T>>(instances.into_iter().collect())
+    }
+
     /// Same as `ServiceProvider::add_transient`, but can be used for convert one type to another
     /// when resolving. Can be used for creating `Box<dyn Trait>` instances, for example.
     ///
@@ -347,6 +783,599 @@ impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
     {
         self._add::<ConvertContainer<InstanceContainer<T>, T, U>>(instance)
     }
+
+    /// Same as `ServiceProvider::add_transient_c`, but converts `T` to `U` by the function `map`
+    /// instead of the `Into` trait, so `From` implementations are not needed.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::sync::Arc;
+    /// use teloc::*;
+    ///
+    /// trait Clock {
+    ///     fn now(&self) -> u64;
+    /// }
+    /// struct SystemClock;
+    /// impl Clock for SystemClock {
+    ///     fn now(&self) -> u64 { 42 }
+    /// }
+    /// #[inject]
+    /// impl SystemClock {
+    ///     fn new() -> Self { SystemClock }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_transient_mapped::<SystemClock, Arc<dyn Clock>>(|clock| Arc::new(clock));
+    ///
+    /// let clock: Arc<dyn Clock> = sp.resolve();
+    /// assert_eq!(clock.now(), 42);
+    /// ```
+    pub fn add_transient_mapped<T, U>(
+        self,
+        map: impl Fn(T) -> U + 'static,
+    ) -> ContainerTransientAddMap<Parent, T, U, Conts> {
+        self._add::<MapContainer<TransientContainer<T>, T, U>>(((), Box::new(map)))
+    }
+
+    /// Add dependency with the `Transient` lifetime that is created by the factory instead of
+    /// `Dependency::init`. Factory gets all dependencies as a tuple (or as an `HList`, see
+    /// `FactoryArgs`).
+    ///
+    /// Factory is boxed, so the type of the provider does not contain the type of the closure and
+    /// can be written by hand. If the factory borrows dependencies from the same provider, use
+    /// `ServiceProvider::add_transient_factory_unboxed`.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::rc::Rc;
+    /// use teloc::*;
+    ///
+    /// struct Greeting(String);
+    ///
+    /// type SP = ServiceProvider<
+    ///     EmptyServiceProvider,
+    ///     HCons<
+    ///         TransientFactoryContainer<(Rc<String>,), Greeting>,
+    ///         HCons<InstanceContainer<Rc<String>>, HNil>,
+    ///     >,
+    /// >;
+    ///
+    /// let sp: SP = ServiceProvider::new()
+    ///     .add_instance(Rc::new("world".to_string()))
+    ///     .add_transient_factory(|(name,): (Rc<String>,)| Greeting(format!("Hello, {}!", name)));
+    ///
+    /// let greeting: Greeting = sp.resolve();
+    /// assert_eq!(greeting.0, "Hello, world!");
+    /// ```
+    pub fn add_transient_factory<Deps, T, F>(
+        self,
+        factory: F,
+    ) -> ServiceProvider<Parent, HCons<TransientFactoryContainer<Deps, T>, Conts>>
+    where
+        F: Fn(Deps) -> T + 'static,
+    {
+        self._add::<TransientFactoryContainer<Deps, T>>(Box::new(factory) as BoxedFactory<Deps, T>)
+    }
+
+    /// Same as `ServiceProvider::add_transient_factory`, but stores the closure without boxing.
+    /// Calls are not dynamically dispatched and the factory may borrow dependencies from the same
+    /// provider, but the type of the provider contains the type of the closure.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct Port(u16);
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(8080u16)
+    ///     .add_transient_factory_unboxed(|(port,): (&u16,)| Port(*port));
+    ///
+    /// let port: Port = sp.resolve();
+    /// assert_eq!(port.0, 8080);
+    /// ```
+    pub fn add_transient_factory_unboxed<Deps, T, F>(
+        self,
+        factory: F,
+    ) -> ServiceProvider<Parent, HCons<TransientFactoryContainer<Deps, T, F>, Conts>>
+    where
+        F: Fn(Deps) -> T,
+    {
+        self._add::<TransientFactoryContainer<Deps, T, F>>(factory)
+    }
+
+    /// Add dependency with the `Transient` lifetime that is created by an `async` factory. Factory
+    /// gets all dependencies as a tuple (or as an `HList`, see `FactoryArgs`) and returns a future
+    /// of the service. Such services are resolved by `AsyncResolver::resolve_async`, each time
+    /// with a new future, and cannot be dependencies of other services.
+    ///
+    /// The closure is stored without boxing, so the factory may borrow dependencies from the same
+    /// provider.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct TokenSource { prefix: &'static str }
+    /// impl TokenSource {
+    ///     async fn fetch(&self) -> String { format!("{}-1", self.prefix) }
+    /// }
+    ///
+    /// struct Token(String);
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(TokenSource { prefix: "token" })
+    ///     .add_transient_factory_async(|(source,): (&TokenSource,)| async move {
+    ///         Token(source.fetch().await)
+    ///     });
+    ///
+    /// futures::executor::block_on(async {
+    ///     let token: Token = sp.resolve_async().await;
+    ///     assert_eq!(token.0, "token-1");
+    /// });
+    /// ```
+    pub fn add_transient_factory_async<Deps, T, F, Fut>(
+        self,
+        factory: F,
+    ) -> ServiceProvider<Parent, HCons<AsyncTransientFactoryContainer<Deps, T, F>, Conts>>
+    where
+        F: Fn(Deps) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        self._add::<AsyncTransientFactoryContainer<Deps, T, F>>(factory)
+    }
+
+    /// Add a factory function written with the `#[factory]` attribute, with the `Transient`
+    /// lifetime. Same as `ServiceProvider::add_transient_factory_unboxed`, so the function may
+    /// borrow dependencies from the same provider. For `Singleton` lifetime pass the function to
+    /// `ServiceProvider::add_singleton_factory_unboxed`.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct Label<'a> {
+    ///     text: &'a str,
+    /// }
+    ///
+    /// #[factory]
+    /// fn make_label(text: &String) -> Label<'_> {
+    ///     Label { text }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance("label".to_string())
+    ///     .add_factory(make_label);
+    ///
+    /// let label: Label = sp.resolve();
+    /// assert_eq!(label.text, "label");
+    /// ```
+    pub fn add_factory<Deps, T, F>(
+        self,
+        factory: F,
+    ) -> ServiceProvider<Parent, HCons<TransientFactoryContainer<Deps, T, F>, Conts>>
+    where
+        F: Fn(Deps) -> T,
+    {
+        self.add_transient_factory_unboxed(factory)
+    }
+
+    /// Add dependency with the `GraphScoped` lifetime. It is created once per resolution of a
+    /// graph of services, and is shared by all services of the graph that depend on it, like a
+    /// singleton that lives only during one `resolve` call. Use it for units of work, database
+    /// transactions and so on, when several transient services must share one instance.
+    ///
+    /// Graph-scoped services are resolved only from providers created by
+    /// `ServiceProvider::graph`, otherwise code does not compile. They are resolved by cloning, so
+    /// `T` must implement `DependencyClone` (e.g. be an `Rc<_>` or `Arc<_>`) and be `'static`.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::rc::Rc;
+    /// use teloc::*;
+    ///
+    /// struct Transaction;
+    /// #[inject]
+    /// impl Transaction {
+    ///     fn new() -> Self { Transaction }
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Orders { tx: Rc<Transaction> }
+    /// #[derive(Dependency)]
+    /// struct Payments { tx: Rc<Transaction> }
+    /// #[derive(Dependency)]
+    /// struct Checkout { orders: Orders, payments: Payments }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_graph_scoped::<Rc<Transaction>>()
+    ///     .add_transient::<Orders>()
+    ///     .add_transient::<Payments>()
+    ///     .add_transient::<Checkout>();
+    ///
+    /// let first: Checkout = sp.graph().resolve();
+    /// let second: Checkout = sp.graph().resolve();
+    /// assert!(Rc::ptr_eq(&first.orders.tx, &first.payments.tx));
+    /// assert!(!Rc::ptr_eq(&first.orders.tx, &second.orders.tx));
+    /// ```
+    pub fn add_graph_scoped<T>(
+        self,
+    ) -> ServiceProvider<Parent, HCons<GraphScopedContainer<T>, Conts>> {
+        self._add::<GraphScopedContainer<T>>(())
+    }
+
+    /// Same as `ServiceProvider::add_transient_factory` but for `Singleton` lifetime. Factory will
+    /// be called only once, when the service will be resolved first time.
+    pub fn add_singleton_factory<Deps, T, F>(
+        self,
+        factory: F,
+    ) -> ServiceProvider<Parent, HCons<SingletonFactoryContainer<Deps, T>, Conts>>
+    where
+        F: Fn(Deps) -> T + 'static,
+    {
+        self._add::<SingletonFactoryContainer<Deps, T>>(Box::new(factory) as BoxedFactory<Deps, T>)
+    }
+
+    /// Add dependency with the `Singleton` lifetime that is created by one of two factories:
+    /// `if_true` when the `predicate` returns `true`, otherwise `if_false`. Both factories return
+    /// the same type (usually `Box<dyn Trait>` or `Arc<dyn Trait>`) and get their dependencies as
+    /// tuples, like `ServiceProvider::add_singleton_factory`. The predicate is called once, during
+    /// the registration, and only dependencies of the chosen factory are resolved, so deployment
+    /// switches (environment variables, configuration) do not require rebuilding.
+    ///
+    /// Factories are boxed. If a factory borrows dependencies from the same provider, use
+    /// `ServiceProvider::add_singleton_if_unboxed`.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// trait Mailer {
+    ///     fn name(&self) -> &'static str;
+    /// }
+    /// struct SmtpMailer { host: &'static str }
+    /// impl Mailer for SmtpMailer {
+    ///     fn name(&self) -> &'static str { self.host }
+    /// }
+    /// struct FakeMailer;
+    /// impl Mailer for FakeMailer {
+    ///     fn name(&self) -> &'static str { "fake" }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance("smtp.example.com")
+    ///     .add_singleton_if(
+    ///         || std::env::var("USE_FAKE_MAILER").is_ok(),
+    ///         |()| Box::new(FakeMailer) as Box<dyn Mailer>,
+    ///         |(host,): (&'static str,)| Box::new(SmtpMailer { host }) as Box<dyn Mailer>,
+    ///     );
+    ///
+    /// let mailer: &Box<dyn Mailer> = sp.resolve();
+    /// assert_eq!(mailer.name(), "smtp.example.com");
+    /// ```
+    pub fn add_singleton_if<DepsA, DepsB, T, P, A, B>(
+        self,
+        predicate: P,
+        if_true: A,
+        if_false: B,
+    ) -> ServiceProvider<Parent, HCons<ConditionalSingletonContainer<DepsA, DepsB, T>, Conts>>
+    where
+        P: FnOnce() -> bool,
+        A: Fn(DepsA) -> T + 'static,
+        B: Fn(DepsB) -> T + 'static,
+    {
+        self._add::<ConditionalSingletonContainer<DepsA, DepsB, T>>((
+            predicate(),
+            Box::new(if_true) as BoxedFactory<DepsA, T>,
+            Box::new(if_false) as BoxedFactory<DepsB, T>,
+        ))
+    }
+
+    /// Same as `ServiceProvider::add_singleton_if`, but stores the factories without boxing.
+    #[allow(clippy::type_complexity)]
+    pub fn add_singleton_if_unboxed<DepsA, DepsB, T, P, A, B>(
+        self,
+        predicate: P,
+        if_true: A,
+        if_false: B,
+    ) -> ServiceProvider<Parent, HCons<ConditionalSingletonContainer<DepsA, DepsB, T, A, B>, Conts>>
+    where
+        P: FnOnce() -> bool,
+        A: Fn(DepsA) -> T,
+        B: Fn(DepsB) -> T,
+    {
+        self._add::<ConditionalSingletonContainer<DepsA, DepsB, T, A, B>>((
+            predicate(),
+            if_true,
+            if_false,
+        ))
+    }
+
+    /// Add a `Deferred<T>` handle that can be resolved by any service and will be set during
+    /// `ServiceProvider::warm_up`. `T` must be resolvable by ownership from this provider by the
+    /// time of warm up. For more information see `Deferred`.
+    #[cfg(feature = "std")]
+    pub fn add_deferred<T>(self) -> ServiceProvider<Parent, HCons<DeferredContainer<T>, Conts>> {
+        self._add::<DeferredContainer<T>>(())
+    }
+
+    /// Forbid resolving `T` (and `&T`) from this provider and all its forks, including as a
+    /// dependency of other services, for example to keep the `DbPool` inside the repository's part
+    /// of the application. Services registered in the parent are forbidden to depend on `T` too,
+    /// when they are resolved through this provider. Resolving a forbidden type is a compile error,
+    /// that is reported by `cargo check` too (and by `check_provider`):
+    /// - if `T` is not registered, the error says that `T` is forbidden by `Reason`, see
+    ///   `Forbidden`.
+    /// - if `T` is registered in this provider or in its parent, resolving is ambiguous, and the
+    ///   `ForbiddenContainer` is listed among the candidates.
+    ///
+    /// Usage:
+    /// ```compile_fail
+    /// use teloc::*;
+    ///
+    /// struct GlobalState;
+    /// struct NoGlobalState;
+    /// impl ForbidReason for NoGlobalState {
+    ///     const REASON: &'static str = "pass the state to services explicitly";
+    /// }
+    ///
+    /// struct Service;
+    /// #[inject]
+    /// impl Service {
+    ///     fn new(_: &GlobalState) -> Self { Service }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .forbid::<GlobalState, NoGlobalState>()
+    ///     .add_transient::<Service>();
+    ///
+    /// let service: Service = sp.resolve();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use teloc::*;
+    ///
+    /// struct DbPool;
+    /// struct UseRepository;
+    /// impl ForbidReason for UseRepository {
+    ///     const REASON: &'static str = "use `Repository` instead of `DbPool`";
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(DbPool)
+    ///     .fork()
+    ///     .forbid::<DbPool, UseRepository>();
+    ///
+    /// let pool: &DbPool = sp.resolve();
+    /// ```
+    pub fn forbid<T, Reason>(
+        self,
+    ) -> ServiceProvider<Parent, HCons<ForbiddenContainer<T, Reason>, Conts>> {
+        self._add::<ForbiddenContainer<T, Reason>>(())
+    }
+
+    /// Add a `ScopeHandle`, that services can take as a dependency to resolve `Types` (a tuple of
+    /// owned `'static` services) later, by their type. The handle resolves services from the scope
+    /// it was resolved from, so handles resolved from forks see services of the fork too. All of
+    /// `Types` must be resolvable from that scope.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::rc::Rc;
+    /// use teloc::*;
+    ///
+    /// trait Plugin {
+    ///     fn run(&self) -> String;
+    /// }
+    /// struct Upper(Rc<String>);
+    /// impl Plugin for Upper {
+    ///     fn run(&self) -> String { self.0.to_uppercase() }
+    /// }
+    /// struct Lower(Rc<String>);
+    /// impl Plugin for Lower {
+    ///     fn run(&self) -> String { self.0.to_lowercase() }
+    /// }
+    ///
+    /// struct Dispatcher<'a> {
+    ///     scope: ScopeHandle<'a>,
+    /// }
+    /// #[inject]
+    /// impl<'a> Dispatcher<'a> {
+    ///     fn new(scope: ScopeHandle<'a>) -> Self { Dispatcher { scope } }
+    /// }
+    /// impl Dispatcher<'_> {
+    ///     fn dispatch(&self, name: &str) -> Option<String> {
+    ///         let plugin: Rc<dyn Plugin> = match name {
+    ///             "upper" => Rc::new(Upper(self.scope.resolve()?)),
+    ///             "lower" => Rc::new(Lower(self.scope.resolve()?)),
+    ///             _ => return None,
+    ///         };
+    ///         Some(plugin.run())
+    ///     }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_scope_handle::<(Rc<String>,)>()
+    ///     .add_transient::<Dispatcher>();
+    /// let scope = sp.fork().add_instance(Rc::new("Text".to_string()));
+    ///
+    /// let dispatcher: Dispatcher = scope.resolve();
+    /// assert_eq!(dispatcher.dispatch("upper").as_deref(), Some("TEXT"));
+    /// assert_eq!(dispatcher.dispatch("other"), None);
+    /// ```
+    pub fn add_scope_handle<Types>(
+        self,
+    ) -> ServiceProvider<Parent, HCons<ScopeHandleContainer<Types>, Conts>> {
+        self._add::<ScopeHandleContainer<Types>>(())
+    }
+
+    /// Add dependency with the `Singleton` lifetime, that can be referred by its dependencies as
+    /// `Later<T>`. The handle is filled right after the singleton is created, so mutually
+    /// referencing services (like a registry and its plugins) can be wired without `unsafe` or
+    /// `Option` fields. For more information see `Later`.
+    pub fn add_singleton_cyclic<T>(
+        self,
+    ) -> ServiceProvider<Parent, HCons<CyclicSingletonContainer<T>, Conts>> {
+        self._add::<CyclicSingletonContainer<T>>(())
+    }
+
+    /// Declare that the singleton (or instance) `T` must be initialized by
+    /// `ServiceProvider::warm_up` after `Before`, even if `T` does not depend on `Before` by its
+    /// type. For example, migrations must be run before the HTTP listener is started.
+    /// Dependencies by types are always initialized first, so constraints must not contradict
+    /// them. `warm_up` panics with the cycle if constraints contradict each other.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::cell::RefCell;
+    /// use teloc::*;
+    ///
+    /// struct Migrations;
+    /// #[inject]
+    /// impl Migrations {
+    ///     fn new(log: &RefCell<Vec<&'static str>>) -> Self {
+    ///         log.borrow_mut().push("migrations");
+    ///         Migrations
+    ///     }
+    /// }
+    ///
+    /// struct Listener;
+    /// #[inject]
+    /// impl Listener {
+    ///     fn new(log: &RefCell<Vec<&'static str>>) -> Self {
+    ///         log.borrow_mut().push("listener");
+    ///         Listener
+    ///     }
+    /// }
+    ///
+    /// let log = RefCell::new(vec![]);
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(&log)
+    ///     .add_singleton::<Listener>()
+    ///     .add_singleton::<Migrations>()
+    ///     .depends_on::<Listener, Migrations>();
+    ///
+    /// sp.warm_up();
+    /// assert_eq!(*log.borrow(), ["migrations", "listener"]);
+    /// ```
+    pub fn depends_on<T, Before>(
+        self,
+    ) -> ServiceProvider<Parent, HCons<OrderContainer<T, Before>, Conts>> {
+        self._add::<OrderContainer<T, Before>>(())
+    }
+
+    /// Register a callback that is called when the provider is dropped, usually on a forked scope,
+    /// for ad-hoc cleanup (temporary files, span exits) that does not warrant a service type.
+    /// Callbacks are called in reverse order of registration, before services registered earlier
+    /// are dropped.
+    ///
+    /// Usage:
+    /// ```
+    /// use std::cell::RefCell;
+    /// use teloc::*;
+    ///
+    /// let log = RefCell::new(vec![]);
+    /// let sp = ServiceProvider::new();
+    /// {
+    ///     let scope = sp
+    ///         .fork()
+    ///         .on_drop(|| log.borrow_mut().push("remove temp dir"))
+    ///         .add_instance(String::from("/tmp/upload-1"))
+    ///         .on_drop(|| log.borrow_mut().push("exit span"));
+    ///     let _: &String = scope.resolve();
+    /// }
+    /// assert_eq!(*log.borrow(), ["exit span", "remove temp dir"]);
+    /// ```
+    pub fn on_drop<F>(
+        self,
+        callback: F,
+    ) -> ServiceProvider<Parent, HCons<OnDropContainer<F>, Conts>>
+    where
+        F: FnOnce(),
+    {
+        self._add::<OnDropContainer<F>>(callback)
+    }
+
+    /// Mark the singleton (or instance) `T` as an implementor of `Dyn`, so it is returned by
+    /// `ServiceProvider::iter_implementors`. `cast` converts a reference to the service into a
+    /// reference to `Dyn`, for trait objects it is usually `|s| s`. `T` must be registered in the
+    /// provider by itself.
+    pub fn add_implementor<Dyn, T>(
+        self,
+        cast: fn(&T) -> &Dyn,
+    ) -> ServiceProvider<Parent, HCons<ImplementorContainer<Dyn, T>, Conts>>
+    where
+        Dyn: ?Sized,
+    {
+        self._add::<ImplementorContainer<Dyn, T>>((cast, 0))
+    }
+
+    /// Same as `ServiceProvider::add_implementor`, but `ServiceProvider::iter_implementors` returns
+    /// the service ordered by `priority`: lower priorities come first, and services with equal
+    /// priorities keep the order of registration. It lets chains of handlers (middlewares, hooks)
+    /// have an order that does not depend on where the services are registered.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// trait Middleware {
+    ///     fn name(&self) -> &'static str;
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Auth;
+    /// impl Middleware for Auth {
+    ///     fn name(&self) -> &'static str { "auth" }
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Logging;
+    /// impl Middleware for Logging {
+    ///     fn name(&self) -> &'static str { "logging" }
+    /// }
+    ///
+    /// #[derive(Dependency)]
+    /// struct Compression;
+    /// impl Middleware for Compression {
+    ///     fn name(&self) -> &'static str { "compression" }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_singleton::<Auth>()
+    ///     .add_implementor_with_priority::<dyn Middleware, Auth>(10, |m| m)
+    ///     .add_singleton::<Compression>()
+    ///     .add_implementor::<dyn Middleware, Compression>(|m| m)
+    ///     .add_singleton::<Logging>()
+    ///     .add_implementor_with_priority::<dyn Middleware, Logging>(-10, |m| m);
+    ///
+    /// let chain: Vec<_> = sp
+    ///     .iter_implementors::<dyn Middleware, _>()
+    ///     .map(|m| m.name())
+    ///     .collect();
+    /// assert_eq!(chain, ["logging", "compression", "auth"]);
+    /// ```
+    pub fn add_implementor_with_priority<Dyn, T>(
+        self,
+        priority: i32,
+        cast: fn(&T) -> &Dyn,
+    ) -> ServiceProvider<Parent, HCons<ImplementorContainer<Dyn, T>, Conts>>
+    where
+        Dyn: ?Sized,
+    {
+        self._add::<ImplementorContainer<Dyn, T>>((cast, priority))
+    }
+
+    /// Same as `ServiceProvider::add_transient_factory_unboxed` but for `Singleton` lifetime.
+    pub fn add_singleton_factory_unboxed<Deps, T, F>(
+        self,
+        factory: F,
+    ) -> ServiceProvider<Parent, HCons<SingletonFactoryContainer<Deps, T, F>, Conts>>
+    where
+        F: Fn(Deps) -> T,
+    {
+        self._add::<SingletonFactoryContainer<Deps, T, F>>(factory)
+    }
 }
 
 /// Borrow containers from a ServiceProvider.
@@ -422,3 +1451,60 @@ where
         self.parent.get()
     }
 }
+
+/// Implemented for lists of containers where every container type is registered once. Do not use
+/// it by yourself, see `ServiceProvider::check_unique`.
+///
+/// Finding the index of a container that is registered twice is ambiguous, so the check fails to
+/// compile with "type annotations needed".
+pub trait UniqueContainers<Infer> {}
+
+impl UniqueContainers<HNil> for HNil {}
+
+impl<H, Tail, Index, TailInfer> UniqueContainers<HCons<Index, TailInfer>> for HCons<H, Tail>
+where
+    Self: Selector<H, Index>,
+    Tail: UniqueContainers<TailInfer>,
+{
+}
+
+/// Compiles only when no container of the provider type `SP` is registered twice, and does
+/// nothing. Like `check_provider`, it can be placed in a `const` item next to the type of a
+/// provider. `Infer` is always `_`.
+///
+/// Usage:
+/// ```
+/// use teloc::*;
+///
+/// type AppSp = ServiceProvider<
+///     EmptyServiceProvider,
+///     HCons<InstanceContainer<u8>, HCons<InstanceContainer<u16>, HNil>>,
+/// >;
+///
+/// const _: () = check_unique::<AppSp, _>();
+/// ```
+///
+/// ```compile_fail
+/// use teloc::*;
+///
+/// type AppSp = ServiceProvider<
+///     EmptyServiceProvider,
+///     HCons<InstanceContainer<u8>, HCons<InstanceContainer<u8>, HNil>>,
+/// >;
+///
+/// const _: () = check_unique::<AppSp, _>();
+/// ```
+pub const fn check_unique<SP, Infer>()
+where
+    SP: UniqueProvider<Infer>,
+{
+}
+
+/// Implemented for providers where every container type is registered once, see `check_unique`.
+/// Do not use it by yourself.
+pub trait UniqueProvider<Infer> {}
+
+impl<Parent, Conts, Infer> UniqueProvider<Infer> for ServiceProvider<Parent, Conts> where
+    Conts: UniqueContainers<Infer>
+{
+}
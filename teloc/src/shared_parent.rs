@@ -0,0 +1,161 @@
+//! Type-stable parent of forked scopes, see `SharedParent`.
+
+use crate::{Resolver, ServiceProvider};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::any::{Any, TypeId};
+use core::marker::PhantomData;
+use frunk::hlist::Selector;
+use frunk::{HCons, HNil};
+
+type Resolve = Arc<dyn Fn() -> Box<dyn Any> + Send + Sync>;
+
+/// Parent of scopes, that shares services of a provider without its type. Scopes forked from it
+/// by `SharedParent::fork` have the type `SharedFork<Types>`, where `Types` is the list of shared
+/// services, so types of scopes do not grow with the provider, and creating a scope copies one
+/// pointer.
+///
+/// Services are listed by `SharedParentBuilder::share`. Each of them is resolved by a function that gets
+/// the provider by a reference of any lifetime, usually `|sp| sp.resolve()`, so they must be owned
+/// and `'static` (usually `Arc<_>` services). Resolving a service that is not listed is a compile
+/// error, as it is for `ServiceProvider`.
+///
+/// Usage:
+/// ```
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// struct Config { name: &'static str }
+///
+/// struct Handler<'a> {
+///     config: Arc<Config>,
+///     request: &'a u32,
+/// }
+/// #[inject]
+/// impl<'a> Handler<'a> {
+///     fn new(config: Arc<Config>, request: &'a u32) -> Self { Handler { config, request } }
+/// }
+///
+/// type AppParent = SharedParent<HList![Arc<Config>]>;
+///
+/// // The type of the scope does not depend on the type of the provider.
+/// fn handle(scope: SharedFork<HList![Arc<Config>], HList![InstanceContainer<u32>]>) -> String {
+///     let scope = scope.add_transient::<Handler>();
+///     let handler: Handler = scope.resolve();
+///     format!("{} {}", handler.config.name, handler.request)
+/// }
+///
+/// let sp = Arc::new(ServiceProvider::new().add_instance(Arc::new(Config { name: "app" })));
+/// let parent: AppParent = SharedParent::builder(sp).share(|sp| sp.resolve()).build();
+/// assert_eq!(handle(parent.fork().add_instance(5)), "app 5");
+/// ```
+///
+/// Services that are not shared cannot be resolved from scopes:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// let sp = Arc::new(ServiceProvider::new().add_instance(Arc::new(5u8)).add_instance(Arc::new(1u16)));
+/// let parent: SharedParent<HList![Arc<u8>]> = SharedParent::builder(sp).share(|sp| sp.resolve()).build();
+/// let value: Arc<u16> = parent.fork().resolve();
+/// ```
+pub struct SharedParent<Types> {
+    resolvers: Arc<BTreeMap<TypeId, Resolve>>,
+    phantom: PhantomData<fn() -> Types>,
+}
+
+/// Scope forked from `SharedParent<Types>`, with containers `Conts` added to it.
+pub type SharedFork<Types, Conts = HNil> = ServiceProvider<SharedParent<Types>, Conts>;
+
+/// Index of a service resolved from `SharedParent`. Do not use it by yourself.
+pub struct SharedIndex<Index>(PhantomData<Index>);
+
+impl SharedParent<HNil> {
+    /// Create a parent that shares no services of the provider yet, see `SharedParentBuilder::share`.
+    pub fn builder<SP>(sp: Arc<SP>) -> SharedParentBuilder<SP> {
+        SharedParentBuilder {
+            sp,
+            parent: SharedParent {
+                resolvers: Arc::new(BTreeMap::new()),
+                phantom: PhantomData,
+            },
+        }
+    }
+}
+
+impl<Types> SharedParent<Types> {
+    /// Fork a new scope from the parent.
+    pub fn fork(&self) -> SharedFork<Types> {
+        ServiceProvider {
+            parent: self.clone(),
+            containers: HNil,
+        }
+    }
+
+    fn resolve<T: 'static>(&self) -> T {
+        let resolve = self
+            .resolvers
+            .get(&TypeId::of::<T>())
+            .expect("every listed type is shared");
+        *resolve()
+            .downcast::<T>()
+            .expect("resolver is registered for the same type")
+    }
+}
+
+impl<Types> Clone for SharedParent<Types> {
+    fn clone(&self) -> Self {
+        SharedParent {
+            resolvers: self.resolvers.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Builder of `SharedParent`, created by `SharedParent::builder`.
+pub struct SharedParentBuilder<SP, Types = HNil> {
+    sp: Arc<SP>,
+    parent: SharedParent<Types>,
+}
+
+impl<SP, Types> SharedParentBuilder<SP, Types>
+where
+    SP: Send + Sync + 'static,
+{
+    /// Share `T` of the provider, resolved by the `resolve` function.
+    pub fn share<T, F>(self, resolve: F) -> SharedParentBuilder<SP, HCons<T, Types>>
+    where
+        T: 'static,
+        F: Fn(&SP) -> T + Send + Sync + 'static,
+    {
+        let SharedParentBuilder { sp, parent } = self;
+        let mut resolvers = parent.resolvers;
+        let shared = sp.clone();
+        let resolve: Resolve = Arc::new(move || Box::new(resolve(&shared)));
+        Arc::make_mut(&mut resolvers).insert(TypeId::of::<T>(), resolve);
+        SharedParentBuilder {
+            sp,
+            parent: SharedParent {
+                resolvers,
+                phantom: PhantomData,
+            },
+        }
+    }
+
+    /// Build the parent with the shared services.
+    pub fn build(self) -> SharedParent<Types> {
+        self.parent
+    }
+}
+
+impl<'a, T, Types, Conts, Index> Resolver<'a, T, SharedIndex<Index>>
+    for ServiceProvider<SharedParent<Types>, Conts>
+where
+    T: 'static,
+    Types: Selector<T, Index>,
+{
+    fn resolve(&'a self) -> T {
+        self.parent.resolve()
+    }
+}
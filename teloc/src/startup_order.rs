@@ -0,0 +1,306 @@
+//! Support for explicit ordering of eager initialization, see `ServiceProvider::depends_on`.
+
+use crate::cleanup::OnDropContainer;
+use crate::conditional::ConditionalSingletonContainer;
+use crate::container::{
+    Container, ConvertContainer, InstanceContainer, InstancesContainer, MapContainer,
+    SingletonContainer, SingletonFactoryContainer, TransientContainer, TransientFactoryContainer,
+};
+#[cfg(feature = "std")]
+use crate::deferred::DeferredContainer;
+use crate::forbid::ForbiddenContainer;
+use crate::graph::{GraphCache, GraphScopedContainer};
+use crate::implementors::{CollectImplementors, ImplementorContainer};
+use crate::scope_handle::ScopeHandleContainer;
+use crate::validate::{Validate, ValidationError};
+use crate::warm_up::WarmUp;
+use crate::Resolver;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::fmt;
+use core::marker::PhantomData;
+use frunk::{HCons, HNil};
+
+/// Container of the constraint that the singleton `T` is initialized by `ServiceProvider::warm_up`
+/// after the singleton `Before`, see `ServiceProvider::depends_on`.
+pub struct OrderContainer<T, Before>(PhantomData<fn() -> (T, Before)>);
+
+impl<T, Before> Container for OrderContainer<T, Before> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<T, Before> Clone for OrderContainer<T, Before> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'a, SP, T, Before> WarmUp<'a, SP, ()> for OrderContainer<T, Before> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, T, Before> CollectImplementors<'a, SP, Dyn, ()>
+    for OrderContainer<T, Before>
+{
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+// Name of a service and the function that initializes it.
+type Startup<'a, SP> = (&'static str, fn(&'a SP));
+
+// Name of a type and the function that resolves and validates it.
+type Validation<'a, SP> = (&'static str, fn(&'a SP) -> Result<(), String>);
+
+/// Services constrained by `ServiceProvider::depends_on` and the constraints between them, and
+/// types validated by `ServiceProvider::add_validation`.
+pub struct StartupOrder<'a, SP> {
+    // Services in order of the first mention.
+    services: Vec<Startup<'a, SP>>,
+    // Indices of services: the first one is initialized before the second one.
+    edges: Vec<(usize, usize)>,
+    validations: Vec<Validation<'a, SP>>,
+}
+
+impl<'a, SP> StartupOrder<'a, SP> {
+    pub(crate) fn new() -> Self {
+        StartupOrder {
+            services: Vec::new(),
+            edges: Vec::new(),
+            validations: Vec::new(),
+        }
+    }
+
+    /// Add the constraint that `T` is initialized after `Before`.
+    pub fn add<T, Before, InferT, InferBefore>(&mut self)
+    where
+        SP: Resolver<'a, &'a T, InferT> + Resolver<'a, &'a Before, InferBefore>,
+        T: 'a,
+        Before: 'a,
+    {
+        let before = self.service(type_name::<Before>(), init::<SP, Before, InferBefore>);
+        let after = self.service(type_name::<T>(), init::<SP, T, InferT>);
+        self.edges.push((before, after));
+    }
+
+    /// Add the validation of `T`, that is run before services are initialized.
+    pub fn validate<T, Infer>(&mut self)
+    where
+        SP: Resolver<'a, T, Infer>,
+        T: Validate,
+    {
+        self.validations
+            .push((type_name::<T>(), validate::<SP, T, Infer>));
+    }
+
+    fn service(&mut self, name: &'static str, init: fn(&'a SP)) -> usize {
+        match self.services.iter().position(|(n, _)| *n == name) {
+            Some(index) => index,
+            None => {
+                self.services.push((name, init));
+                self.services.len() - 1
+            }
+        }
+    }
+
+    /// Run all validations and collect their failures.
+    pub(crate) fn run_validations(&self, sp: &'a SP) -> Result<(), ValidationError> {
+        let failures: Vec<_> = self
+            .validations
+            .iter()
+            .filter_map(|&(name, validate)| validate(sp).err().map(|message| (name, message)))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::new(failures))
+        }
+    }
+
+    /// Initialize services in topological order. Among services that can be initialized, the one
+    /// mentioned first goes first. If constraints contradict each other, nothing is initialized.
+    pub(crate) fn run(self, sp: &'a SP) -> Result<(), StartupError> {
+        for i in self.sorted()? {
+            (self.services[i].1)(sp);
+        }
+        Ok(())
+    }
+
+    fn sorted(&self) -> Result<Vec<usize>, StartupError> {
+        let mut done = alloc::vec![false; self.services.len()];
+        let mut sorted = Vec::with_capacity(self.services.len());
+        for _ in 0..self.services.len() {
+            let next = (0..self.services.len()).find(|&i| {
+                !done[i]
+                    && self
+                        .edges
+                        .iter()
+                        .all(|&(before, after)| after != i || done[before])
+            });
+            match next {
+                Some(i) => {
+                    sorted.push(i);
+                    done[i] = true;
+                }
+                None => return Err(StartupError::Cycle(self.find_cycle(&done))),
+            }
+        }
+        Ok(sorted)
+    }
+
+    // Every service that is left has a predecessor that is left too, so walking back over them
+    // must come to a service that is already in the path.
+    fn find_cycle(&self, done: &[bool]) -> Vec<&'static str> {
+        let mut path: Vec<usize> = Vec::new();
+        let mut current = (0..done.len()).find(|&i| !done[i]).unwrap();
+        while !path.contains(&current) {
+            path.push(current);
+            current = self
+                .edges
+                .iter()
+                .find(|&&(before, after)| after == current && !done[before])
+                .map(|&(before, _)| before)
+                .unwrap();
+        }
+        let start = path.iter().position(|&i| i == current).unwrap();
+        let mut cycle = path[start..].to_vec();
+        cycle.push(current);
+        // The path was walked from later services to earlier ones.
+        cycle
+            .into_iter()
+            .rev()
+            .map(|i| self.services[i].0)
+            .collect()
+    }
+}
+
+/// Error of `ServiceProvider::try_warm_up`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupError {
+    /// Validations added by `ServiceProvider::add_validation` failed.
+    Validation(ValidationError),
+    /// Constraints added by `ServiceProvider::depends_on` contradict each other. Contains names
+    /// of services in the cycle, the first one is repeated at the end.
+    Cycle(Vec<&'static str>),
+}
+
+impl From<ValidationError> for StartupError {
+    fn from(e: ValidationError) -> Self {
+        StartupError::Validation(e)
+    }
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartupError::Validation(e) => e.fmt(f),
+            StartupError::Cycle(cycle) => {
+                write!(f, "contradicting startup order: {}", cycle.join(" -> "))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StartupError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StartupError::Validation(e) => Some(e),
+            StartupError::Cycle(_) => None,
+        }
+    }
+}
+
+fn init<'a, SP, T, Infer>(sp: &'a SP)
+where
+    SP: Resolver<'a, &'a T, Infer>,
+    T: 'a,
+{
+    Resolver::<&T, Infer>::resolve(sp);
+}
+
+fn validate<'a, SP, T, Infer>(sp: &'a SP) -> Result<(), String>
+where
+    SP: Resolver<'a, T, Infer>,
+    T: Validate,
+{
+    Resolver::<T, Infer>::resolve(sp).validate()
+}
+
+/// The trait, used by `ServiceProvider::warm_up` to collect ordering constraints and validations
+/// from containers. Implement it (as a no-op with `Infer = ()`) for your own container if you wish that providers
+/// with it can be warmed up.
+pub trait CollectOrder<'a, SP, Infer> {
+    fn collect_order(&'a self, order: &mut StartupOrder<'a, SP>);
+}
+
+impl<'a, SP> CollectOrder<'a, SP, HNil> for HNil {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+impl<'a, SP, H, Tail, InferH, InferTail> CollectOrder<'a, SP, HCons<InferH, InferTail>>
+    for HCons<H, Tail>
+where
+    H: CollectOrder<'a, SP, InferH>,
+    Tail: CollectOrder<'a, SP, InferTail>,
+{
+    fn collect_order(&'a self, order: &mut StartupOrder<'a, SP>) {
+        // The head is the last registered container.
+        self.tail.collect_order(order);
+        self.head.collect_order(order);
+    }
+}
+
+impl<'a, SP, T, Before, InferT, InferBefore> CollectOrder<'a, SP, (InferT, InferBefore)>
+    for OrderContainer<T, Before>
+where
+    SP: Resolver<'a, &'a T, InferT> + Resolver<'a, &'a Before, InferBefore>,
+    T: 'a,
+    Before: 'a,
+{
+    fn collect_order(&'a self, order: &mut StartupOrder<'a, SP>) {
+        order.add::<T, Before, InferT, InferBefore>();
+    }
+}
+
+macro_rules! impl_noop_collect_order {
+    ($($cont:ty => ($($param:ident),*)),*) => {
+        $(
+        impl<'a, SP, $($param),*> CollectOrder<'a, SP, ()> for $cont {
+            fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+        }
+        )*
+    };
+}
+
+impl_noop_collect_order!(
+    TransientContainer<T> => (T),
+    SingletonContainer<T> => (T),
+    InstanceContainer<T> => (T),
+    InstancesContainer<T> => (T),
+    ConvertContainer<Cont, T, U> => (Cont, T, U),
+    MapContainer<Cont, T, U, F> => (Cont, T, U, F),
+    TransientFactoryContainer<Deps, T, F> => (Deps, T, F),
+    SingletonFactoryContainer<Deps, T, F> => (Deps, T, F),
+    ConditionalSingletonContainer<DepsA, DepsB, T, A, B> => (DepsA, DepsB, T, A, B),
+    ForbiddenContainer<T, Reason> => (T, Reason),
+    GraphScopedContainer<T> => (T),
+    GraphCache => (),
+    ScopeHandleContainer<Types> => (Types)
+);
+
+impl<'a, SP, Dyn: ?Sized, T> CollectOrder<'a, SP, ()> for ImplementorContainer<Dyn, T> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+impl<'a, SP, F: FnOnce()> CollectOrder<'a, SP, ()> for OnDropContainer<F> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+#[cfg(feature = "std")]
+impl_noop_collect_order!(DeferredContainer<T> => (T));
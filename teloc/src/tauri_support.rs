@@ -0,0 +1,166 @@
+//! Support for `tauri` crate.
+//!
+//! `TelocPlugin` attaches the `ServiceProvider` to the managed state of the application, and
+//! `Inject<T>` command argument resolves `T` from a scope that is forked for every invocation.
+//!
+//! Usage:
+//! ```no_run
+//! use std::sync::Arc;
+//! use teloc::tauri_support::{Inject, TelocPlugin};
+//! use teloc::*;
+//!
+//! struct Repository;
+//! #[inject]
+//! impl Repository {
+//!     fn new() -> Self { Repository }
+//! }
+//!
+//! struct Controller {
+//!     repository: Arc<Repository>,
+//! }
+//! #[inject]
+//! impl Controller {
+//!     fn new(repository: Arc<Repository>) -> Self { Controller { repository } }
+//! }
+//! impl Controller {
+//!     fn greet(&self, name: &str) -> String { format!("Hello, {}!", name) }
+//! }
+//!
+//! #[tauri::command]
+//! fn greet(name: String, controller: Inject<Controller>) -> String {
+//!     controller.greet(&name)
+//! }
+//!
+//! fn builder<R: tauri::Runtime>() -> tauri::Builder<R> {
+//!     let sp = Arc::new(
+//!         ServiceProvider::new()
+//!             .add_singleton::<Arc<Repository>>()
+//!             .add_transient::<Controller>(),
+//!     );
+//!
+//!     tauri::Builder::default()
+//!         .plugin(TelocPlugin::new(sp, |scope| scope).inject::<Controller, _>(|scope| scope.resolve()))
+//!         .invoke_handler(tauri::generate_handler![greet])
+//! }
+//! # fn main() {}
+//! ```
+
+use crate::injectors::{Injectors, Scope};
+use crate::ServiceProvider;
+use frunk::HNil;
+use serde_json::Value as JsonValue;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tauri::ipc::{CommandArg, CommandItem, InvokeError};
+use tauri::plugin::Plugin;
+use tauri::{AppHandle, Manager, Runtime};
+
+type CreateScope = Arc<dyn Fn() -> Scope + Send + Sync>;
+
+/// Plugin that adds the `ServiceProvider` to the managed state, so commands can get services using
+/// the `Inject<T>` argument.
+///
+/// - Scope factory gets the forked scope and can add some local dependencies that will be unique
+///   in different invocations.
+/// - Every type that is extracted using `Inject` must be registered using `TelocPlugin::inject`,
+///   otherwise the command will fail with an error.
+pub struct TelocPlugin<ScopeResult> {
+    create_scope: CreateScope,
+    injectors: Injectors,
+    phantom: PhantomData<fn() -> ScopeResult>,
+}
+
+impl<ScopeResult: Send + Sync + 'static> TelocPlugin<ScopeResult> {
+    /// Creates `TelocPlugin` with specified `ServiceProvider` and scope factory.
+    pub fn new<ParSP, DepsSP, ScopeFactory>(
+        sp: Arc<ServiceProvider<ParSP, DepsSP>>,
+        scope_factory: ScopeFactory,
+    ) -> Self
+    where
+        ParSP: Send + Sync + 'static,
+        DepsSP: Send + Sync + 'static,
+        ScopeFactory: Fn(ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HNil>) -> ScopeResult
+            + Send
+            + Sync
+            + 'static,
+    {
+        TelocPlugin {
+            create_scope: Arc::new(move || Arc::new(scope_factory(sp.fork_arc()))),
+            injectors: Injectors::default(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Allow to extract `T` from the invocation scope using `Inject<T>`. `resolve` is usually
+    /// `|scope| scope.resolve()`: `.inject::<Controller, _>(|scope| scope.resolve())`. `T` cannot
+    /// borrow services from the scope.
+    pub fn inject<T, F>(mut self, resolve: F) -> Self
+    where
+        F: Fn(&ScopeResult) -> T + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        self.injectors.register(resolve);
+        self
+    }
+}
+
+impl<ScopeResult: 'static, R: Runtime> Plugin<R> for TelocPlugin<ScopeResult> {
+    fn name(&self) -> &'static str {
+        "teloc"
+    }
+
+    fn initialize(
+        &mut self,
+        app: &AppHandle<R>,
+        _: JsonValue,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        app.manage(TelocState {
+            create_scope: self.create_scope.clone(),
+            injectors: Arc::new(self.injectors.clone()),
+        });
+        Ok(())
+    }
+}
+
+struct TelocState {
+    create_scope: CreateScope,
+    injectors: Arc<Injectors>,
+}
+
+/// Command argument that resolves `T` from the invocation scope created by `TelocPlugin`. The
+/// value is accessed through `Deref` and `DerefMut`. `T` must be registered using
+/// `TelocPlugin::inject`, otherwise the command fails with an error.
+pub struct Inject<T>(T);
+
+impl<T> std::ops::Deref for Inject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Inject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<'de, T: Send + 'static, R: Runtime> CommandArg<'de, R> for Inject<T> {
+    fn from_command(command: CommandItem<'de, R>) -> Result<Self, InvokeError> {
+        let state = command
+            .message
+            .state_ref()
+            .try_get::<TelocState>()
+            .ok_or_else(|| InvokeError::from("`TelocPlugin` is not added".to_string()))?;
+        let scope = (state.create_scope)();
+        match state.injectors.resolve(&*scope) {
+            Some(value) => Ok(Inject(value)),
+            None => Err(InvokeError::from(format!(
+                "`{}` is not registered in the `TelocPlugin` (command `{}`)",
+                std::any::type_name::<T>(),
+                command.name
+            ))),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Injectable source of the current time, so services that depend on time can be tested with a
+//! controlled clock.
+//!
+//! Services take `Arc<dyn Clock>` (or `&Arc<dyn Clock>`) as a dependency. The application registers
+//! the real clock by `ServiceProvider::with_system_clock`, tests register a `TestClock` by
+//! `ServiceProvider::with_test_clock` and move it by hand.
+//!
+//! Usage:
+//! ```
+//! use std::sync::Arc;
+//! use std::time::{Duration, SystemTime};
+//! use teloc::time::{Clock, TestClock};
+//! use teloc::*;
+//!
+//! struct Session {
+//!     clock: Arc<dyn Clock>,
+//!     expires_at: SystemTime,
+//! }
+//! #[inject]
+//! impl Session {
+//!     fn new(clock: Arc<dyn Clock>) -> Self {
+//!         let expires_at = clock.now() + Duration::from_secs(60);
+//!         Session { clock, expires_at }
+//!     }
+//! }
+//! impl Session {
+//!     fn is_expired(&self) -> bool {
+//!         self.clock.now() >= self.expires_at
+//!     }
+//! }
+//!
+//! let sp = ServiceProvider::new()
+//!     .with_test_clock()
+//!     .add_transient::<Session>();
+//!
+//! let session: Session = sp.resolve();
+//! assert!(!session.is_expired());
+//!
+//! let clock: &Arc<TestClock> = sp.resolve();
+//! clock.advance(Duration::from_secs(60));
+//! assert!(session.is_expired());
+//! ```
+
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use frunk::hlist::HList;
+use frunk::HCons;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Source of the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// `Clock` that returns the time of the system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// `Clock` that stays at the same time until it is moved by `TestClock::set` or
+/// `TestClock::advance`. By default it starts at `UNIX_EPOCH`.
+#[derive(Debug)]
+pub struct TestClock(Mutex<SystemTime>);
+
+impl TestClock {
+    pub fn new(start: SystemTime) -> Self {
+        TestClock(Mutex::new(start))
+    }
+
+    /// Move the clock to `time`. The clock may be moved backwards.
+    pub fn set(&self, time: SystemTime) {
+        *self.lock() = time;
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.lock() += duration;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SystemTime> {
+        // The time is always valid, so a panic of other thread does not matter.
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        TestClock::new(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> SystemTime {
+        *self.lock()
+    }
+}
+
+/// Container of the clock registered by `ServiceProvider::with_system_clock` and
+/// `ServiceProvider::with_test_clock`.
+pub type ClockContainer = InstanceContainer<Arc<dyn Clock>>;
+
+/// Container of the `TestClock` registered by `ServiceProvider::with_test_clock`.
+pub type TestClockContainer = InstanceContainer<Arc<TestClock>>;
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add `SystemClock` as the `Arc<dyn Clock>` instance.
+    pub fn with_system_clock(self) -> ServiceProvider<Parent, HCons<ClockContainer, Conts>> {
+        self.add_instance(Arc::new(SystemClock) as Arc<dyn Clock>)
+    }
+
+    /// Add a new `TestClock` as the `Arc<dyn Clock>` instance. The same clock is added as the
+    /// `Arc<TestClock>` instance too, so tests can move it. See the module documentation for an
+    /// example.
+    pub fn with_test_clock(
+        self,
+    ) -> ServiceProvider<Parent, HCons<ClockContainer, HCons<TestClockContainer, Conts>>> {
+        let clock = Arc::new(TestClock::default());
+        self.add_instance(clock.clone())
+            .add_instance(clock as Arc<dyn Clock>)
+    }
+}
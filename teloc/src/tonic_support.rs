@@ -0,0 +1,251 @@
+//! Support for `tonic` crate.
+
+use crate::container::InstanceContainer;
+use crate::dependency::DependencyClone;
+use crate::injectors::{Injectors, Scope};
+use crate::tower_support::Scoped;
+use crate::{ArcFork, GetDependencies, ServiceProvider};
+use frunk::{HCons, HNil};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Status};
+
+/// Scope that is forked from the `ServiceProvider` for every RPC. It contains `MetadataMap` and
+/// `Option<SocketAddr>` of the peer.
+pub type RpcScope<SP> = ArcFork<
+    SP,
+    HCons<InstanceContainer<Option<SocketAddr>>, HCons<InstanceContainer<MetadataMap>, HNil>>,
+>;
+
+type CreateScope = Arc<dyn Fn(MetadataMap, Option<SocketAddr>) -> Scope + Send + Sync>;
+
+/// Wrapper around implementation of a tonic-generated service trait, that is built from the
+/// `ServiceProvider` and can resolve services from a per-RPC scope inside handler methods.
+///
+/// Implement the generated trait for `DiGrpcService<MyServiceImpl>` and call
+/// `DiGrpcService::resolve` in handler methods. Fields of `MyServiceImpl` are available through
+/// `Deref`. Type of the `ServiceProvider` is erased, so it need not be named in the impl.
+///
+/// Usage:
+/// ```
+/// use std::sync::Arc;
+/// use teloc::*;
+/// use tonic::{Request, Response, Status};
+///
+/// // Usually generated by `tonic-build`.
+/// trait Greeter {
+///     fn say_hello(&self, request: Request<String>) -> Result<Response<String>, Status>;
+/// }
+///
+/// struct GreeterImpl {
+///     greeting: Arc<String>,
+/// }
+/// #[inject]
+/// impl GreeterImpl {
+///     fn new(greeting: Arc<String>) -> Self { GreeterImpl { greeting } }
+/// }
+///
+/// struct Caller {
+///     user: String,
+/// }
+/// #[inject]
+/// impl Caller {
+///     fn new(metadata: &tonic::metadata::MetadataMap) -> Self {
+///         let user = metadata.get("user").and_then(|v| v.to_str().ok()).unwrap_or("anonymous");
+///         Caller { user: user.to_string() }
+///     }
+/// }
+///
+/// impl Greeter for DiGrpcService<GreeterImpl> {
+///     fn say_hello(&self, request: Request<String>) -> Result<Response<String>, Status> {
+///         let caller = self.resolve::<Caller, _>(&request)?;
+///         Ok(Response::new(format!("{} {} from {}", self.greeting, request.get_ref(), caller.user)))
+///     }
+/// }
+///
+/// let sp = Arc::new(
+///     ServiceProvider::new()
+///         .add_instance(Arc::new("Hello".to_string()))
+///         .add_transient::<Caller>(),
+/// );
+/// let greeter = DiGrpcService::builder(&sp)
+///     .inject::<Caller, _>(|scope| scope.resolve())
+///     .build(GreeterImpl::init);
+///
+/// let mut request = Request::new("Bob".to_string());
+/// request.metadata_mut().insert("user", "alice".parse().unwrap());
+/// let response = greeter.say_hello(request).unwrap();
+/// assert_eq!(response.get_ref(), "Hello Bob from alice");
+/// ```
+pub struct DiGrpcService<S> {
+    service: S,
+    create_scope: CreateScope,
+    injectors: Arc<Injectors>,
+}
+
+impl<S> DiGrpcService<S> {
+    /// Build the service implementation using `init` (usually `MyServiceImpl::init`, generated by
+    /// `#[inject]`) with dependencies resolved from the `ServiceProvider`. No services can be
+    /// resolved from the per-RPC scope, use `DiGrpcService::builder` for it.
+    pub fn new<'a, ParSP, DepsSP, Deps, Infer>(
+        sp: &'a Arc<ServiceProvider<ParSP, DepsSP>>,
+        init: impl FnOnce(Deps) -> S,
+    ) -> Self
+    where
+        ServiceProvider<ParSP, DepsSP>: GetDependencies<'a, Deps, Infer>,
+        ParSP: Send + Sync + 'static,
+        DepsSP: Send + Sync + 'static,
+    {
+        DiGrpcService::builder(sp).build(init)
+    }
+
+    /// Fork a new scope for the RPC and resolve `T` from it. `T` must be registered using
+    /// `DiGrpcServiceBuilder::inject`, otherwise `Status::internal` is returned. Every call forks a
+    /// new scope, so resolve one service that takes all needed dependencies. `T` owns its value,
+    /// and the scope is dropped as soon as `T` is resolved.
+    // `Status` is large, but returning it as is lets `?` work in tonic handlers.
+    #[allow(clippy::result_large_err)]
+    pub fn resolve<T: 'static, R>(&self, request: &Request<R>) -> Result<Scoped<T>, Status> {
+        let scope = (self.create_scope)(request.metadata().clone(), request.remote_addr());
+        match self.injectors.resolve(&*scope) {
+            Some(value) => Ok(Scoped(value)),
+            None => Err(Status::internal(format!(
+                "`{}` is not registered in the `DiGrpcService`",
+                std::any::type_name::<T>()
+            ))),
+        }
+    }
+
+    /// Get the service implementation.
+    pub fn get_ref(&self) -> &S {
+        &self.service
+    }
+}
+
+impl DiGrpcService<()> {
+    /// Creates a builder, that allows to register types resolved from the per-RPC scope.
+    pub fn builder<ParSP, DepsSP>(
+        sp: &Arc<ServiceProvider<ParSP, DepsSP>>,
+    ) -> DiGrpcServiceBuilder<'_, ServiceProvider<ParSP, DepsSP>>
+    where
+        ParSP: Send + Sync + 'static,
+        DepsSP: Send + Sync + 'static,
+    {
+        DiGrpcServiceBuilder {
+            sp,
+            injectors: Injectors::default(),
+        }
+    }
+}
+
+impl<S> std::ops::Deref for DiGrpcService<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.service
+    }
+}
+
+impl<S: Clone> Clone for DiGrpcService<S> {
+    fn clone(&self) -> Self {
+        DiGrpcService {
+            service: self.service.clone(),
+            create_scope: self.create_scope.clone(),
+            injectors: self.injectors.clone(),
+        }
+    }
+}
+
+/// Builder of `DiGrpcService`, see `DiGrpcService::builder`.
+pub struct DiGrpcServiceBuilder<'a, SP> {
+    sp: &'a Arc<SP>,
+    injectors: Injectors,
+}
+
+impl<'a, ParSP, DepsSP> DiGrpcServiceBuilder<'a, ServiceProvider<ParSP, DepsSP>>
+where
+    ParSP: Send + Sync + 'static,
+    DepsSP: Send + Sync + 'static,
+{
+    /// Allow to resolve `T` from the per-RPC scope using `DiGrpcService::resolve`. `resolve` is
+    /// usually `|scope| scope.resolve()`: `.inject::<Controller, _>(|scope| scope.resolve())`.
+    /// `T` cannot borrow services from the scope.
+    pub fn inject<T, F>(mut self, resolve: F) -> Self
+    where
+        F: Fn(&RpcScope<ServiceProvider<ParSP, DepsSP>>) -> T + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        self.injectors.register(resolve);
+        self
+    }
+
+    /// Build the service implementation using `init` with dependencies resolved from the
+    /// `ServiceProvider`.
+    pub fn build<S, Deps, Infer>(self, init: impl FnOnce(Deps) -> S) -> DiGrpcService<S>
+    where
+        ServiceProvider<ParSP, DepsSP>: GetDependencies<'a, Deps, Infer>,
+    {
+        let sp = self.sp.clone();
+        DiGrpcService {
+            service: init(GetDependencies::<Deps, Infer>::get_deps(&**self.sp)),
+            create_scope: Arc::new(move |metadata, addr| {
+                Arc::new(sp.fork_arc().add_instance(metadata).add_instance(addr))
+            }),
+            injectors: Arc::new(self.injectors),
+        }
+    }
+}
+
+impl DependencyClone for Option<SocketAddr> {}
+
+/// Tonic interceptor built from a closure that gets the provider and the `Request`, so it can use
+/// services resolved from the provider (auth validators, metrics sinks and so on). Services are
+/// resolved for each request, and the provider is passed by a reference of any lifetime, so they
+/// cannot outlive the call.
+///
+/// Usage:
+/// ```
+/// use std::sync::Arc;
+/// use teloc::*;
+/// use tonic::service::Interceptor;
+/// use tonic::{Request, Status};
+///
+/// struct TokenValidator { token: String }
+///
+/// let sp = ServiceProvider::new().add_instance(TokenValidator { token: "secret".into() });
+///
+/// let mut interceptor = DiInterceptor::new(Arc::new(sp), |sp, request| {
+///     let validator: &TokenValidator = sp.resolve();
+///     match request.metadata().get("token") {
+///         Some(token) if token == validator.token.as_str() => Ok(request),
+///         _ => Err(Status::unauthenticated("invalid token")),
+///     }
+/// });
+///
+/// let mut request = Request::new(());
+/// request.metadata_mut().insert("token", "secret".parse().unwrap());
+/// assert!(interceptor.call(request).is_ok());
+/// assert!(interceptor.call(Request::new(())).is_err());
+/// ```
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct DiInterceptor(Arc<dyn Fn(Request<()>) -> Result<Request<()>, Status> + Send + Sync>);
+
+impl DiInterceptor {
+    /// Create an interceptor from the provider and the interceptor function.
+    #[allow(clippy::result_large_err)]
+    pub fn new<SP, F>(sp: Arc<SP>, f: F) -> Self
+    where
+        SP: Send + Sync + 'static,
+        F: Fn(&SP, Request<()>) -> Result<Request<()>, Status> + Send + Sync + 'static,
+    {
+        DiInterceptor(Arc::new(move |request| f(&sp, request)))
+    }
+}
+
+impl tonic::service::Interceptor for DiInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        (self.0)(request)
+    }
+}
@@ -0,0 +1,249 @@
+//! Support for `tower` based stacks (hyper, tonic, axum and others).
+//!
+//! `ProvideLayer` forks a request scope from the `ServiceProvider` for every `http::Request` and
+//! puts it into the request extensions, and `Scoped<T>` resolves `T` from this scope.
+//!
+//! Usage:
+//! ```
+//! use http::{Method, Request};
+//! use std::convert::Infallible;
+//! use std::sync::Arc;
+//! use teloc::tower_support::{ProvideLayer, Scoped};
+//! use teloc::*;
+//! use tower::{service_fn, Layer, Service, ServiceExt};
+//!
+//! struct Controller {
+//!     method: Method,
+//! }
+//! #[inject]
+//! impl Controller {
+//!     fn new(method: Method) -> Self {
+//!         Controller { method }
+//!     }
+//! }
+//!
+//! let sp = Arc::new(ServiceProvider::new().add_transient::<Controller>());
+//! let layer = ProvideLayer::new(sp, |scope, parts| scope.add_instance(parts.method.clone()))
+//!     .inject::<Controller, _>(|scope| scope.resolve());
+//!
+//! let mut service = layer.layer(service_fn(|req: Request<()>| async move {
+//!     let controller = Scoped::<Controller>::from_extensions(req.extensions()).unwrap();
+//!     Ok::<_, Infallible>(controller.method.to_string())
+//! }));
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let res = service.ready().await.unwrap().call(Request::new(())).await.unwrap();
+//! assert_eq!(res, "GET");
+//! # });
+//! ```
+use crate::dependency::DependencyClone;
+use crate::injectors::{Injectors, Scope};
+use crate::ServiceProvider;
+use frunk::HNil;
+use http::request::Parts;
+use http::{Extensions, HeaderMap, Method, Request, Uri, Version};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layer that forks a request scope from the `ServiceProvider` for every request and puts it into
+/// the request extensions, so services can be got from it using `Scoped<T>` (or extractors of
+/// framework integrations built on it, like `axum_support::Inject<T>`).
+///
+/// - Scope factory gets the forked scope and the request parts, and can add some local
+///   dependencies that will be unique in different requests.
+/// - Every type that is got using `Scoped` must be registered using `ProvideLayer::inject`.
+/// - Got types own their values, so share services of the scope between them as `Arc<T>`.
+pub struct ProvideLayer<SP, ScopeFactory, ScopeResult> {
+    sp: Arc<SP>,
+    scope_factory: ScopeFactory,
+    injectors: Arc<Injectors>,
+    phantom: PhantomData<fn() -> ScopeResult>,
+}
+
+impl<ParSP, DepsSP, ScopeFactory, ScopeResult>
+    ProvideLayer<ServiceProvider<ParSP, DepsSP>, ScopeFactory, ScopeResult>
+where
+    ScopeFactory:
+        Fn(ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HNil>, &Parts) -> ScopeResult,
+    ScopeResult: Send + Sync + 'static,
+{
+    /// Creates `ProvideLayer` with specified `ServiceProvider` and scope factory.
+    pub fn new(sp: Arc<ServiceProvider<ParSP, DepsSP>>, scope_factory: ScopeFactory) -> Self {
+        ProvideLayer {
+            sp,
+            scope_factory,
+            injectors: Arc::new(Injectors::default()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Allow to get `T` from the request scope using `Scoped<T>`. `resolve` is usually
+    /// `|scope| scope.resolve()`: `.inject::<Controller, _>(|scope| scope.resolve())`. `T` cannot
+    /// borrow services from the scope.
+    pub fn inject<T, F>(mut self, resolve: F) -> Self
+    where
+        F: Fn(&ScopeResult) -> T + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        Arc::make_mut(&mut self.injectors).register(resolve);
+        self
+    }
+}
+
+impl<SP, ScopeFactory: Clone, ScopeResult> Clone for ProvideLayer<SP, ScopeFactory, ScopeResult> {
+    fn clone(&self) -> Self {
+        ProvideLayer {
+            sp: self.sp.clone(),
+            scope_factory: self.scope_factory.clone(),
+            injectors: self.injectors.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, SP, ScopeFactory: Clone, ScopeResult> Layer<S>
+    for ProvideLayer<SP, ScopeFactory, ScopeResult>
+{
+    type Service = ProvideService<S, SP, ScopeFactory, ScopeResult>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProvideService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by `ProvideLayer`.
+pub struct ProvideService<S, SP, ScopeFactory, ScopeResult> {
+    inner: S,
+    layer: ProvideLayer<SP, ScopeFactory, ScopeResult>,
+}
+
+impl<S: Clone, SP, ScopeFactory: Clone, ScopeResult> Clone
+    for ProvideService<S, SP, ScopeFactory, ScopeResult>
+{
+    fn clone(&self) -> Self {
+        ProvideService {
+            inner: self.inner.clone(),
+            layer: self.layer.clone(),
+        }
+    }
+}
+
+impl<S, B, ParSP, DepsSP, ScopeFactory, ScopeResult> Service<Request<B>>
+    for ProvideService<S, ServiceProvider<ParSP, DepsSP>, ScopeFactory, ScopeResult>
+where
+    S: Service<Request<B>>,
+    ScopeFactory:
+        Fn(ServiceProvider<Arc<ServiceProvider<ParSP, DepsSP>>, HNil>, &Parts) -> ScopeResult,
+    ScopeResult: Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let (mut parts, body) = req.into_parts();
+        let scope = (self.layer.scope_factory)(self.layer.sp.fork_arc(), &parts);
+        parts.extensions.insert(RequestScope {
+            scope: Arc::new(scope),
+            injectors: self.layer.injectors.clone(),
+        });
+        self.inner.call(Request::from_parts(parts, body))
+    }
+}
+
+#[derive(Clone)]
+struct RequestScope {
+    scope: Scope,
+    injectors: Arc<Injectors>,
+}
+
+/// `T` resolved from the request scope created by `ProvideLayer`. The value is accessed through
+/// `Deref` and `DerefMut`.
+///
+/// `T` owns its value, so it cannot borrow services from the scope, which is dropped when the
+/// request is handled:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::tower_support::ProvideLayer;
+/// use teloc::*;
+///
+/// struct RequestId(u64);
+/// struct Controller<'a> {
+///     id: &'a RequestId,
+/// }
+/// #[inject]
+/// impl<'a> Controller<'a> {
+///     fn new(id: &'a RequestId) -> Self { Controller { id } }
+/// }
+///
+/// let sp = Arc::new(ServiceProvider::new().add_transient::<Controller>());
+/// let layer = ProvideLayer::new(sp, |scope, _| scope.add_instance(RequestId(1)))
+///     .inject::<Controller<'static>, _>(|scope| scope.resolve());
+/// ```
+pub struct Scoped<T>(pub(crate) T);
+
+impl<T: 'static> Scoped<T> {
+    /// Resolve `T` from the request scope stored in the request extensions.
+    pub fn from_extensions(extensions: &Extensions) -> Result<Self, ScopedError> {
+        let request_scope = extensions
+            .get::<RequestScope>()
+            .ok_or(ScopedError::MissingLayer)?;
+        let value = request_scope
+            .injectors
+            .resolve(&*request_scope.scope)
+            .ok_or(ScopedError::NotRegistered(std::any::type_name::<T>()))?;
+        Ok(Scoped(value))
+    }
+}
+
+impl<T> std::ops::Deref for Scoped<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Scoped<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Error returned by `Scoped::from_extensions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopedError {
+    /// Request was not handled by `ProvideLayer`.
+    MissingLayer,
+    /// Type with the given name was not registered using `ProvideLayer::inject`.
+    NotRegistered(&'static str),
+}
+
+impl fmt::Display for ScopedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScopedError::MissingLayer => f.write_str("`ProvideLayer` is not added to the service"),
+            ScopedError::NotRegistered(name) => {
+                write!(f, "`{}` is not registered in the `ProvideLayer`", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScopedError {}
+
+impl DependencyClone for Method {}
+impl DependencyClone for Uri {}
+impl DependencyClone for Version {}
+impl DependencyClone for HeaderMap {}
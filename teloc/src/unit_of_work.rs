@@ -0,0 +1,167 @@
+use crate::container::InstanceContainer;
+use crate::ServiceProvider;
+use frunk::hlist::HList;
+use frunk::HCons;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard};
+
+/// Transaction that can be completed by `UnitOfWork`. Dropping a transaction without completing
+/// it must roll it back, as `sqlx::Transaction` does.
+///
+/// Implemented for `sqlx::Transaction` with the `sqlx-support` feature.
+pub trait Transaction: Sized {
+    type Error;
+
+    fn commit(self) -> impl Future<Output = Result<(), Self::Error>>;
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// Holds one transaction for a scope, so all repositories in the scope work in the same
+/// transaction. Add it to a forked scope with `ServiceProvider::add_unit_of_work` and depend on
+/// `&UnitOfWork<Tx>` in your repositories.
+///
+/// Transaction is committed or rolled back by calling `commit` or `rollback`. If the scope is
+/// dropped before that, the transaction is dropped and thus rolled back.
+///
+/// Usage:
+/// ```
+/// use std::cell::Cell;
+/// use std::future::{ready, Future};
+/// use std::rc::Rc;
+/// use teloc::*;
+///
+/// struct Tx { commits: Rc<Cell<u32>>, rows: Vec<String> }
+/// impl Transaction for Tx {
+///     type Error = ();
+///     fn commit(self) -> impl Future<Output = Result<(), ()>> {
+///         self.commits.set(self.commits.get() + 1);
+///         ready(Ok(()))
+///     }
+///     fn rollback(self) -> impl Future<Output = Result<(), ()>> {
+///         ready(Ok(()))
+///     }
+/// }
+///
+/// struct UserRepository<'a> { uow: &'a UnitOfWork<Tx> }
+/// #[inject]
+/// impl<'a> UserRepository<'a> {
+///     fn new(uow: &'a UnitOfWork<Tx>) -> Self { Self { uow } }
+/// }
+/// impl UserRepository<'_> {
+///     fn insert(&self, name: &str) { self.uow.transaction().rows.push(name.into()) }
+/// }
+///
+/// let commits = Rc::new(Cell::new(0));
+/// let sp = ServiceProvider::new().add_transient::<UserRepository>();
+///
+/// let scope = sp.fork().add_unit_of_work(Tx { commits: commits.clone(), rows: vec![] });
+/// let first: UserRepository = scope.resolve();
+/// let second: UserRepository = scope.resolve();
+/// first.insert("alice");
+/// second.insert("bob");
+/// assert_eq!(first.uow.transaction().rows, ["alice", "bob"]);
+///
+/// let uow: &UnitOfWork<Tx> = scope.resolve();
+/// futures::executor::block_on(uow.commit()).unwrap();
+/// assert_eq!(commits.get(), 1);
+/// ```
+#[derive(Debug)]
+pub struct UnitOfWork<Tx> {
+    tx: Mutex<Option<Tx>>,
+}
+
+impl<Tx> UnitOfWork<Tx> {
+    pub fn new(tx: Tx) -> Self {
+        Self {
+            tx: Mutex::new(Some(tx)),
+        }
+    }
+
+    /// Get access to the transaction. Guard must be dropped before another service of the scope
+    /// will ask for the transaction.
+    ///
+    /// # Panics
+    /// Panics if the unit of work was already committed or rolled back.
+    pub fn transaction(&self) -> TransactionGuard<'_, Tx> {
+        let guard = self.lock();
+        assert!(guard.is_some(), "Unit of work was already completed");
+        TransactionGuard(guard)
+    }
+
+    /// Returns `true` if the unit of work was committed or rolled back.
+    pub fn is_completed(&self) -> bool {
+        self.lock().is_none()
+    }
+
+    fn take(&self) -> Tx {
+        self.lock()
+            .take()
+            .expect("Unit of work was already completed")
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Tx>> {
+        self.tx.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<Tx: Transaction> UnitOfWork<Tx> {
+    /// Commit the transaction.
+    ///
+    /// # Panics
+    /// Panics if the unit of work was already committed or rolled back.
+    pub async fn commit(&self) -> Result<(), Tx::Error> {
+        let tx = self.take();
+        tx.commit().await
+    }
+
+    /// Roll back the transaction.
+    ///
+    /// # Panics
+    /// Panics if the unit of work was already committed or rolled back.
+    pub async fn rollback(&self) -> Result<(), Tx::Error> {
+        let tx = self.take();
+        tx.rollback().await
+    }
+}
+
+/// Guard returned by `UnitOfWork::transaction`.
+pub struct TransactionGuard<'a, Tx>(MutexGuard<'a, Option<Tx>>);
+
+impl<Tx> Deref for TransactionGuard<'_, Tx> {
+    type Target = Tx;
+
+    fn deref(&self) -> &Tx {
+        self.0.as_ref().unwrap()
+    }
+}
+
+impl<Tx> DerefMut for TransactionGuard<'_, Tx> {
+    fn deref_mut(&mut self) -> &mut Tx {
+        self.0.as_mut().unwrap()
+    }
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add a transaction as `UnitOfWork` instance. Use it on a forked scope, so every scope will
+    /// have its own transaction. For more information see `UnitOfWork`.
+    pub fn add_unit_of_work<Tx>(
+        self,
+        tx: Tx,
+    ) -> ServiceProvider<Parent, HCons<InstanceContainer<UnitOfWork<Tx>>, Conts>> {
+        self.add_instance(UnitOfWork::new(tx))
+    }
+}
+
+#[cfg(feature = "sqlx-support")]
+impl<'c, DB: sqlx::Database> Transaction for sqlx::Transaction<'c, DB> {
+    type Error = sqlx::Error;
+
+    fn commit(self) -> impl Future<Output = Result<(), sqlx::Error>> {
+        sqlx::Transaction::commit(self)
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<(), sqlx::Error>> {
+        sqlx::Transaction::rollback(self)
+    }
+}
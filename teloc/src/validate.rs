@@ -0,0 +1,181 @@
+//! Validation of services at startup, see `ServiceProvider::add_validation`.
+
+use crate::container::Container;
+use crate::implementors::CollectImplementors;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Resolver, ServiceProvider};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use frunk::hlist::HList;
+use frunk::HCons;
+
+/// Type with invariants that are checked by `ServiceProvider::try_warm_up`, usually a
+/// configuration. Return a message that describes what is wrong, if they do not hold. Only types
+/// added by `ServiceProvider::add_validation` are checked.
+pub trait Validate {
+    fn validate(&self) -> Result<(), String>;
+}
+
+impl<T: Validate + ?Sized> Validate for &T {
+    fn validate(&self) -> Result<(), String> {
+        (**self).validate()
+    }
+}
+
+impl<T: Validate + ?Sized> Validate for Box<T> {
+    fn validate(&self) -> Result<(), String> {
+        (**self).validate()
+    }
+}
+
+impl<T: Validate + ?Sized> Validate for Rc<T> {
+    fn validate(&self) -> Result<(), String> {
+        (**self).validate()
+    }
+}
+
+impl<T: Validate + ?Sized> Validate for Arc<T> {
+    fn validate(&self) -> Result<(), String> {
+        (**self).validate()
+    }
+}
+
+/// The error is a failure, so resolving `Result<T, E>` reports errors of creating `T` too.
+impl<T: Validate, E: fmt::Display> Validate for Result<T, E> {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Ok(value) => value.validate(),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Failures of all validations of the provider, returned by `ServiceProvider::try_warm_up` in
+/// `StartupError::Validation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    failures: Vec<(&'static str, String)>,
+}
+
+impl ValidationError {
+    pub(crate) fn new(failures: Vec<(&'static str, String)>) -> Self {
+        ValidationError { failures }
+    }
+
+    /// Names of types that failed validation and messages of failures, in order of registration.
+    pub fn failures(&self) -> &[(&'static str, String)] {
+        &self.failures
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} validation(s) failed at startup:",
+            self.failures.len()
+        )?;
+        for (name, message) in &self.failures {
+            write!(f, "\n  - {}: {}", name, message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+/// Container of the validation of `T`, see `ServiceProvider::add_validation`.
+pub struct ValidateContainer<T>(PhantomData<fn() -> T>);
+
+impl<T> Container for ValidateContainer<T> {
+    type Data = ();
+
+    fn init(_: ()) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<T> Clone for ValidateContainer<T> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'a, SP, T> WarmUp<'a, SP, ()> for ValidateContainer<T> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, T> CollectImplementors<'a, SP, Dyn, ()> for ValidateContainer<T> {
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, T, Infer> CollectOrder<'a, SP, Infer> for ValidateContainer<T>
+where
+    SP: Resolver<'a, T, Infer>,
+    T: Validate,
+{
+    fn collect_order(&'a self, order: &mut StartupOrder<'a, SP>) {
+        order.validate::<T, Infer>();
+    }
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Validate `T` by `ServiceProvider::try_warm_up` (and `ServiceProvider::warm_up`), before
+    /// singletons are created. `T` is resolved from the provider, so it is usually a reference to
+    /// an instance (`&AppConfig`) or `Section<T>` of the configuration. Failures of all
+    /// validations are reported together, instead of one by one when services use invalid values.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::*;
+    ///
+    /// struct ServerConfig {
+    ///     port: u16,
+    /// }
+    /// impl Validate for ServerConfig {
+    ///     fn validate(&self) -> Result<(), String> {
+    ///         if self.port == 0 {
+    ///             return Err("port must not be 0".into());
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// struct PoolConfig {
+    ///     size: usize,
+    /// }
+    /// impl Validate for PoolConfig {
+    ///     fn validate(&self) -> Result<(), String> {
+    ///         if self.size == 0 {
+    ///             return Err("size must be positive".into());
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_instance(ServerConfig { port: 0 })
+    ///     .add_instance(PoolConfig { size: 0 })
+    ///     .add_validation::<&ServerConfig>()
+    ///     .add_validation::<&PoolConfig>();
+    ///
+    /// let err = match sp.try_warm_up() {
+    ///     Err(StartupError::Validation(err)) => err,
+    ///     _ => unreachable!(),
+    /// };
+    /// assert_eq!(err.failures().len(), 2);
+    /// assert_eq!(err.failures()[0].1, "port must not be 0");
+    /// assert_eq!(err.failures()[1].1, "size must be positive");
+    /// ```
+    pub fn add_validation<T>(self) -> ServiceProvider<Parent, HCons<ValidateContainer<T>, Conts>> {
+        self._add::<ValidateContainer<T>>(())
+    }
+}
@@ -0,0 +1,72 @@
+//! This is a section for advanced usage. For common usage you can not read this page.
+
+use crate::container::{
+    ConvertContainer, InstanceContainer, InstancesContainer, MapContainer, ResolveContainer,
+    SingletonContainer, SingletonFactoryContainer, TransientContainer, TransientFactoryContainer,
+};
+use crate::get_dependencies::{FactoryArgs, GetDependencies};
+use crate::Dependency;
+use frunk::{HCons, HNil};
+
+/// The trait, used by `ServiceProvider::warm_up` to initialize containers ahead of time. Implement
+/// it (usually as a no-op with `Infer = ()`) for your own container if you wish that providers
+/// with it can be warmed up.
+pub trait WarmUp<'a, SP, Infer> {
+    fn warm_up(&'a self, sp: &'a SP);
+}
+
+impl<'a, SP> WarmUp<'a, SP, HNil> for HNil {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, H, Tail, InferH, InferTail> WarmUp<'a, SP, HCons<InferH, InferTail>> for HCons<H, Tail>
+where
+    H: WarmUp<'a, SP, InferH>,
+    Tail: WarmUp<'a, SP, InferTail>,
+{
+    fn warm_up(&'a self, sp: &'a SP) {
+        self.head.warm_up(sp);
+        self.tail.warm_up(sp);
+    }
+}
+
+impl<'a, SP, T, Deps, Infer> WarmUp<'a, SP, (Deps, Infer)> for SingletonContainer<T>
+where
+    SP: GetDependencies<'a, Deps, Infer>,
+    T: Dependency<Deps> + 'a,
+{
+    fn warm_up(&'a self, sp: &'a SP) {
+        SingletonContainer::resolve_container(self, || sp.get_deps());
+    }
+}
+
+impl<'a, SP, Deps, T, F, Infer> WarmUp<'a, SP, (Deps, Infer)>
+    for SingletonFactoryContainer<Deps, T, F>
+where
+    SP: GetDependencies<'a, Deps::List, Infer>,
+    Deps: FactoryArgs,
+    F: Fn(Deps) -> T,
+{
+    fn warm_up(&'a self, sp: &'a SP) {
+        SingletonFactoryContainer::resolve_container(self, || Deps::from_list(sp.get_deps()));
+    }
+}
+
+macro_rules! impl_noop_warm_up {
+    ($($cont:ty => ($($param:ident),*)),*) => {
+        $(
+        impl<'a, SP, $($param),*> WarmUp<'a, SP, ()> for $cont {
+            fn warm_up(&'a self, _: &'a SP) {}
+        }
+        )*
+    };
+}
+
+impl_noop_warm_up!(
+    TransientContainer<T> => (T),
+    InstanceContainer<T> => (T),
+    InstancesContainer<T> => (T),
+    ConvertContainer<Cont, T, U> => (Cont, T, U),
+    MapContainer<Cont, T, U, F> => (Cont, T, U, F),
+    TransientFactoryContainer<Deps, T, F> => (Deps, T, F)
+);
@@ -0,0 +1,99 @@
+//! Support for `warp` crate.
+//!
+//! Filters in this module put the `ServiceProvider`, a request scope or resolved services into the
+//! extracted values, so warp handlers can get their dependencies.
+//!
+//! Usage:
+//! ```
+//! use std::sync::Arc;
+//! use teloc::warp_support::{inject, with_scope};
+//! use teloc::*;
+//! use warp::Filter;
+//!
+//! struct Repository;
+//! #[inject]
+//! impl Repository {
+//!     fn new() -> Self { Repository }
+//! }
+//!
+//! struct RequestId(u64);
+//!
+//! struct Controller<'a> {
+//!     repository: Arc<Repository>,
+//!     id: &'a RequestId,
+//! }
+//! #[inject]
+//! impl<'a> Controller<'a> {
+//!     fn new(repository: Arc<Repository>, id: &'a RequestId) -> Self {
+//!         Controller { repository, id }
+//!     }
+//! }
+//!
+//! let sp = Arc::new(
+//!     ServiceProvider::new()
+//!         .add_singleton::<Arc<Repository>>()
+//!         .add_transient::<Controller>(),
+//! );
+//!
+//! // Services without borrows can be injected directly.
+//! let repository = warp::path("repository")
+//!     .and(inject::<Arc<Repository>, _, _>(sp.clone(), |sp| sp.resolve()))
+//!     .map(|_repository: Arc<Repository>| "ok");
+//!
+//! // Services that borrow from the request scope are resolved inside the handler.
+//! let controller = warp::path("controller")
+//!     .and(with_scope(sp, |scope| scope.add_instance(RequestId(1))))
+//!     .map(|scope: Arc<_>| {
+//!         let controller: Controller = Resolver::resolve(&*scope);
+//!         controller.id.0.to_string()
+//!     });
+//!
+//! let routes = repository.or(controller);
+//! ```
+
+use crate::{ArcFork, ServiceProvider};
+use std::convert::Infallible;
+use std::sync::Arc;
+use warp::Filter;
+
+/// Filter that extracts a clone of the `Arc<ServiceProvider>`.
+pub fn with_provider<SP>(
+    sp: Arc<SP>,
+) -> impl Filter<Extract = (Arc<SP>,), Error = Infallible> + Clone
+where
+    SP: Send + Sync + 'static,
+{
+    warp::any().map(move || sp.clone())
+}
+
+/// Filter that forks a new request scope for every request. Scope factory gets the forked scope
+/// and can add some local dependencies that will be unique in different requests.
+pub fn with_scope<ParSP, DepsSP, ScopeFactory, ScopeResult>(
+    sp: Arc<ServiceProvider<ParSP, DepsSP>>,
+    scope_factory: ScopeFactory,
+) -> impl Filter<Extract = (Arc<ScopeResult>,), Error = Infallible> + Clone
+where
+    ParSP: Send + Sync + 'static,
+    DepsSP: Send + Sync + 'static,
+    ScopeFactory:
+        Fn(ArcFork<ServiceProvider<ParSP, DepsSP>>) -> ScopeResult + Clone + Send + Sync + 'static,
+    ScopeResult: Send + Sync,
+{
+    warp::any().map(move || Arc::new(scope_factory(sp.fork_arc())))
+}
+
+/// Filter that resolves `T` from the `ServiceProvider` for every request by the `resolve`
+/// function, usually `|sp| sp.resolve()`. The function gets the provider by a reference of any
+/// lifetime, so `T` must be owned (`Arc<_>` singletons, transients without borrows), use
+/// `with_scope` to resolve services that borrow from the scope.
+pub fn inject<T, SP, F>(
+    sp: Arc<SP>,
+    resolve: F,
+) -> impl Filter<Extract = (T,), Error = Infallible> + Clone
+where
+    SP: Send + Sync + 'static,
+    F: Fn(&SP) -> T + Clone + Send + Sync + 'static,
+    T: Send,
+{
+    warp::any().map(move || resolve(&sp))
+}
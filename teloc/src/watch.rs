@@ -0,0 +1,142 @@
+//! Injectable handles to values that change at runtime, see `ServiceProvider::add_watch`. Enabled
+//! by the `watch` feature.
+
+use crate::container::Container;
+use crate::implementors::CollectImplementors;
+use crate::service_provider::SelectContainer;
+use crate::startup_order::{CollectOrder, StartupOrder};
+use crate::warm_up::WarmUp;
+use crate::{Resolver, ServiceProvider};
+use frunk::hlist::HList;
+use frunk::HCons;
+use tokio::sync::watch;
+
+pub use tokio::sync::watch::error::RecvError;
+
+/// Handle to the latest value of `T`, that is registered by `ServiceProvider::add_watch`. Unlike
+/// an instance, which is a snapshot, `Watch` lets a service wait for changes of the value and
+/// react to them.
+///
+/// Every resolved `Watch` is a new subscription, so changes are tracked by each service separately.
+#[derive(Debug, Clone)]
+pub struct Watch<T>(watch::Receiver<T>);
+
+impl<T> Watch<T> {
+    /// Returns the latest value. The value is locked while the reference is alive, so do not hold
+    /// it across `.await`.
+    pub fn borrow(&self) -> watch::Ref<'_, T> {
+        self.0.borrow()
+    }
+
+    /// Returns the latest value and marks it as seen, so `Watch::changed` waits for the next one.
+    pub fn borrow_and_update(&mut self) -> watch::Ref<'_, T> {
+        self.0.borrow_and_update()
+    }
+
+    /// Waits for a value that is not seen yet by this handle. Returns an error when the provider
+    /// with the value is dropped.
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        self.0.changed().await
+    }
+
+    /// Take the underlying `tokio::sync::watch::Receiver`.
+    pub fn into_receiver(self) -> watch::Receiver<T> {
+        self.0
+    }
+}
+
+/// Container of a value that can be changed at runtime, see `ServiceProvider::add_watch`.
+pub struct WatchContainer<T>(watch::Sender<T>);
+
+impl<T> Container for WatchContainer<T> {
+    type Data = T;
+
+    fn init(init: T) -> Self {
+        Self(watch::Sender::new(init))
+    }
+}
+
+#[cfg(feature = "shared-singletons")]
+impl<T> Clone for WatchContainer<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> WatchContainer<T> {
+    #[inline]
+    pub fn get(&self) -> &watch::Sender<T> {
+        &self.0
+    }
+}
+
+impl<'this, 'cont, T, SP, Index> Resolver<'this, Watch<T>, (&'cont WatchContainer<T>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont WatchContainer<T>, Index>,
+    T: 'cont,
+{
+    fn resolve(&'this self) -> Watch<T> {
+        Watch(self.get().get().subscribe())
+    }
+}
+
+impl<'this, 'cont, T, SP, Index>
+    Resolver<'this, &'cont watch::Sender<T>, (&'cont WatchContainer<T>, Index)> for SP
+where
+    SP: SelectContainer<'this, &'cont WatchContainer<T>, Index>,
+    T: 'cont,
+{
+    fn resolve(&'this self) -> &'cont watch::Sender<T> {
+        self.get().get()
+    }
+}
+
+impl<'a, SP, T> WarmUp<'a, SP, ()> for WatchContainer<T> {
+    fn warm_up(&'a self, _: &'a SP) {}
+}
+
+impl<'a, SP, Dyn: ?Sized, T> CollectImplementors<'a, SP, Dyn, ()> for WatchContainer<T> {
+    fn collect(&'a self, _: &'a SP, _: &mut Vec<(i32, &'a Dyn)>) {}
+}
+
+impl<'a, SP, T> CollectOrder<'a, SP, ()> for WatchContainer<T> {
+    fn collect_order(&'a self, _: &mut StartupOrder<'a, SP>) {}
+}
+
+impl<Parent, Conts: HList> ServiceProvider<Parent, Conts> {
+    /// Add a value that can be changed at runtime, for example a config that is reloaded from a
+    /// file. Services depend on `Watch<T>` to read the latest value and wait for changes of it.
+    /// The value is changed through `&tokio::sync::watch::Sender<T>`, that can be resolved from
+    /// the provider too.
+    ///
+    /// Usage:
+    /// ```
+    /// use teloc::watch::Watch;
+    /// use teloc::*;
+    /// use tokio::sync::watch::Sender;
+    ///
+    /// struct Limits { max_connections: u32 }
+    ///
+    /// struct Acceptor { limits: Watch<Limits> }
+    /// #[inject]
+    /// impl Acceptor {
+    ///     fn new(limits: Watch<Limits>) -> Self { Acceptor { limits } }
+    /// }
+    ///
+    /// let sp = ServiceProvider::new()
+    ///     .add_watch(Limits { max_connections: 10 })
+    ///     .add_transient::<Acceptor>();
+    ///
+    /// let mut acceptor: Acceptor = sp.resolve();
+    /// assert_eq!(acceptor.limits.borrow().max_connections, 10);
+    ///
+    /// let reloader: &Sender<Limits> = sp.resolve();
+    /// reloader.send_replace(Limits { max_connections: 20 });
+    ///
+    /// futures::executor::block_on(acceptor.limits.changed()).unwrap();
+    /// assert_eq!(acceptor.limits.borrow().max_connections, 20);
+    /// ```
+    pub fn add_watch<T>(self, init: T) -> ServiceProvider<Parent, HCons<WatchContainer<T>, Conts>> {
+        self._add::<WatchContainer<T>>(init)
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "actix-support")]
+
+use actix_web::{test, web, App};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct Counter {
+    hits: AtomicU32,
+}
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter {
+            hits: AtomicU32::new(0),
+        }
+    }
+}
+
+struct Greeting(&'static str);
+
+async fn index(counter: web::Data<Counter>, greeting: web::Data<Greeting>) -> String {
+    let hits = counter.hits.fetch_add(1, Ordering::SeqCst) + 1;
+    format!("{} {}", greeting.0, hits)
+}
+
+#[test]
+fn test_actix_data_shares_instances() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Arc<Counter>>()
+        .add_instance(Arc::new(Greeting("hello")));
+
+    let counter = sp.to_actix_data::<Counter, _>();
+    let configure = sp.actix_data::<(Greeting,), _>();
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(
+            App::new()
+                .app_data(counter)
+                .configure(configure)
+                .route("/", web::get().to(index)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "hello 1");
+    });
+
+    let counter: Arc<Counter> = sp.resolve();
+    assert_eq!(counter.hits.load(Ordering::SeqCst), 1);
+}
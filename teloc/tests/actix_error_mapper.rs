@@ -0,0 +1,59 @@
+#![cfg(feature = "actix-support")]
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use teloc::reexport::frunk::{HCons, HNil};
+use teloc::{DiActixHandler, ErrorMapper, ServiceProvider};
+
+struct User(String);
+
+struct Unauthorized;
+
+fn current_user(HCons { head: req, .. }: HCons<HttpRequest, HNil>) -> Result<User, Unauthorized> {
+    req.headers()
+        .get("user")
+        .and_then(|user| user.to_str().ok())
+        .map(|user| User(user.to_string()))
+        .ok_or(Unauthorized)
+}
+
+async fn index(user: User, path: web::Path<String>) -> String {
+    format!("{} {}", user.0, path.into_inner())
+}
+
+#[test]
+fn test_error_mapper() {
+    let sp = Arc::new(
+        ServiceProvider::new().add_instance::<Arc<dyn ErrorMapper<Unauthorized>>>(Arc::new(
+            |_: Unauthorized| HttpResponse::Unauthorized().body("who are you?"),
+        )),
+    );
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(App::new().route(
+            "/{page}",
+            web::get().to(DiActixHandler::new(
+                sp,
+                |s| s.add_transient_factory(current_user),
+                |scope, path| {
+                    Box::pin(async move {
+                        let user = scope.resolve_or_respond::<User, Unauthorized, _, _>()?;
+                        Ok::<_, HttpResponse>(index(user, path).await)
+                    })
+                },
+            )),
+        ))
+        .await;
+
+        let req = test::TestRequest::with_uri("/home")
+            .header("user", "alice")
+            .to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "alice home");
+
+        let req = test::TestRequest::with_uri("/home").to_request();
+        let res = test::call_service(&mut app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(test::read_body(res).await, "who are you?");
+    });
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "actix-support")]
+
+use actix_web::dev::RequestHead;
+use actix_web::{test, web, App};
+use std::sync::Arc;
+use teloc::{DiGuard, Resolver, ServiceProvider};
+
+struct FeatureFlags {
+    beta_users: Vec<&'static str>,
+}
+
+fn is_beta(head: &RequestHead, flags: &FeatureFlags) -> bool {
+    head.headers()
+        .get("user")
+        .and_then(|user| user.to_str().ok())
+        .is_some_and(|user| flags.beta_users.contains(&user))
+}
+
+#[test]
+fn test_guard() {
+    let sp = ServiceProvider::new().add_instance(FeatureFlags {
+        beta_users: vec!["alice"],
+    });
+    let sp = Arc::new(sp);
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(
+            App::new()
+                .route(
+                    "/",
+                    web::get()
+                        .guard(DiGuard::new(sp, |sp, head| is_beta(head, sp.resolve())))
+                        .to(|| async { "beta" }),
+                )
+                .route("/", web::get().to(|| async { "stable" })),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .header("user", "alice")
+            .to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "beta");
+        let req = test::TestRequest::get().header("user", "bob").to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "stable");
+    });
+}
@@ -0,0 +1,224 @@
+#![cfg(feature = "actix-support")]
+
+use actix_web::dev::Factory;
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use futures::stream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use teloc::{inject, DiActixHandler, Resolver, ScopeGuard, SendScopeGuard, ServiceProvider};
+
+struct Greeter {
+    greeting: String,
+}
+#[inject]
+impl Greeter {
+    fn new() -> Self {
+        Greeter {
+            greeting: "hello".into(),
+        }
+    }
+}
+
+struct Uppercase;
+#[inject]
+impl Uppercase {
+    fn new() -> Self {
+        Uppercase
+    }
+}
+
+async fn interleaved(
+    name: web::Path<String>,
+    greeter: Greeter,
+    req: &HttpRequest,
+    body: String,
+    _upper: Uppercase,
+) -> String {
+    format!("{} {} {} {}", greeter.greeting, name, req.method(), body)
+}
+
+#[test]
+fn test_interleaved_args() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_transient::<Greeter>()
+            .add_transient::<Uppercase>(),
+    );
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(App::new().route(
+            "/{name}",
+            web::post().to(DiActixHandler::new(
+                sp,
+                |s| s,
+                |scope, (name, body)| {
+                    Box::pin(interleaved(
+                        name,
+                        scope.resolve(),
+                        scope.resolve(),
+                        body,
+                        scope.resolve(),
+                    ))
+                },
+            )),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/alice")
+            .set_payload("body")
+            .to_request();
+        assert_eq!(
+            test::read_response(&mut app, req).await,
+            "hello alice POST body"
+        );
+    });
+}
+
+struct ScopeDropped(Arc<AtomicBool>);
+impl Drop for ScopeDropped {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+async fn streaming(
+    words: web::Json<Vec<String>>,
+    _dropped: &ScopeDropped,
+    guard: ScopeGuard,
+) -> HttpResponse {
+    let chunks = words.into_inner().into_iter().map(move |word| {
+        let _keep_scope = &guard;
+        Ok::<_, actix_web::Error>(web::Bytes::from(word))
+    });
+    HttpResponse::Ok().streaming(stream::iter(chunks))
+}
+
+#[test]
+fn test_scope_lives_until_stream_completes() {
+    let sp = Arc::new(ServiceProvider::new());
+    let dropped = Arc::new(AtomicBool::new(false));
+    let flag = dropped.clone();
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(App::new().route(
+            "/",
+            web::post().to(DiActixHandler::new(
+                sp,
+                move |s| s.add_instance(ScopeDropped(flag.clone())),
+                |scope, words| Box::pin(streaming(words, scope.resolve(), ScopeGuard::new(scope))),
+            )),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(&vec!["a", "b"])
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+        assert!(!dropped.load(Ordering::SeqCst));
+        assert_eq!(test::read_body(res).await, "ab");
+        assert!(dropped.load(Ordering::SeqCst));
+    });
+}
+
+async fn send_streaming(
+    words: web::Json<Vec<String>>,
+    _dropped: &ScopeDropped,
+    guard: SendScopeGuard,
+) -> HttpResponse {
+    let chunks = words.into_inner().into_iter().map(move |word| {
+        let _keep_scope = &guard;
+        Ok::<_, actix_web::Error>(web::Bytes::from(word))
+    });
+    HttpResponse::Ok().streaming(stream::iter(chunks))
+}
+
+#[test]
+fn test_send_scope_lives_until_stream_completes() {
+    let sp = Arc::new(ServiceProvider::new());
+    let dropped = Arc::new(AtomicBool::new(false));
+    let flag = dropped.clone();
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(App::new().route(
+            "/",
+            web::post().to(DiActixHandler::new_send(
+                sp,
+                move |s| s.add_instance(ScopeDropped(flag.clone())),
+                |scope, words| {
+                    Box::pin(send_streaming(
+                        words,
+                        scope.resolve(),
+                        SendScopeGuard::new(scope),
+                    ))
+                },
+            )),
+        ))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_json(&vec!["a", "b"])
+            .to_request();
+        let res = test::call_service(&mut app, req).await;
+        assert!(!dropped.load(Ordering::SeqCst));
+        assert_eq!(test::read_body(res).await, "ab");
+        assert!(dropped.load(Ordering::SeqCst));
+    });
+}
+
+async fn send_handler(greeter: &Greeter, body: String) -> String {
+    let greeting = greeter.greeting.clone();
+    // The value is held across an await point, so the future is `Send` only if it is.
+    let joined = async move { format!("{} {}", greeting, body) };
+    joined.await
+}
+
+#[test]
+fn test_send_handler() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Greeter>());
+    let handler = DiActixHandler::new_send(
+        sp,
+        |s| s,
+        |scope, body| Box::pin(send_handler(scope.resolve(), body)),
+    );
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let req = test::TestRequest::default().to_http_request();
+        let fut = Factory::call(&handler, (req, "body".to_string()));
+        assert_send(&fut);
+        assert_eq!(fut.await, "hello body");
+
+        let mut app =
+            test::init_service(App::new().route("/", web::post().to(handler.clone()))).await;
+        let req = test::TestRequest::post()
+            .uri("/")
+            .set_payload("body")
+            .to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "hello body");
+    });
+}
+
+async fn spawning_handler(greeter: &Greeter, body: String) -> String {
+    let greeting = greeter.greeting.clone();
+    tokio::spawn(async move { format!("{} {}", greeting, body) })
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_send_handler_is_spawned() {
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Greeter>());
+    let handler = DiActixHandler::new_send(
+        sp,
+        |s| s,
+        |scope, body| Box::pin(spawning_handler(scope.resolve(), body)),
+    );
+
+    let req = test::TestRequest::default().to_http_request();
+    let fut = Factory::call(&handler, (req, "body".to_string()));
+    assert_eq!(tokio::spawn(fut).await.unwrap(), "hello body");
+}
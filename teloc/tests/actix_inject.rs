@@ -0,0 +1,53 @@
+#![cfg(feature = "actix-support")]
+
+use actix_web::{test, web, App};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use teloc::{inject, Inject, Injector, Resolver, ServiceProvider};
+
+struct Counter {
+    hits: AtomicU32,
+}
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter {
+            hits: AtomicU32::new(0),
+        }
+    }
+}
+
+async fn index(name: web::Path<String>, counter: Inject<Arc<Counter>>) -> String {
+    let count = counter.hits.fetch_add(1, Ordering::SeqCst) + 1;
+    format!("{} {}", name, count)
+}
+
+#[test]
+fn test_inject() {
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Counter>>());
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Injector::<Arc<Counter>>::new(sp, |sp| sp.resolve()))
+                .route("/{name}", web::get().to(index)),
+        )
+        .await;
+
+        let req = test::TestRequest::with_uri("/alice").to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "alice 1");
+        let req = test::TestRequest::with_uri("/bob").to_request();
+        assert_eq!(test::read_response(&mut app, req).await, "bob 2");
+    });
+}
+
+#[test]
+fn test_missing_injector() {
+    actix_web::rt::System::new("test").block_on(async {
+        let mut app = test::init_service(App::new().route("/{name}", web::get().to(index))).await;
+
+        let req = test::TestRequest::with_uri("/alice").to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), 500);
+    });
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "actix-support")]
+
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{HeaderName, HeaderValue};
+use actix_web::{test, web, App, Error};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use teloc::{inject, DiTransform, Resolver, ServiceProvider};
+
+struct Ids(AtomicU32);
+
+struct RequestId {
+    id: u32,
+}
+#[inject]
+impl RequestId {
+    fn new(ids: Arc<Ids>) -> Self {
+        RequestId {
+            id: ids.0.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+}
+
+struct SetRequestIdService<S> {
+    id: u32,
+    service: S,
+}
+
+impl<S> Transform<S> for RequestId
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error>,
+    S::Future: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Transform = SetRequestIdService<S>;
+    type InitError = ();
+    type Future = Ready<Result<SetRequestIdService<S>, ()>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(SetRequestIdService {
+            id: self.id,
+            service,
+        }))
+    }
+}
+
+impl<S> Service for SetRequestIdService<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error>,
+    S::Future: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<ServiceResponse, Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let id = self.id;
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let mut res = fut.await?;
+            res.headers_mut()
+                .insert(HeaderName::from_static("request-id"), HeaderValue::from(id));
+            Ok(res)
+        })
+    }
+}
+
+async fn request_ids<S, B>(app: &mut S) -> Vec<String>
+where
+    S: Service<Request = actix_http::Request, Response = ServiceResponse<B>, Error = Error>,
+{
+    let mut ids = vec![];
+    for _ in 0..2 {
+        let res = test::call_service(app, test::TestRequest::get().to_request()).await;
+        let id = res.headers().get("request-id").unwrap();
+        ids.push(id.to_str().unwrap().to_string());
+    }
+    ids
+}
+
+#[test]
+fn test_per_app() {
+    let sp = ServiceProvider::new()
+        .add_instance(Arc::new(Ids(AtomicU32::new(0))))
+        .add_transient::<RequestId>();
+    let sp = Arc::new(sp);
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(
+            App::new()
+                .wrap(DiTransform::<RequestId, _>::new(sp, |sp| sp.resolve()))
+                .route("/", web::get().to(|| async { "Hello!" })),
+        )
+        .await;
+
+        assert_eq!(request_ids(&mut app).await, ["0", "0"]);
+    });
+}
+
+#[test]
+fn test_per_request() {
+    let sp = ServiceProvider::new()
+        .add_instance(Arc::new(Ids(AtomicU32::new(0))))
+        .add_transient::<RequestId>();
+    let sp = Arc::new(sp);
+
+    actix_web::rt::System::new("test").block_on(async move {
+        let mut app = test::init_service(
+            App::new()
+                .wrap(DiTransform::<RequestId, _>::per_request(sp, |sp| {
+                    sp.resolve()
+                }))
+                .route("/", web::get().to(|| async { "Hello!" })),
+        )
+        .await;
+
+        assert_eq!(request_ids(&mut app).await, ["0", "1"]);
+    });
+}
@@ -0,0 +1,53 @@
+use std::rc::Rc;
+use teloc::*;
+use uuid::Uuid;
+
+#[derive(Dependency)]
+#[dependency(lifetime = "singleton")]
+struct Counter {
+    #[init(Uuid::new_v4())]
+    id: Id,
+}
+
+struct Id(Uuid);
+impl Id {
+    fn init(id: Uuid) -> Self {
+        Id(id)
+    }
+}
+
+#[derive(Dependency)]
+#[dependency(lifetime = "transient")]
+struct Request<'a> {
+    counter: &'a Counter,
+    #[init(Uuid::new_v4())]
+    id: Id,
+}
+
+#[derive(Dependency)]
+#[dependency(ctor = "new", lifetime = "singleton")]
+struct Shared(Rc<String>);
+impl Shared {
+    fn new(name: Rc<String>) -> Self {
+        Shared(name)
+    }
+}
+
+#[test]
+fn test_add_auto() {
+    let sp = ServiceProvider::new()
+        .add_instance(Rc::new("shared".to_string()))
+        .add_auto::<Counter>()
+        .add_auto::<Request>()
+        .add_auto::<Shared>();
+
+    let r1: Request = sp.resolve();
+    let r2: Request = sp.resolve();
+    assert_eq!(r1.counter.id.0, r2.counter.id.0);
+    assert_ne!(r1.id.0, r2.id.0);
+
+    let s1: &Shared = sp.resolve();
+    let s2: &Shared = sp.resolve();
+    assert!(std::ptr::eq(s1, s2));
+    assert_eq!(s1.0.as_str(), "shared");
+}
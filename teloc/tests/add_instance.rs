@@ -1,5 +1,7 @@
+use std::net::{Ipv4Addr, SocketAddr};
 use std::rc::Rc;
-use teloc::{Resolver, ServiceProvider};
+use std::time::Duration;
+use teloc::{Dependency, Resolver, ServiceProvider};
 
 struct ConstService;
 
@@ -14,3 +16,24 @@ fn test() {
 
     assert!(Rc::ptr_eq(&first, &second));
 }
+
+#[derive(Dependency)]
+struct Server {
+    addr: SocketAddr,
+    workers: usize,
+    timeout: Duration,
+}
+
+#[test]
+fn test_copy_values() {
+    let container = ServiceProvider::new()
+        .add_instance(SocketAddr::from((Ipv4Addr::LOCALHOST, 8080)))
+        .add_instance(4usize)
+        .add_instance(Duration::from_secs(30))
+        .add_transient::<Server>();
+
+    let server: Server = container.fork().resolve();
+    assert_eq!(server.addr.port(), 8080);
+    assert_eq!(server.workers, 4);
+    assert_eq!(server.timeout, Duration::from_secs(30));
+}
@@ -0,0 +1,97 @@
+use teloc::*;
+
+struct Route {
+    path: &'static str,
+}
+
+trait Validator {
+    fn validate(&self, input: &str) -> bool;
+}
+
+struct NotEmpty;
+impl Validator for NotEmpty {
+    fn validate(&self, input: &str) -> bool {
+        !input.is_empty()
+    }
+}
+
+struct MaxLen(usize);
+impl Validator for MaxLen {
+    fn validate(&self, input: &str) -> bool {
+        input.len() <= self.0
+    }
+}
+
+struct Form<'a> {
+    validators: &'a [Box<dyn Validator>],
+}
+#[inject]
+impl<'a> Form<'a> {
+    fn new(validators: &'a [Box<dyn Validator>]) -> Self {
+        Form { validators }
+    }
+}
+impl Form<'_> {
+    fn is_valid(&self, input: &str) -> bool {
+        self.validators.iter().all(|v| v.validate(input))
+    }
+}
+
+fn paths(routes: &[Route]) -> Vec<&'static str> {
+    routes.iter().map(|r| r.path).collect()
+}
+
+fn admin_routes<P, C, I>(sp: ServiceProvider<P, C>) -> ServiceProvider<P, C>
+where
+    C: Selector<InstancesContainer<Route>, I>,
+{
+    sp.extend_instances(vec![
+        Route { path: "/admin" },
+        Route {
+            path: "/admin/users",
+        },
+    ])
+}
+
+#[test]
+fn test_resolve_slice() {
+    let sp = ServiceProvider::new()
+        .add_instances(vec![
+            Box::new(NotEmpty) as Box<dyn Validator>,
+            Box::new(MaxLen(5)),
+        ])
+        .add_transient::<Form>();
+
+    let form: Form = sp.resolve();
+    assert!(form.is_valid("abc"));
+    assert!(!form.is_valid(""));
+    assert!(!form.is_valid("abcdef"));
+}
+
+#[test]
+fn test_extend_instances() {
+    let sp = ServiceProvider::new()
+        .add_instances(vec![Route { path: "/" }])
+        .add_instance(42u32);
+    let sp = admin_routes(sp).extend_instances(Some(Route { path: "/about" }));
+
+    let routes: &[Route] = sp.resolve();
+    assert_eq!(paths(routes), ["/", "/admin", "/admin/users", "/about"]);
+}
+
+#[test]
+fn test_empty_instances() {
+    let sp = ServiceProvider::new().add_instances(Vec::<Route>::new());
+
+    let routes: &[Route] = sp.resolve();
+    assert!(routes.is_empty());
+}
+
+#[test]
+fn test_instances_from_parent() {
+    let sp = ServiceProvider::new().add_instances(vec![Route { path: "/" }]);
+    let scope = sp.fork();
+
+    let routes: &[Route] = scope.resolve();
+    assert_eq!(paths(routes), ["/"]);
+}
@@ -40,3 +40,79 @@ fn test() {
     assert_eq!(schema.b.service.data, 1);
     assert_eq!(schema.b.service.data2, 5);
 }
+
+trait Greeter {
+    fn greet(&self) -> String;
+}
+
+struct English {
+    name: &'static str,
+}
+impl Greeter for English {
+    fn greet(&self) -> String {
+        format!("Hello, {}!", self.name)
+    }
+}
+#[teloc::inject]
+impl English {
+    fn new() -> Self {
+        English { name: "world" }
+    }
+}
+
+#[derive(Dependency)]
+struct Greeting {
+    greeter: Box<dyn Greeter>,
+}
+
+#[test]
+fn test_mapped() {
+    let container = ServiceProvider::new()
+        .add_transient_mapped::<English, Box<dyn Greeter>>(|english| {
+            Box::new(English {
+                name: english.name.trim_end_matches('d'),
+            })
+        })
+        .add_transient::<Greeting>();
+    let greeting: Greeting = container.resolve();
+    assert_eq!(greeting.greeter.greet(), "Hello, worl!");
+}
+
+struct Settings {
+    timeout: u32,
+}
+
+struct Timeout(u32);
+impl Timeout {
+    pub fn init(secs: u32) -> Self {
+        Timeout(secs)
+    }
+}
+
+struct Deadline(u32);
+impl Deadline {
+    pub fn init(timeout: &Timeout, extra: u32) -> Self {
+        Deadline(timeout.0 + extra)
+    }
+}
+
+#[derive(Dependency)]
+struct Request<'a> {
+    #[init(settings.timeout)]
+    timeout: Timeout,
+    #[init(&timeout, 5)]
+    deadline: Deadline,
+    settings: &'a Settings,
+}
+
+#[test]
+fn test_init_with_siblings() {
+    let settings = Settings { timeout: 30 };
+    let sp = ServiceProvider::new()
+        .add_instance(&settings)
+        .add_transient::<Request>();
+    let request: Request = sp.resolve();
+    assert_eq!(request.timeout.0, 30);
+    assert_eq!(request.deadline.0, 35);
+    assert_eq!(request.settings.timeout, 30);
+}
@@ -0,0 +1,44 @@
+use std::rc::Rc;
+use teloc::*;
+
+#[derive(Dependency)]
+#[dependency(lifetime = "singleton")]
+struct Config;
+
+#[derive(Dependency)]
+#[dependency(lifetime = "transient")]
+struct Repo<'a> {
+    config: &'a Config,
+}
+
+#[derive(Dependency)]
+struct Service<'a> {
+    repo: Repo<'a>,
+    cache: Rc<Cache>,
+}
+
+#[derive(Dependency)]
+struct Cache;
+
+#[test]
+fn test_add_types() {
+    let sp = add_types!(
+        ServiceProvider::new();
+        Config,
+        Repo,
+        singleton Rc<Cache>,
+        transient Service,
+    );
+
+    let s1: Service = sp.resolve();
+    let s2: Service = sp.resolve();
+    let config: &Config = sp.resolve();
+    assert!(std::ptr::eq(s1.repo.config, config));
+    assert!(Rc::ptr_eq(&s1.cache, &s2.cache));
+}
+
+#[test]
+fn test_add_types_empty() {
+    let sp = add_types!(ServiceProvider::new(););
+    let _: ServiceProvider<EmptyServiceProvider, HNil> = sp;
+}
@@ -0,0 +1,112 @@
+use futures::executor::block_on;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use teloc::{inject, AsyncResolver, ServiceProvider};
+
+#[derive(Default)]
+struct Pool {
+    acquired: AtomicU32,
+}
+impl Pool {
+    async fn connect(&self) -> u32 {
+        self.acquired.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+struct Conn<'a> {
+    id: u32,
+    pool: &'a Pool,
+}
+
+struct Settings {
+    retries: u8,
+}
+#[inject]
+impl Settings {
+    fn new() -> Self {
+        Settings { retries: 3 }
+    }
+}
+
+#[test]
+fn test_new_instance_per_resolve() {
+    let sp = ServiceProvider::new()
+        .add_instance(Pool::default())
+        .add_transient_factory_async(|(pool,): (&Pool,)| async move {
+            Conn {
+                id: pool.connect().await,
+                pool,
+            }
+        });
+
+    block_on(async {
+        let first: Conn = sp.resolve_async().await;
+        let second: Conn = sp.resolve_async().await;
+        assert_eq!(first.id, 0);
+        assert_eq!(second.id, 1);
+        assert_eq!(first.pool.acquired.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn test_factory_runs_only_when_awaited() {
+    let sp = ServiceProvider::new()
+        .add_instance(Pool::default())
+        .add_transient_factory_async(|(pool,): (&Pool,)| async move { pool.connect().await });
+
+    let pending = AsyncResolver::<u32, _>::resolve_async(&sp);
+    let pool: &Pool = teloc::Resolver::resolve(&sp);
+    assert_eq!(pool.acquired.load(Ordering::SeqCst), 0);
+
+    assert_eq!(block_on(pending), 0);
+    assert_eq!(pool.acquired.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_sync_services_are_ready() {
+    let sp = ServiceProvider::new()
+        .add_transient::<Settings>()
+        .add_instance(Rc::new(10u8));
+
+    block_on(async {
+        let settings: Settings = sp.resolve_async().await;
+        let number: Rc<u8> = sp.resolve_async().await;
+        assert_eq!(settings.retries, 3);
+        assert_eq!(*number, 10);
+    });
+}
+
+#[test]
+fn test_from_forked_scope() {
+    let sp = ServiceProvider::new()
+        .add_transient_factory_async(|(id,): (Rc<u32>,)| async move { format!("session-{}", id) });
+    let scope = sp.fork().add_instance(Rc::new(7u32));
+
+    let session: String = block_on(scope.resolve_async());
+    assert_eq!(session, "session-7");
+}
+
+#[test]
+fn test_lazy_resolves_on_poll() {
+    let sp = ServiceProvider::new()
+        .add_instance(Pool::default())
+        .add_transient_factory_async(|(pool,): (&Pool,)| async move { pool.connect().await });
+    let pool: &Pool = teloc::Resolver::resolve(&sp);
+
+    let first = sp.resolve_lazy::<u32, _>();
+    let second = sp.resolve_lazy::<u32, _>();
+    assert_eq!(pool.acquired.load(Ordering::SeqCst), 0);
+
+    // The second future is polled first, so it gets the first connection.
+    let (second, first) = block_on(async { futures::join!(second, first) });
+    assert_eq!((first, second), (1, 0));
+    assert_eq!(pool.acquired.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_lazy_sync_service() {
+    let sp = ServiceProvider::new().add_transient::<Settings>();
+
+    let settings: Settings = block_on(sp.resolve_lazy());
+    assert_eq!(settings.retries, 3);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "async-graphql-support")]
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct DbSession {
+    user: String,
+    queries: AtomicUsize,
+}
+
+struct NameLoader {
+    session: Arc<DbSession>,
+}
+#[inject]
+impl NameLoader {
+    fn new(session: Arc<DbSession>) -> Self {
+        NameLoader { session }
+    }
+}
+
+impl Loader<u32> for NameLoader {
+    type Value = String;
+    type Error = Arc<String>;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, String>, Arc<String>> {
+        self.session.queries.fetch_add(1, Ordering::SeqCst);
+        Ok(keys
+            .iter()
+            .map(|key| (*key, format!("{}:{}", self.session.user, key)))
+            .collect())
+    }
+}
+
+fn session(user: &str) -> Arc<DbSession> {
+    Arc::new(DbSession {
+        user: user.to_string(),
+        queries: AtomicUsize::new(0),
+    })
+}
+
+#[tokio::test]
+async fn test_data_loader_per_scope() {
+    let sp = ServiceProvider::new().add_transient::<NameLoader>();
+
+    let alice = session("alice");
+    let first = sp
+        .fork()
+        .add_instance(alice.clone())
+        .add_data_loader::<NameLoader, _, _>(tokio::spawn);
+    let bob = session("bob");
+    let second = sp
+        .fork()
+        .add_instance(bob.clone())
+        .add_data_loader::<NameLoader, _, _>(tokio::spawn);
+
+    let loader: Arc<DataLoader<NameLoader>> = first.resolve();
+    let same: Arc<DataLoader<NameLoader>> = first.resolve();
+    assert!(Arc::ptr_eq(&loader, &same));
+    let other: Arc<DataLoader<NameLoader>> = second.resolve();
+    assert!(!Arc::ptr_eq(&loader, &other));
+
+    assert_eq!(loader.load_one(1).await.unwrap().unwrap(), "alice:1");
+    assert_eq!(other.load_one(1).await.unwrap().unwrap(), "bob:1");
+    assert_eq!(alice.queries.load(Ordering::SeqCst), 1);
+    assert_eq!(bob.queries.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_data_loader_scope_is_send() {
+    fn assert_send<T: Send + Sync>(_: &T) {}
+
+    let sp = Arc::new(ServiceProvider::new().add_transient::<NameLoader>());
+    let scope = sp
+        .fork_arc()
+        .add_instance(session("alice"))
+        .add_data_loader::<NameLoader, _, _>(tokio::spawn);
+    assert_send(&scope);
+
+    let loader: Arc<DataLoader<NameLoader>> = scope.resolve();
+    let names = loader.load_many([1, 2]).await.unwrap();
+    assert_eq!(names.len(), 2);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "axum-support")]
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::Request;
+use axum::routing::get;
+use axum::Router;
+use std::sync::Arc;
+use teloc::axum_support::AppState;
+use teloc::{inject, ServiceProvider};
+use tower::ServiceExt;
+
+struct Repository {
+    name: String,
+}
+#[inject]
+impl Repository {
+    fn new() -> Self {
+        Repository {
+            name: "users".into(),
+        }
+    }
+}
+
+struct Config {
+    prefix: String,
+}
+
+#[derive(Clone, AppState)]
+struct MyState {
+    repository: Arc<Repository>,
+    config: Arc<Config>,
+}
+
+async fn index(
+    State(repository): State<Arc<Repository>>,
+    State(config): State<Arc<Config>>,
+) -> String {
+    format!("{}{}", config.prefix, repository.name)
+}
+
+#[tokio::test]
+async fn test_state_from_provider() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Arc<Repository>>()
+        .add_instance(Arc::new(Config {
+            prefix: "/api/".into(),
+        }));
+    let state = MyState::from_provider(&sp);
+
+    let app = Router::new().route("/", get(index)).with_state(state);
+    let res = app
+        .oneshot(Request::get("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(bytes, "/api/users");
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "axum-support")]
+
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use teloc::axum_support::{Inject, ProvideLayer};
+use teloc::{inject, Resolver, ServiceProvider};
+use tower::ServiceExt;
+
+struct Counter(AtomicUsize);
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter(AtomicUsize::new(0))
+    }
+}
+
+struct RequestId(usize);
+
+struct Controller {
+    counter: Arc<Counter>,
+    id: Arc<RequestId>,
+    method: Method,
+}
+#[inject]
+impl Controller {
+    fn new(counter: Arc<Counter>, id: Arc<RequestId>, method: Method) -> Self {
+        Controller {
+            counter,
+            id,
+            method,
+        }
+    }
+}
+
+async fn index(first: Inject<Controller>, second: Inject<Controller>) -> String {
+    format!(
+        "{} {} {} {}",
+        first.method,
+        first.id.0,
+        Arc::ptr_eq(&first.id, &second.id),
+        Arc::ptr_eq(&first.counter, &second.counter)
+    )
+}
+
+async fn not_registered(_: Inject<Arc<Counter>>) -> &'static str {
+    "unreachable"
+}
+
+fn app() -> Router {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Counter>>()
+            .add_transient::<Controller>(),
+    );
+    let counter = sp.clone();
+
+    Router::new()
+        .route("/", get(index))
+        .route("/missing", get(not_registered))
+        .layer(
+            ProvideLayer::new(sp, move |scope, parts| {
+                let counter: Arc<Counter> = teloc::Resolver::resolve(&*counter);
+                let id = counter.0.fetch_add(1, Ordering::SeqCst);
+                scope
+                    .add_instance(Arc::new(RequestId(id)))
+                    .add_instance(parts.method.clone())
+            })
+            .inject::<Controller, _>(|scope| scope.resolve()),
+        )
+}
+
+async fn body(app: Router, uri: &str) -> (StatusCode, String) {
+    let res = app
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = res.status();
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_inject_from_request_scope() {
+    let app = app();
+    assert_eq!(
+        body(app.clone(), "/").await,
+        (StatusCode::OK, "GET 0 true true".to_string())
+    );
+    assert_eq!(
+        body(app, "/").await,
+        (StatusCode::OK, "GET 1 true true".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_not_registered_type() {
+    let (status, message) = body(app(), "/missing").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(message.contains("is not registered in the `ProvideLayer`"));
+}
@@ -0,0 +1,43 @@
+use std::rc::Rc;
+use teloc::*;
+
+struct Config {
+    port: u16,
+}
+
+struct Repo<'a> {
+    config: &'a Config,
+}
+#[inject]
+impl<'a> Repo<'a> {
+    fn new(config: &'a Config) -> Self {
+        Repo { config }
+    }
+}
+
+#[derive(Dependency)]
+struct Cache;
+
+type AppSp = ServiceProvider<
+    EmptyServiceProvider,
+    HCons<
+        SingletonContainer<Rc<Cache>>,
+        HCons<TransientContainer<Repo<'static>>, HCons<InstanceContainer<Config>, HNil>>,
+    >,
+>;
+
+check_provider!(AppSp: Repo, &Config, Rc<Cache>, (Repo, Rc<Cache>));
+
+const _: () = check_provider::<Box<Repo>, AppSp, _>();
+
+#[test]
+fn test_checked_provider_resolves() {
+    let sp: AppSp = ServiceProvider::new()
+        .add_instance(Config { port: 80 })
+        .add_transient::<Repo>()
+        .add_singleton::<Rc<Cache>>();
+    check_provider::<Repo, AppSp, _>();
+
+    let repo: Repo = sp.resolve();
+    assert_eq!(repo.config.port, 80);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "clap-support")]
+
+use clap::Parser;
+use teloc::{inject, Resolver, ServiceProvider};
+
+#[derive(Parser)]
+struct Cli {
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+}
+
+struct Server<'a> {
+    cli: &'a Cli,
+}
+#[inject]
+impl<'a> Server<'a> {
+    fn new(cli: &'a Cli) -> Self {
+        Self { cli }
+    }
+}
+
+#[test]
+fn test_args() {
+    let sp = ServiceProvider::new()
+        .try_add_cli_args_from::<Cli, _, _>(vec!["app", "--port", "3000"])
+        .unwrap()
+        .add_transient::<Server>();
+
+    let server: Server = sp.resolve();
+    assert_eq!(server.cli.port, 3000);
+}
+
+#[test]
+fn test_invalid_args() {
+    let res = ServiceProvider::new().try_add_cli_args_from::<Cli, _, _>(vec!["app", "--port", "x"]);
+    assert!(res.is_err());
+}
+
+#[derive(Parser)]
+struct Tool {
+    #[command(subcommand)]
+    command: ToolCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum ToolCommand {
+    Migrate(MigrateArgs),
+    Serve,
+}
+
+#[derive(clap::Args, Clone)]
+struct MigrateArgs {
+    #[arg(long)]
+    steps: u32,
+}
+
+struct Migrator<'a> {
+    args: &'a Option<MigrateArgs>,
+}
+#[inject]
+impl<'a> Migrator<'a> {
+    fn new(args: &'a Option<MigrateArgs>) -> Self {
+        Self { args }
+    }
+}
+
+fn select_migrate(tool: &Tool) -> Option<MigrateArgs> {
+    match &tool.command {
+        ToolCommand::Migrate(args) => Some(args.clone()),
+        ToolCommand::Serve => None,
+    }
+}
+
+#[test]
+fn test_subcommand_args() {
+    let sp = ServiceProvider::new()
+        .try_add_cli_subcommand_from::<Tool, _, _, _, _>(
+            vec!["app", "migrate", "--steps", "3"],
+            select_migrate,
+        )
+        .unwrap()
+        .add_transient::<Migrator>();
+
+    let migrator: Migrator = sp.resolve();
+    assert_eq!(migrator.args.as_ref().map(|args| args.steps), Some(3));
+
+    let sp = ServiceProvider::new()
+        .try_add_cli_subcommand_from::<Tool, _, _, _, _>(vec!["app", "serve"], select_migrate)
+        .unwrap();
+    let tool: &Tool = sp.resolve();
+    let args: &Option<MigrateArgs> = sp.resolve();
+    assert!(matches!(tool.command, ToolCommand::Serve));
+    assert!(args.is_none());
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "std")]
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use teloc::time::{Clock, TestClock};
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct Stamper {
+    clock: Arc<dyn Clock>,
+}
+#[inject]
+impl Stamper {
+    fn new(clock: Arc<dyn Clock>) -> Self {
+        Stamper { clock }
+    }
+}
+impl Stamper {
+    fn stamp(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+#[test]
+fn test_system_clock() {
+    let sp = ServiceProvider::new()
+        .with_system_clock()
+        .add_transient::<Stamper>();
+
+    let before = SystemTime::now();
+    let stamper: Stamper = sp.resolve();
+    assert!(stamper.clock.now() >= before);
+}
+
+#[test]
+fn test_test_clock_is_shared() {
+    let sp = ServiceProvider::new()
+        .with_test_clock()
+        .add_transient::<Stamper>();
+
+    let first: Stamper = sp.resolve();
+    let second: Stamper = sp.resolve();
+    assert_eq!(first.stamp(), 0);
+
+    let clock: Arc<TestClock> = sp.resolve();
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(first.stamp(), 30);
+    assert_eq!(second.stamp(), 30);
+
+    clock.set(UNIX_EPOCH + Duration::from_secs(10));
+    assert_eq!(first.stamp(), 10);
+}
+
+#[test]
+fn test_test_clock_in_fork() {
+    let sp = ServiceProvider::new().add_transient::<Stamper>();
+    let scope = sp.fork().with_test_clock();
+
+    let stamper: Stamper = scope.resolve();
+    assert_eq!(stamper.stamp(), 0);
+}
@@ -0,0 +1,87 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use teloc::*;
+
+trait Mailer {
+    fn name(&self) -> String;
+}
+
+struct SmtpMailer {
+    host: String,
+}
+impl Mailer for SmtpMailer {
+    fn name(&self) -> String {
+        self.host.clone()
+    }
+}
+
+struct FakeMailer;
+impl Mailer for FakeMailer {
+    fn name(&self) -> String {
+        "fake".into()
+    }
+}
+
+struct Connection;
+#[inject]
+impl Connection {
+    fn new(created: &Cell<u32>) -> Self {
+        created.set(created.get() + 1);
+        Connection
+    }
+}
+
+#[derive(Dependency)]
+struct Notifier {
+    mailer: Rc<dyn Mailer>,
+}
+
+#[test]
+fn test_add_singleton_if() {
+    let created = Cell::new(0);
+    let provider = |use_fake: bool| {
+        ServiceProvider::new()
+            .add_instance(&created)
+            .add_instance("smtp".to_string())
+            .add_transient::<Connection>()
+            .add_singleton_if_unboxed(
+                move || use_fake,
+                |()| Rc::new(FakeMailer) as Rc<dyn Mailer>,
+                |(host, _): (&String, Connection)| {
+                    Rc::new(SmtpMailer { host: host.clone() }) as Rc<dyn Mailer>
+                },
+            )
+            .add_transient::<Notifier>()
+    };
+
+    let sp = provider(true);
+    let notifier: Notifier = sp.fork().resolve();
+    assert_eq!(notifier.mailer.name(), "fake");
+    assert_eq!(created.get(), 0);
+
+    let sp = provider(false);
+    sp.warm_up();
+    let first: Notifier = sp.resolve();
+    let second: Rc<dyn Mailer> = sp.resolve();
+    assert_eq!(first.mailer.name(), "smtp");
+    assert!(Rc::ptr_eq(&first.mailer, &second));
+    assert_eq!(created.get(), 1);
+}
+
+#[test]
+fn test_add_singleton_if_boxed() {
+    let sp = ServiceProvider::new()
+        .add_instance(Rc::new("smtp".to_string()))
+        .add_singleton_if(
+            || false,
+            |()| Rc::new(FakeMailer) as Rc<dyn Mailer>,
+            |(host,): (Rc<String>,)| {
+                Rc::new(SmtpMailer {
+                    host: host.to_string(),
+                }) as Rc<dyn Mailer>
+            },
+        );
+
+    let mailer: Rc<dyn Mailer> = sp.resolve();
+    assert_eq!(mailer.name(), "smtp");
+}
@@ -0,0 +1,223 @@
+#![cfg(feature = "config")]
+
+use serde::Deserialize;
+use serde_json::json;
+use teloc::config::{ConfigError, ConfigSection, ConfigTree, Section};
+use teloc::*;
+
+struct Db {
+    url: &'static str,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Mode {
+    Fast,
+    Safe,
+}
+
+#[derive(Dependency)]
+struct Server<'a> {
+    db: &'a Db,
+    #[config("server.port")]
+    port: u16,
+    #[config("server.mode")]
+    mode: Mode,
+    #[config("server.hosts.1")]
+    host: String,
+}
+
+struct Timeout(u64);
+impl Timeout {
+    fn init(secs: u64) -> Self {
+        Timeout(secs * 1000)
+    }
+}
+
+#[derive(Dependency)]
+struct Client {
+    #[config("client.timeout")]
+    secs: u64,
+    #[init(secs)]
+    timeout: Timeout,
+}
+
+#[derive(Dependency)]
+struct Broken {
+    #[config("server.missing")]
+    _port: u16,
+}
+
+fn config() -> serde_json::Value {
+    json!({
+        "server": { "port": 8080, "mode": "Safe", "hosts": ["a.local", "b.local"] },
+        "client": { "timeout": 3 },
+    })
+}
+
+#[test]
+fn test_config_fields() {
+    let sp = ServiceProvider::new()
+        .add_instance(Db { url: "postgres://" })
+        .add_config(config())
+        .add_transient::<Server>()
+        .add_transient::<Client>();
+
+    let server: Server = sp.resolve();
+    assert_eq!(server.db.url, "postgres://");
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.mode, Mode::Safe);
+    assert_ne!(server.mode, Mode::Fast);
+    assert_eq!(server.host, "b.local");
+
+    let client: Client = sp.resolve();
+    assert_eq!(client.secs, 3);
+    assert_eq!(client.timeout.0, 3000);
+}
+
+#[test]
+#[should_panic(expected = "missing config key `server.missing`")]
+fn test_missing_key_panics() {
+    let sp = ServiceProvider::new()
+        .add_config(config())
+        .add_transient::<Broken>();
+    let _: Broken = sp.resolve();
+}
+
+#[test]
+fn test_config_tree_errors() {
+    let tree = ConfigTree::new(config());
+    assert_eq!(tree.get::<u16>("server.port").unwrap(), 8080);
+    assert!(tree.find("").is_some());
+
+    let err = tree.get::<u16>("server.hosts.5").unwrap_err();
+    assert!(matches!(err, ConfigError::Missing { .. }));
+    assert_eq!(err.to_string(), "missing config key `server.hosts.5`");
+
+    let err = tree.get::<u16>("server.mode").unwrap_err();
+    assert!(matches!(err, ConfigError::Invalid { .. }));
+    assert!(err
+        .to_string()
+        .starts_with("invalid config key `server.mode`: "));
+}
+
+#[derive(Deserialize, Debug)]
+struct ServerConfig {
+    port: u16,
+    hosts: Vec<String>,
+}
+impl ConfigSection for ServerConfig {
+    const KEY: &'static str = "server";
+}
+
+#[derive(Deserialize, Debug)]
+struct MetricsConfig {
+    _interval: u64,
+}
+impl ConfigSection for MetricsConfig {
+    const KEY: &'static str = "metrics";
+}
+
+#[derive(Deserialize, Debug)]
+struct ClientConfig {
+    _timeout: String,
+}
+impl ConfigSection for ClientConfig {
+    const KEY: &'static str = "client";
+}
+
+#[test]
+fn test_sections_are_cached() {
+    let sp = ServiceProvider::new().add_config(config());
+
+    let first: Section<ServerConfig> = sp.resolve();
+    let second: Section<ServerConfig> = sp.resolve();
+    assert_eq!(first.port, 8080);
+    assert_eq!(second.hosts, ["a.local", "b.local"]);
+    assert!(std::ptr::eq(&*first, &*second));
+}
+
+#[test]
+fn test_optional_section() {
+    let sp = ServiceProvider::new().add_config(config());
+
+    let metrics: Option<Section<MetricsConfig>> = sp.resolve();
+    assert!(metrics.is_none());
+    let server: Option<Section<ServerConfig>> = sp.resolve();
+    assert_eq!(server.unwrap().port, 8080);
+}
+
+#[test]
+#[should_panic(expected = "invalid config key `client`")]
+fn test_invalid_section_panics() {
+    let sp = ServiceProvider::new().add_config(config());
+    let _: Option<Section<ClientConfig>> = sp.resolve();
+}
+
+#[test]
+fn test_sections_from_fork() {
+    let sp = ServiceProvider::new().add_config(config());
+    let first: Section<ServerConfig> = sp.fork().resolve();
+    let second: Section<ServerConfig> = sp.fork().resolve();
+    assert!(std::ptr::eq(&*first, &*second));
+}
+
+impl Validate for ServerConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.port < 1024 {
+            return Ok(());
+        }
+        Err(format!("port {} is not privileged", self.port))
+    }
+}
+
+#[test]
+fn test_validate_section() {
+    let sp = ServiceProvider::new()
+        .add_config(config())
+        .add_validation::<Section<ServerConfig>>();
+
+    let err = match sp.try_warm_up() {
+        Err(StartupError::Validation(err)) => err,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    assert_eq!(err.failures()[0].1, "port 8080 is not privileged");
+}
+
+#[test]
+fn test_fallible_section() {
+    let sp = ServiceProvider::new().add_config(config());
+
+    let server: Result<Section<ServerConfig>, ConfigError> = sp.resolve();
+    assert_eq!(server.unwrap().port, 8080);
+    let client: Result<Section<ClientConfig>, ConfigError> = sp.resolve();
+    assert!(matches!(client, Err(ConfigError::Invalid { ref path, .. }) if path == "client"));
+    let metrics: Result<Section<MetricsConfig>, ConfigError> = sp.resolve();
+    assert!(matches!(metrics, Err(ConfigError::Missing { ref path }) if path == "metrics"));
+}
+
+impl Validate for MetricsConfig {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_validate_fallible_section() {
+    let sp = ServiceProvider::new()
+        .add_config(config())
+        .add_validation::<Result<Section<ServerConfig>, ConfigError>>()
+        .add_validation::<Result<Section<MetricsConfig>, ConfigError>>();
+
+    let err = match sp.try_warm_up() {
+        Err(StartupError::Validation(err)) => err,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    let messages: Vec<&str> = err.failures().iter().map(|(_, m)| m.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "port 8080 is not privileged",
+            "missing config key `metrics`"
+        ]
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(all(feature = "consumer", feature = "sync"))]
+
+use futures::executor::block_on;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use teloc::{inject, DiConsumer, Resolver, ServiceProvider};
+
+struct Delivery {
+    tag: u64,
+    body: String,
+}
+
+struct Metrics(AtomicUsize);
+#[inject]
+impl Metrics {
+    fn new() -> Self {
+        Metrics(AtomicUsize::new(0))
+    }
+}
+
+struct Attempt(u32);
+
+struct MessageContext<'a> {
+    tag: u64,
+    attempt: &'a Attempt,
+}
+#[inject]
+impl<'a> MessageContext<'a> {
+    fn new(delivery: &'a Delivery, attempt: &'a Attempt) -> Self {
+        MessageContext {
+            tag: delivery.tag,
+            attempt,
+        }
+    }
+}
+
+#[test]
+fn test_consumer_forks_scope_per_message() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Metrics>>()
+            .add_transient::<MessageContext>(),
+    );
+    let consumer = DiConsumer::new(
+        sp,
+        |scope| scope.add_instance(Attempt(1)),
+        |scope| {
+            let delivery: &Delivery = scope.resolve();
+            let metrics: Arc<Metrics> = scope.resolve();
+            let context: MessageContext = scope.resolve();
+            Box::pin(async move {
+                let count = metrics.0.fetch_add(1, Ordering::SeqCst) + 1;
+                format!(
+                    "{} #{} attempt {} ({})",
+                    delivery.body, context.tag, context.attempt.0, count
+                )
+            })
+        },
+    );
+
+    let first = consumer.consume(Delivery {
+        tag: 1,
+        body: "created".into(),
+    });
+    let second = consumer.consume(Delivery {
+        tag: 2,
+        body: "paid".into(),
+    });
+    // The handler is called when the future is polled, so the second message is handled first.
+    assert_eq!(block_on(second), "paid #2 attempt 1 (1)");
+    assert_eq!(block_on(first), "created #1 attempt 1 (2)");
+}
+
+#[tokio::test]
+async fn test_consumer_future_is_send() {
+    async fn handle(delivery: &Delivery, metrics: Arc<Metrics>) -> usize {
+        tokio::task::yield_now().await;
+        metrics.0.fetch_add(1, Ordering::SeqCst);
+        delivery.body.len()
+    }
+
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Metrics>>());
+    let consumer = DiConsumer::new(
+        sp,
+        |scope| scope,
+        |scope| Box::pin(handle(scope.resolve(), scope.resolve())),
+    );
+    let len = tokio::spawn(consumer.consume(Delivery {
+        tag: 1,
+        body: "shipped".into(),
+    }))
+    .await
+    .unwrap();
+    assert_eq!(len, 7);
+}
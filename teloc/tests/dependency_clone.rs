@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use teloc::*;
+
+struct Config {
+    name: &'static str,
+}
+
+struct Pool;
+#[inject]
+impl Pool {
+    fn new() -> Self {
+        Pool
+    }
+}
+
+#[derive(Clone, DependencyClone)]
+struct Handle<'a> {
+    config: &'a Config,
+    pool: Arc<Pool>,
+}
+#[inject]
+impl<'a> Handle<'a> {
+    fn new(config: &'a Config, pool: Arc<Pool>) -> Self {
+        Handle { config, pool }
+    }
+}
+
+#[derive(Clone, Copy, DependencyClone)]
+struct Limit<T: Copy>(T);
+
+#[derive(Dependency)]
+struct Service<'a> {
+    handle: Handle<'a>,
+    limit: Limit<u32>,
+}
+
+#[test]
+fn test_derive_dependency_clone() {
+    let sp = ServiceProvider::new()
+        .add_instance(Config { name: "app" })
+        .add_instance(Limit(10u32))
+        .add_singleton::<Arc<Pool>>()
+        .add_singleton::<Handle>()
+        .add_transient::<Service>();
+
+    let first: Service = sp.resolve();
+    let second: Handle = sp.resolve();
+    assert_eq!(first.handle.config.name, "app");
+    assert_eq!(first.limit.0, 10);
+    assert!(Arc::ptr_eq(&first.handle.pool, &second.pool));
+}
@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+use teloc::*;
+
+type Log = RefCell<Vec<&'static str>>;
+
+macro_rules! service {
+    ($name:ident) => {
+        struct $name;
+        #[inject]
+        impl $name {
+            fn new(log: &Log) -> Self {
+                log.borrow_mut().push(stringify!($name));
+                $name
+            }
+        }
+    };
+}
+
+service!(Migrations);
+service!(Cache);
+service!(Poller);
+service!(Listener);
+
+#[test]
+fn test_depends_on_chain() {
+    let log = Log::default();
+    let sp = ServiceProvider::new()
+        .add_instance(&log)
+        .add_singleton::<Listener>()
+        .add_singleton::<Poller>()
+        .add_singleton::<Cache>()
+        .add_singleton::<Migrations>()
+        .depends_on::<Listener, Poller>()
+        .depends_on::<Poller, Cache>()
+        .depends_on::<Cache, Migrations>();
+
+    sp.warm_up();
+    assert_eq!(*log.borrow(), ["Migrations", "Cache", "Poller", "Listener"]);
+}
+
+#[test]
+fn test_depends_on_in_scope() {
+    let log = Log::default();
+    let sp = ServiceProvider::new()
+        .add_instance(&log)
+        .add_singleton::<Migrations>();
+    let scope = sp
+        .fork()
+        .add_singleton::<Listener>()
+        .depends_on::<Listener, Migrations>();
+
+    scope.warm_up();
+    assert_eq!(*log.borrow(), ["Migrations", "Listener"]);
+}
+
+#[test]
+#[should_panic(
+    expected = "contradicting startup order: depends_on::Poller -> depends_on::Cache -> depends_on::Poller"
+)]
+fn test_contradicting_order() {
+    let log = Log::default();
+    let sp = ServiceProvider::new()
+        .add_instance(&log)
+        .add_singleton::<Cache>()
+        .add_singleton::<Poller>()
+        .depends_on::<Cache, Poller>()
+        .depends_on::<Poller, Cache>();
+
+    sp.warm_up();
+}
+
+#[test]
+fn test_contradicting_order_error() {
+    let log = Log::default();
+    let sp = ServiceProvider::new()
+        .add_instance(&log)
+        .add_singleton::<Cache>()
+        .add_singleton::<Poller>()
+        .add_singleton::<Migrations>()
+        .depends_on::<Poller, Migrations>()
+        .depends_on::<Cache, Poller>()
+        .depends_on::<Poller, Cache>();
+
+    let err = sp.try_warm_up().unwrap_err();
+    assert_eq!(
+        err,
+        StartupError::Cycle(vec![
+            "depends_on::Poller",
+            "depends_on::Cache",
+            "depends_on::Poller"
+        ])
+    );
+    assert!(log.borrow().is_empty(), "nothing must be initialized");
+}
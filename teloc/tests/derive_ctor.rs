@@ -0,0 +1,52 @@
+use std::rc::Rc;
+use teloc::*;
+
+struct Config {
+    retries: u8,
+}
+
+#[derive(Dependency)]
+#[dependency(ctor = "new")]
+struct Client<'a> {
+    retries: u8,
+    name: Rc<String>,
+    config: &'a Config,
+}
+impl<'a> Client<'a> {
+    fn new(retries: u8, name: Rc<String>, config: &'a Config) -> Self {
+        Client {
+            retries: retries.max(config.retries),
+            name,
+            config,
+        }
+    }
+}
+
+#[derive(Dependency)]
+#[dependency(ctor = "with_limit")]
+struct Limit(u32);
+impl Limit {
+    fn with_limit(limit: u32) -> Self {
+        Limit(limit * 2)
+    }
+}
+
+#[test]
+fn test_derive_with_ctor() {
+    let config = Config { retries: 3 };
+    let sp = ServiceProvider::new()
+        .add_instance(&config)
+        .add_instance(1u8)
+        .add_instance(Rc::new("client".to_string()))
+        .add_instance(5u32)
+        .add_transient::<Client>()
+        .add_transient::<Limit>();
+
+    let client: Client = sp.resolve();
+    assert_eq!(client.retries, 3);
+    assert_eq!(client.name.as_str(), "client");
+    assert_eq!(client.config.retries, 3);
+
+    let limit: Limit = sp.resolve();
+    assert_eq!(limit.0, 10);
+}
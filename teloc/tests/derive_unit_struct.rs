@@ -1,3 +1,6 @@
+// This test only checks that the `#[derive(Dependency)]` expansion compiles.
+#![allow(dead_code)]
+
 use teloc::Dependency;
 
 #[derive(Dependency)]
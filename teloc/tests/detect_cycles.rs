@@ -0,0 +1,33 @@
+use std::rc::Rc;
+use teloc::*;
+
+detect_cycles! {
+    #[derive(Dependency)]
+    struct Config;
+
+    struct Repo {
+        config: Rc<Config>,
+    }
+    #[inject]
+    impl Repo {
+        fn new(config: Rc<Config>) -> Self {
+            Repo { config }
+        }
+    }
+
+    #[derive(Dependency)]
+    struct Service<'a> {
+        repo: &'a Repo,
+        config: Rc<Config>,
+    }
+}
+
+#[test]
+fn test_acyclic_services_compile() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Rc<Config>>()
+        .add_singleton::<Repo>()
+        .add_transient::<Service>();
+    let service: Service = sp.resolve();
+    assert!(Rc::ptr_eq(&service.repo.config, &service.config));
+}
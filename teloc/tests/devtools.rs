@@ -0,0 +1,75 @@
+#![cfg(feature = "devtools")]
+
+use serde::{Deserialize, Serialize};
+use teloc::devtools::{ReplayError, ScopeRecording, REDACTED};
+use teloc::{inject, Resolver, ServiceProvider};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Tenant {
+    name: String,
+}
+
+struct Report<'a> {
+    tenant: &'a Tenant,
+}
+#[inject]
+impl<'a> Report<'a> {
+    fn new(tenant: &'a Tenant) -> Self {
+        Self { tenant }
+    }
+}
+
+#[test]
+fn test_replay() {
+    let mut recording = ScopeRecording::new();
+    recording
+        .record_header("authorization", "Bearer x")
+        .record_body(b"payload")
+        .record_instance(&Tenant {
+            name: "acme".into(),
+        })
+        .unwrap();
+    let saved = serde_json::to_vec(&recording).unwrap();
+    let recording: ScopeRecording = serde_json::from_slice(&saved).unwrap();
+
+    let sp = ServiceProvider::new().add_transient::<Report>();
+    let scope = sp
+        .fork()
+        .add_replayed_instance::<Tenant>(&recording)
+        .unwrap();
+    let report: Report = scope.resolve();
+
+    assert_eq!(report.tenant.name, "acme");
+    assert_eq!(
+        recording.headers(),
+        &[("authorization".to_string(), REDACTED.to_string())]
+    );
+    assert!(recording.matches_body(b"payload"));
+    assert!(!recording.matches_body(b"other"));
+}
+
+#[test]
+fn test_missing_instance() {
+    let recording = ScopeRecording::new();
+    let res = ServiceProvider::new().add_replayed_instance::<Tenant>(&recording);
+    assert!(matches!(res, Err(ReplayError::Missing(_))));
+}
+
+#[cfg(feature = "actix-support")]
+#[test]
+fn test_request_roundtrip() {
+    let req = actix_web::test::TestRequest::default()
+        .header("x-request-id", "42")
+        .header("Authorization", "Bearer x")
+        .header("cookie", "session=1")
+        .to_http_request();
+    let recording = ScopeRecording::from_request(&req);
+    let replayed = recording.to_test_request().to_http_request();
+
+    assert_eq!(replayed.headers().get("x-request-id").unwrap(), "42");
+    assert_eq!(replayed.headers().get("authorization").unwrap(), REDACTED);
+    assert_eq!(replayed.headers().get("cookie").unwrap(), REDACTED);
+    assert!(!serde_json::to_string(&recording)
+        .unwrap()
+        .contains("Bearer"));
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "dotenv")]
+
+use serde::Deserialize;
+use std::path::PathBuf;
+use teloc::dotenv::{from_vars, parse, DotenvError};
+use teloc::*;
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Level {
+    Info,
+    Debug,
+}
+
+#[derive(Deserialize, Debug)]
+struct Settings {
+    teloc_test_name: String,
+    teloc_test_port: u16,
+    teloc_test_ratio: f64,
+    teloc_test_level: Level,
+    teloc_test_hosts: Vec<String>,
+    teloc_test_token: Option<String>,
+}
+
+fn write_env(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("teloc-test-{}.env", name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_parse() {
+    let vars = parse(
+        "# comment\n\nexport A=1\nB = \"two words\" \nC='#not a comment'\nD=x # comment\nE=\n",
+    )
+    .unwrap();
+    assert_eq!(vars["A"], "1");
+    assert_eq!(vars["B"], "two words");
+    assert_eq!(vars["C"], "#not a comment");
+    assert_eq!(vars["D"], "x");
+    assert_eq!(vars["E"], "");
+
+    let err = parse("A=1\nnot a pair\n").unwrap_err();
+    assert!(matches!(err, DotenvError::Syntax { line: 2 }));
+}
+
+#[test]
+fn test_parse_quoted_with_comment() {
+    let vars = parse("A=\"v\" # comment\nB='a # b'  # comment\nC=\"say \\\"hi\\\"\"\n").unwrap();
+    assert_eq!(vars["A"], "v");
+    assert_eq!(vars["B"], "a # b");
+    assert_eq!(vars["C"], "say \"hi\"");
+
+    let err = parse("A=\"v\" trailing\n").unwrap_err();
+    assert!(matches!(err, DotenvError::Syntax { line: 1 }));
+    let err = parse("A=1\nB=\"unclosed\n").unwrap_err();
+    assert!(matches!(err, DotenvError::Syntax { line: 2 }));
+}
+
+#[test]
+fn test_mixed_case_keys_are_ignored() {
+    #[derive(Deserialize)]
+    struct Config {
+        foo_bar: String,
+    }
+
+    let config = from_vars::<Config>(vec![
+        ("Foo_Bar".into(), "mixed".into()),
+        ("FOO_BAR".into(), "upper".into()),
+        ("foo_bar".into(), "lower".into()),
+    ])
+    .unwrap();
+    assert_eq!(config.foo_bar, "upper");
+}
+
+#[test]
+fn test_add_from_dotenv() {
+    let path = write_env(
+        "settings",
+        "TELOC_TEST_NAME=shop\nTELOC_TEST_PORT=8080\nTELOC_TEST_RATIO=0.5\n\
+         TELOC_TEST_LEVEL=Debug\nTELOC_TEST_HOSTS=a.local, b.local\n",
+    );
+    let sp = ServiceProvider::new()
+        .add_from_dotenv::<Settings>(&path)
+        .unwrap();
+
+    let settings: &Settings = sp.resolve();
+    assert_eq!(settings.teloc_test_name, "shop");
+    assert_eq!(settings.teloc_test_port, 8080);
+    assert_eq!(settings.teloc_test_ratio, 0.5);
+    assert_eq!(settings.teloc_test_level, Level::Debug);
+    assert_ne!(settings.teloc_test_level, Level::Info);
+    assert_eq!(settings.teloc_test_hosts, ["a.local", "b.local"]);
+    assert_eq!(settings.teloc_test_token, None);
+}
+
+#[test]
+fn test_process_overrides_file() {
+    #[derive(Deserialize)]
+    struct Override {
+        teloc_test_override: String,
+    }
+
+    std::env::set_var("TELOC_TEST_OVERRIDE", "process");
+    let path = write_env("override", "TELOC_TEST_OVERRIDE=file\n");
+    let sp = ServiceProvider::new()
+        .add_from_dotenv::<Override>(&path)
+        .unwrap();
+
+    let value: &Override = sp.resolve();
+    assert_eq!(value.teloc_test_override, "process");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_unicode_process_variables_are_skipped() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    #[derive(Deserialize)]
+    struct Unicode {
+        teloc_test_unicode: String,
+    }
+
+    std::env::set_var("TELOC_TEST_NOT_UNICODE", OsStr::from_bytes(b"\xff\xfe"));
+    let path = write_env("unicode", "TELOC_TEST_UNICODE=ok\n");
+    let sp = ServiceProvider::new()
+        .add_from_dotenv::<Unicode>(&path)
+        .unwrap();
+
+    let value: &Unicode = sp.resolve();
+    assert_eq!(value.teloc_test_unicode, "ok");
+}
+
+#[test]
+fn test_errors_name_the_key() {
+    #[derive(Deserialize, Debug)]
+    struct Server {
+        _port: u16,
+        _host: String,
+    }
+
+    let err = from_vars::<Server>(vec![("_HOST".into(), "a".into())]).unwrap_err();
+    assert_eq!(err.to_string(), "missing environment variable `_PORT`");
+
+    let err = from_vars::<Server>(vec![
+        ("_HOST".into(), "a".into()),
+        ("_PORT".into(), "http".into()),
+    ])
+    .unwrap_err();
+    assert!(matches!(&err, DotenvError::Invalid { key, .. } if key == "_PORT"));
+    assert!(err
+        .to_string()
+        .starts_with("invalid environment variable `_PORT`: `http`: "));
+
+    let err = ServiceProvider::new()
+        .add_from_dotenv::<Server>("/nonexistent/.env")
+        .err()
+        .unwrap();
+    assert!(matches!(err, DotenvError::Io { .. }));
+}
@@ -0,0 +1,62 @@
+use std::rc::Rc;
+use teloc::dynamic::{DynError, DynServiceProvider};
+use teloc::*;
+use uuid::Uuid;
+
+struct Config {
+    id: Uuid,
+}
+
+#[derive(Dependency)]
+struct Repo {
+    config: Rc<Config>,
+    #[init(Uuid::new_v4())]
+    id: Id,
+}
+
+struct Id(Uuid);
+impl Id {
+    fn init(id: Uuid) -> Self {
+        Id(id)
+    }
+}
+
+#[derive(Dependency)]
+struct Controller {
+    repo: Rc<Repo>,
+    limit: u32,
+}
+
+#[test]
+fn test_dyn_service_provider() {
+    let config = Rc::new(Config { id: Uuid::new_v4() });
+    let sp = DynServiceProvider::new()
+        .add_instance(config.clone())
+        .add_instance(3u32)
+        .add_singleton::<Rc<Repo>, _>()
+        .add_transient::<Controller, _>();
+
+    let c1: Controller = sp.resolve().unwrap();
+    let c2: Controller = sp.resolve().unwrap();
+    assert!(Rc::ptr_eq(&c1.repo, &c2.repo));
+    assert_eq!(c1.repo.id.0, c2.repo.id.0);
+    assert_eq!(c1.repo.config.id, config.id);
+    assert_eq!(c1.limit, 3);
+
+    let limit: &u32 = sp.get().unwrap();
+    assert_eq!(*limit, 3);
+    assert!(sp.resolver().contains::<Controller>());
+}
+
+#[test]
+fn test_missing_dependency() {
+    let sp = DynServiceProvider::new().add_transient::<Controller, _>();
+    assert_eq!(
+        sp.resolve::<Controller>().err(),
+        Some(DynError::NotRegistered(std::any::type_name::<Rc<Repo>>()))
+    );
+    assert_eq!(
+        sp.get::<Controller>().err(),
+        Some(DynError::NotSingleton(std::any::type_name::<Controller>()))
+    );
+}
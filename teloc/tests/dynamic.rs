@@ -0,0 +1,299 @@
+use std::rc::Rc;
+use teloc::dynamic::{DynError, DynResolver, DynService};
+use teloc::*;
+use uuid::Uuid;
+
+#[derive(Dependency)]
+struct Id {
+    #[init(Uuid::new_v4())]
+    value: Value,
+}
+
+struct Value(Uuid);
+impl Value {
+    fn init(id: Uuid) -> Self {
+        Value(id)
+    }
+}
+
+#[derive(Dependency)]
+struct Handler {
+    id: Rc<Id>,
+    limit: Limit,
+}
+
+struct Limit(u32);
+#[inject]
+impl Limit {
+    fn new() -> Self {
+        Limit(10)
+    }
+}
+
+#[test]
+fn test_dyn_resolver() {
+    let resolver = DynResolver::new()
+        .add_service(DynService::singleton::<Rc<Id>, _>())
+        .add_service(DynService::transient::<Limit, _>())
+        .add_service(DynService::transient::<Handler, _>());
+
+    let h1: Handler = resolver.resolve().unwrap();
+    let h2: Handler = resolver.resolve().unwrap();
+    assert!(Rc::ptr_eq(&h1.id, &h2.id));
+    assert_eq!(h1.limit.0, 10);
+    assert!(resolver.contains::<Handler>());
+
+    let id: &Rc<Id> = resolver.get().unwrap();
+    assert_eq!(id.value.0, h1.id.value.0);
+    assert_eq!(
+        resolver.get::<Handler>().err(),
+        Some(DynError::NotSingleton(std::any::type_name::<Handler>()))
+    );
+}
+
+#[test]
+fn test_missing_dependency() {
+    let resolver = DynResolver::new().add_service(DynService::transient::<Handler, _>());
+    assert_eq!(
+        resolver.resolve::<Handler>().err(),
+        Some(DynError::NotRegistered(std::any::type_name::<Rc<Id>>()))
+    );
+}
+
+#[test]
+fn test_resolution_path() {
+    let resolver = DynResolver::new()
+        .with_path_tracking()
+        .add_service(DynService::transient::<Limit, _>())
+        .add_service(DynService::transient::<Handler, _>());
+    let error = resolver.resolve::<Handler>().err().unwrap();
+    assert_eq!(
+        error,
+        DynError::Resolving(
+            vec![std::any::type_name::<Handler>()],
+            Box::new(DynError::NotRegistered(std::any::type_name::<Rc<Id>>()))
+        )
+    );
+    assert_eq!(
+        error.cause(),
+        &DynError::NotRegistered(std::any::type_name::<Rc<Id>>())
+    );
+
+    // The path is cleared after a failure.
+    let limit: Limit = resolver.resolve().unwrap();
+    assert_eq!(limit.0, 10);
+}
+
+#[test]
+fn test_resolution_path_of_singleton() {
+    let mut resolver = DynResolver::new()
+        .with_path_tracking()
+        .add_service(DynService::transient::<Handler, _>())
+        .add_service(DynService::transient::<Limit, _>());
+    resolver.register_singleton::<Rc<Id>, _>(|_| Err(DynError::NotRegistered("Uuid")));
+    let error = resolver.resolve::<Handler>().err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "service `Uuid` is not registered, resolution path: {} -> {}",
+            std::any::type_name::<Handler>(),
+            std::any::type_name::<Rc<Id>>()
+        )
+    );
+}
+
+#[test]
+fn test_resolution_path_after_panic() {
+    let mut resolver = DynResolver::new()
+        .with_path_tracking()
+        .add_service(DynService::transient::<Handler, _>());
+    let calls = std::cell::Cell::new(0);
+    resolver.register_transient::<Rc<Id>, _>(move |_| {
+        calls.set(calls.get() + 1);
+        if calls.get() == 1 {
+            panic!("database is down");
+        }
+        Err(DynError::NotRegistered("Uuid"))
+    });
+    resolver.register_transient(|_| Ok(Limit(5)));
+
+    let resolver = std::panic::AssertUnwindSafe(resolver);
+    assert!(std::panic::catch_unwind(|| resolver.resolve::<Handler>()).is_err());
+
+    // Services of the panicked resolution are popped from the path.
+    assert_eq!(
+        resolver.resolve::<Handler>().err(),
+        Some(DynError::Resolving(
+            vec![
+                std::any::type_name::<Handler>(),
+                std::any::type_name::<Rc<Id>>()
+            ],
+            Box::new(DynError::NotRegistered("Uuid"))
+        ))
+    );
+}
+
+struct Left;
+struct Right;
+
+#[test]
+fn test_transient_cycle() {
+    let mut resolver = DynResolver::new().with_path_tracking();
+    resolver.register_transient(|r| r.resolve::<Right>().map(|_| Left));
+    resolver.register_transient(|r| r.resolve::<Left>().map(|_| Right));
+    let left = std::any::type_name::<Left>();
+    let right = std::any::type_name::<Right>();
+    assert_eq!(
+        resolver.resolve::<Left>().err(),
+        Some(DynError::Cycle(vec![left, right, left]))
+    );
+    assert_eq!(
+        resolver.resolve::<Right>().err().unwrap().to_string(),
+        format!(
+            "service `{1}` depends on itself, resolution path: {1} -> {0} -> {1}",
+            left, right
+        )
+    );
+}
+
+struct Registry;
+struct Plugin;
+
+#[test]
+fn test_singleton_cycle() {
+    let mut resolver = DynResolver::new().with_path_tracking();
+    resolver.register_singleton(|r| r.resolve::<Plugin>().map(|_| Rc::new(Registry)));
+    resolver.register_transient(|r| r.resolve::<Rc<Registry>>().map(|_| Plugin));
+    let registry = std::any::type_name::<Rc<Registry>>();
+    let plugin = std::any::type_name::<Plugin>();
+    assert_eq!(
+        resolver.resolve::<Rc<Registry>>().err(),
+        Some(DynError::Cycle(vec![registry, plugin, registry]))
+    );
+    assert_eq!(
+        resolver.resolve::<Plugin>().err(),
+        Some(DynError::Cycle(vec![plugin, registry, plugin]))
+    );
+}
+
+#[test]
+fn test_max_depth() {
+    let resolver = DynResolver::new()
+        .with_max_depth(1)
+        .add_service(DynService::transient::<Limit, _>())
+        .add_service(DynService::singleton::<Rc<Id>, _>())
+        .add_service(DynService::transient::<Handler, _>());
+    assert_eq!(
+        resolver.resolve::<Handler>().err(),
+        Some(DynError::TooDeep(vec![
+            std::any::type_name::<Handler>(),
+            std::any::type_name::<Rc<Id>>()
+        ]))
+    );
+    assert!(resolver.resolve::<Limit>().is_ok());
+}
+
+#[test]
+fn test_lookup_by_type() {
+    let resolver = DynResolver::new()
+        .add_service(DynService::singleton::<Rc<Id>, _>())
+        .add_service(DynService::transient::<Limit, _>());
+
+    let id = resolver
+        .get_by_type_id(std::any::TypeId::of::<Rc<Id>>())
+        .unwrap();
+    let by_name = resolver
+        .get_by_type_name(std::any::type_name::<Rc<Id>>())
+        .unwrap();
+    assert!(Rc::ptr_eq(
+        id.downcast_ref::<Rc<Id>>().unwrap(),
+        by_name.downcast_ref::<Rc<Id>>().unwrap()
+    ));
+
+    assert_eq!(
+        resolver
+            .get_by_type_name(std::any::type_name::<Limit>())
+            .err(),
+        Some(DynError::NotSingleton(std::any::type_name::<Limit>()))
+    );
+    assert_eq!(
+        resolver.get_by_type_name("Missing").err(),
+        Some(DynError::NotFound(String::from("Missing")))
+    );
+    assert!(resolver
+        .get_by_type_id(std::any::TypeId::of::<Handler>())
+        .is_err());
+
+    let mut names = resolver
+        .services()
+        .map(|(_, name)| name)
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    let mut expected = vec![
+        std::any::type_name::<Rc<Id>>(),
+        std::any::type_name::<Limit>(),
+    ];
+    expected.sort_unstable();
+    assert_eq!(names, expected);
+}
+
+#[derive(Dependency)]
+struct Service<'a> {
+    resolver: &'a DynResolver,
+}
+
+#[test]
+fn test_in_service_provider() {
+    let sp = ServiceProvider::new()
+        .add_instance(DynResolver::new().add_service(DynService::singleton::<Rc<Id>, _>()))
+        .add_transient::<Service>();
+    let service: Service = sp.resolve();
+    assert!(service.resolver.get::<Rc<Id>>().is_ok());
+}
+
+trait Command {
+    fn run(&self) -> String;
+}
+
+struct Echo(String);
+impl Command for Echo {
+    fn run(&self) -> String {
+        self.0.clone()
+    }
+}
+
+#[test]
+fn test_runtime_registration() {
+    let mut resolver = DynResolver::new();
+    let prefix = String::from("echo: ");
+    resolver.register_singleton(|_| Ok(Rc::new(Id::init(HNil))));
+    resolver.register_transient(move |r| {
+        let id = r.get::<Rc<Id>>()?;
+        Ok(Box::new(Echo(format!("{}{}", prefix, id.value.0.is_nil()))) as Box<dyn Command>)
+    });
+
+    let command: Box<dyn Command> = resolver.resolve().unwrap();
+    assert_eq!(command.run(), "echo: false");
+}
+
+#[test]
+fn test_erased_factory() {
+    let mut resolver = DynResolver::new();
+    resolver.register_factory(
+        std::any::TypeId::of::<Limit>(),
+        std::any::type_name::<Limit>(),
+        Box::new(|_| Box::new(Limit(5))),
+    );
+    resolver.register_factory(
+        std::any::TypeId::of::<u8>(),
+        "u8",
+        Box::new(|_| Box::new(Limit(5))),
+    );
+
+    let limit: Limit = resolver.resolve().unwrap();
+    assert_eq!(limit.0, 5);
+    assert_eq!(
+        resolver.resolve::<u8>().err(),
+        Some(DynError::TypeMismatch("u8"))
+    );
+}
@@ -0,0 +1,150 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use teloc::reexport::frunk::{HCons, HNil};
+use teloc::*;
+
+struct Counter(Cell<u32>);
+
+struct Ticket(u32);
+
+#[derive(Dependency)]
+struct Controller {
+    ticket: Ticket,
+}
+
+type SP = ServiceProvider<
+    EmptyServiceProvider,
+    HCons<
+        TransientFactoryContainer<HCons<Rc<Counter>, HNil>, Ticket>,
+        HCons<SingletonFactoryContainer<HNil, Rc<Counter>>, HNil>,
+    >,
+>;
+
+fn provider() -> SP {
+    ServiceProvider::new()
+        .add_singleton_factory(|_: HNil| Rc::new(Counter(Cell::new(0))))
+        .add_transient_factory(|HCons { head: counter, .. }: HCons<Rc<Counter>, HNil>| {
+            counter.0.set(counter.0.get() + 1);
+            Ticket(counter.0.get())
+        })
+}
+
+#[test]
+fn test_boxed_factories() {
+    let sp = provider();
+
+    let first: Ticket = sp.resolve();
+    let second: Ticket = sp.resolve();
+    let counter: Rc<Counter> = sp.resolve();
+
+    assert_eq!(first.0, 1);
+    assert_eq!(second.0, 2);
+    assert_eq!(counter.0.get(), 2);
+}
+
+#[test]
+fn test_factory_as_dependency() {
+    let sp = provider();
+    let scope = sp.fork().add_transient::<Controller>();
+
+    let controller: Controller = scope.resolve();
+    assert_eq!(controller.ticket.0, 1);
+}
+
+struct Label<'a> {
+    text: &'a str,
+}
+
+#[test]
+fn test_unboxed_factories_borrow_from_provider() {
+    let sp = ServiceProvider::new()
+        .add_instance("label".to_string())
+        .add_singleton_factory_unboxed(|HCons { head: text, .. }: HCons<&String, HNil>| text.len())
+        .add_transient_factory_unboxed(|HCons { head: text, .. }: HCons<&String, HNil>| Label {
+            text,
+        });
+
+    let label: Label = sp.resolve();
+    let len: &usize = sp.resolve();
+
+    assert_eq!(label.text, "label");
+    assert_eq!(*len, 5);
+}
+
+struct Address {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn test_tuple_factories() {
+    let sp = ServiceProvider::new()
+        .add_instance("localhost".to_string())
+        .add_instance(8080u16)
+        .add_singleton_factory(|()| Rc::new(Counter(Cell::new(0))))
+        .add_transient_factory_unboxed(|(host, port, counter): (&String, &u16, Rc<Counter>)| {
+            counter.0.set(counter.0.get() + 1);
+            Address {
+                host: host.clone(),
+                port: *port,
+            }
+        });
+
+    let address: Address = sp.resolve();
+    let counter: Rc<Counter> = sp.resolve();
+
+    assert_eq!(address.host, "localhost");
+    assert_eq!(address.port, 8080);
+    assert_eq!(counter.0.get(), 1);
+}
+
+struct Schema {
+    ticket: u32,
+}
+
+#[factory]
+fn make_schema(controller: Controller) -> Schema {
+    Schema {
+        ticket: controller.ticket.0,
+    }
+}
+
+#[factory]
+fn make_counter() -> Rc<Counter> {
+    Rc::new(Counter(Cell::new(10)))
+}
+
+#[factory]
+fn make_ticket(counter: Rc<Counter>) -> Ticket {
+    counter.0.set(counter.0.get() + 1);
+    Ticket(counter.0.get())
+}
+
+struct Caption<'a> {
+    text: &'a str,
+    schema: Schema,
+}
+
+#[factory]
+fn make_caption<'a>(text: &'a String, schema: Schema) -> Caption<'a> {
+    Caption { text, schema }
+}
+
+#[test]
+fn test_factory_attribute() {
+    let sp = ServiceProvider::new()
+        .add_instance("label".to_string())
+        .add_singleton_factory_unboxed(make_counter)
+        .add_factory(make_ticket)
+        .add_transient::<Controller>()
+        .add_factory(make_schema)
+        .add_factory(make_caption);
+
+    let schema: Schema = sp.resolve();
+    let caption: Caption = sp.resolve();
+
+    assert_eq!(schema.ticket, 11);
+    assert_eq!(caption.text, "label");
+    assert_eq!(caption.schema.ticket, 12);
+    assert_eq!(make_ticket((Rc::new(Counter(Cell::new(0))),)).0, 1);
+}
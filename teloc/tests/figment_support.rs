@@ -0,0 +1,50 @@
+#![cfg(feature = "figment-support")]
+
+use figment::providers::{Format, Toml};
+use figment::Figment;
+use serde::Deserialize;
+use teloc::{inject, Resolver, ServiceProvider};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct DbConfig {
+    url: String,
+    pool_size: u32,
+}
+
+struct Repository<'a> {
+    config: &'a DbConfig,
+}
+#[inject]
+impl<'a> Repository<'a> {
+    fn new(config: &'a DbConfig) -> Self {
+        Self { config }
+    }
+}
+
+fn figment() -> Figment {
+    Figment::new().merge(Toml::string(
+        r#"
+        [database]
+        url = "postgres://localhost/app"
+        pool_size = 4
+        "#,
+    ))
+}
+
+#[test]
+fn test_section() {
+    let sp = ServiceProvider::new()
+        .add_config_section::<DbConfig>(&figment(), "database")
+        .unwrap()
+        .add_transient::<Repository>();
+
+    let repository: Repository = sp.resolve();
+    assert_eq!(repository.config.url, "postgres://localhost/app");
+    assert_eq!(repository.config.pool_size, 4);
+}
+
+#[test]
+fn test_missing_section() {
+    let res = ServiceProvider::new().add_config_section::<DbConfig>(&figment(), "cache");
+    assert!(res.is_err());
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "flat-storage")]
+
+use std::rc::Rc;
+use teloc::*;
+use uuid::Uuid;
+
+struct Config {
+    id: Uuid,
+}
+#[inject]
+impl Config {
+    fn new() -> Self {
+        Config { id: Uuid::new_v4() }
+    }
+}
+
+#[derive(Dependency)]
+struct Repo<'a> {
+    config: &'a Config,
+    cache: Rc<Cache>,
+}
+
+#[derive(Dependency)]
+struct Cache;
+
+#[derive(Dependency)]
+struct Handler<'a> {
+    repo: Repo<'a>,
+    request: &'a Request,
+}
+
+struct Request(u32);
+
+#[test]
+fn test_flatten() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Config>()
+        .add_singleton::<Rc<Cache>>()
+        .add_transient::<Repo>()
+        .add_transient::<Handler>()
+        .add_instance(10u8)
+        .flatten();
+    sp.warm_up();
+
+    let r1: Repo = sp.resolve();
+    let r2: Repo = sp.resolve();
+    let config: &Config = sp.resolve();
+    assert_eq!(r1.config.id, config.id);
+    assert!(Rc::ptr_eq(&r1.cache, &r2.cache));
+    let number: u8 = sp.resolve();
+    assert_eq!(number, 10);
+
+    let scope = sp.fork().add_instance(Request(7)).flatten();
+    let handler: Handler = scope.resolve();
+    assert_eq!(handler.request.0, 7);
+    assert_eq!(handler.repo.config.id, config.id);
+}
+
+#[test]
+fn test_flatten_many() {
+    let sp = ServiceProvider::new()
+        .add_instance(1u8)
+        .add_instance(2u16)
+        .add_instance(3u32)
+        .add_instance(4u64)
+        .add_instance(5i8)
+        .add_instance(6i16)
+        .add_instance(7i32)
+        .add_instance(8i64)
+        .add_instance(9usize)
+        .add_instance(10isize)
+        .add_instance(11u128)
+        .add_instance(12i128)
+        .add_instance('c')
+        .add_instance(true)
+        .add_instance(1.5f32)
+        .add_instance(2.5f64)
+        .flatten();
+
+    let first: u8 = sp.resolve();
+    let middle: i128 = sp.resolve();
+    let last: f64 = sp.resolve();
+    assert_eq!((first, middle, last), (1, 12, 2.5));
+    let _: ServiceProvider<EmptyServiceProvider, Flat<()>> = ServiceProvider::new().flatten();
+}
+
+#[derive(Debug, PartialEq)]
+struct Number<const N: usize>;
+
+#[test]
+fn test_flatten_more_than_16() {
+    let sp = ServiceProvider::new()
+        .add_instance(Number::<0>)
+        .add_instance(Number::<1>)
+        .add_instance(Number::<2>)
+        .add_instance(Number::<3>)
+        .add_instance(Number::<4>)
+        .add_instance(Number::<5>)
+        .add_instance(Number::<6>)
+        .add_instance(Number::<7>)
+        .add_instance(Number::<8>)
+        .add_instance(Number::<9>)
+        .add_instance(Number::<10>)
+        .add_instance(Number::<11>)
+        .add_instance(Number::<12>)
+        .add_instance(Number::<13>)
+        .add_instance(Number::<14>)
+        .add_instance(Number::<15>)
+        .add_instance(Number::<16>)
+        .add_instance(Number::<17>)
+        .add_singleton::<Config>()
+        .flatten();
+    sp.warm_up();
+
+    let first: &Number<0> = sp.resolve();
+    let last: &Number<17> = sp.resolve();
+    let config: &Config = sp.resolve();
+    assert_eq!((first, last), (&Number, &Number));
+    let _ = config.id;
+}
+
+#[test]
+fn test_add_after_flatten() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Config>()
+        .add_singleton::<Rc<Cache>>()
+        .flatten()
+        .add_transient::<Repo>()
+        .add_instance(Request(3))
+        .add_transient::<Handler>();
+    sp.warm_up();
+
+    let config: &Config = sp.resolve();
+    let handler: Handler = sp.resolve();
+    assert_eq!(handler.request.0, 3);
+    assert_eq!(handler.repo.config.id, config.id);
+}
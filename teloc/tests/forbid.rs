@@ -0,0 +1,44 @@
+use teloc::{inject, ForbidReason, Resolver, ServiceProvider};
+
+struct DbPool;
+struct UseRepository;
+impl ForbidReason for UseRepository {
+    const REASON: &'static str = "use `Repository` instead of `DbPool`";
+}
+
+struct Repository<'a> {
+    _pool: &'a DbPool,
+}
+#[inject]
+impl<'a> Repository<'a> {
+    fn new(pool: &'a DbPool) -> Self {
+        Repository { _pool: pool }
+    }
+}
+
+struct Config;
+
+struct Controller<'a> {
+    _config: &'a Config,
+}
+#[inject]
+impl<'a> Controller<'a> {
+    fn new(config: &'a Config) -> Self {
+        Controller { _config: config }
+    }
+}
+
+#[test]
+fn test_forbid_does_not_affect_other_services() {
+    let repositories = ServiceProvider::new()
+        .add_instance(Config)
+        .add_instance(DbPool)
+        .add_transient::<Repository>();
+    let sp = repositories
+        .fork()
+        .forbid::<DbPool, UseRepository>()
+        .add_transient::<Controller>();
+
+    let _: Controller = sp.resolve();
+    let _: Repository = repositories.resolve();
+}
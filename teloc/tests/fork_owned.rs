@@ -0,0 +1,44 @@
+#![cfg(feature = "sync")]
+
+use std::sync::Arc;
+use teloc::*;
+
+struct Counter {
+    start: u32,
+}
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter { start: 5 }
+    }
+}
+
+struct Request(u32);
+
+#[derive(Dependency)]
+struct Handler<'a> {
+    counter: Arc<Counter>,
+    request: &'a Request,
+}
+
+#[tokio::test]
+async fn test_fork_owned_moves_into_task() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Counter>>()
+            .add_transient::<Handler>(),
+    );
+    let counter: Arc<Counter> = sp.resolve();
+
+    let scope = sp.fork_owned().add_instance(Request(3));
+    let (start, request, same) = tokio::spawn(async move {
+        let handler: Handler = scope.resolve();
+        let same = Arc::ptr_eq(&handler.counter, &scope.resolve());
+        (handler.counter.start, handler.request.0, same)
+    })
+    .await
+    .unwrap();
+
+    assert_eq!((start, request, same), (5, 3, true));
+    assert_eq!(counter.start, 5);
+}
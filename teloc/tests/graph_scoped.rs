@@ -0,0 +1,62 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use teloc::*;
+
+struct Connection(u32);
+#[inject]
+impl Connection {
+    fn new(ids: &Cell<u32>) -> Self {
+        ids.set(ids.get() + 1);
+        Connection(ids.get())
+    }
+}
+
+#[derive(Dependency)]
+struct Transaction {
+    connection: Rc<Connection>,
+}
+
+#[derive(Dependency)]
+struct Orders {
+    tx: Rc<Transaction>,
+    connection: Rc<Connection>,
+}
+
+#[derive(Dependency)]
+struct Payments {
+    tx: Rc<Transaction>,
+}
+
+#[derive(Dependency)]
+struct Checkout {
+    orders: Orders,
+    payments: Payments,
+}
+
+#[test]
+fn test_graph_scoped() {
+    let ids = Cell::new(0);
+    let sp = ServiceProvider::new()
+        .add_instance(&ids)
+        .add_graph_scoped::<Rc<Connection>>()
+        .add_graph_scoped::<Rc<Transaction>>()
+        .add_transient::<Orders>()
+        .add_transient::<Payments>()
+        .add_transient::<Checkout>();
+
+    let first: Checkout = sp.graph().resolve();
+    assert!(Rc::ptr_eq(&first.orders.tx, &first.payments.tx));
+    assert!(Rc::ptr_eq(
+        &first.orders.connection,
+        &first.orders.tx.connection
+    ));
+    assert_eq!(first.orders.connection.0, 1);
+
+    let scope = sp.fork();
+    let graph = scope.graph();
+    let second: Checkout = graph.resolve();
+    let orders: Orders = graph.resolve();
+    assert!(!Rc::ptr_eq(&first.orders.tx, &second.orders.tx));
+    assert!(Rc::ptr_eq(&second.orders.tx, &orders.tx));
+    assert_eq!(second.payments.tx.connection.0, 2);
+}
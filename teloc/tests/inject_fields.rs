@@ -0,0 +1,55 @@
+use std::rc::Rc;
+use teloc::*;
+
+struct Config {
+    greeting: &'static str,
+}
+
+struct Repository;
+#[inject]
+impl Repository {
+    fn new() -> Self {
+        Repository
+    }
+}
+
+#[derive(Default, InjectFields)]
+struct Handler<'a> {
+    name: String,
+    #[injected]
+    config: Option<&'a Config>,
+    #[injected]
+    repository: Option<Rc<Repository>>,
+    #[injected]
+    counter: Rc<u8>,
+}
+
+impl Handler<'_> {
+    fn greet(&self) -> String {
+        format!("{}, {}!", self.config.unwrap().greeting, self.name)
+    }
+}
+
+#[derive(InjectFields)]
+struct Pair(#[injected] Option<Rc<Repository>>, u8);
+
+#[test]
+fn test_inject_fields() {
+    let sp = ServiceProvider::new()
+        .add_instance(Config { greeting: "Hello" })
+        .add_instance(Rc::new(3u8))
+        .add_singleton::<Rc<Repository>>();
+
+    let mut handler = Handler {
+        name: "world".into(),
+        ..Default::default()
+    };
+    sp.inject(&mut handler);
+    assert_eq!(handler.greet(), "Hello, world!");
+    assert_eq!(*handler.counter, 3);
+
+    let mut pair = Pair(None, 1);
+    sp.fork().inject(&mut pair);
+    assert!(Rc::ptr_eq(&pair.0.unwrap(), &handler.repository.unwrap()));
+    assert_eq!(pair.1, 1);
+}
@@ -0,0 +1,83 @@
+use std::cell::Cell;
+use teloc::*;
+
+struct UserRepo {
+    users: Vec<&'static str>,
+}
+#[inject]
+impl UserRepo {
+    fn new() -> Self {
+        UserRepo {
+            users: vec!["ann", "bob"],
+        }
+    }
+}
+
+struct UserService<'a> {
+    repo: &'a UserRepo,
+    calls: &'a Cell<u32>,
+}
+#[inject]
+impl<'a> UserService<'a> {
+    fn new(repo: &'a UserRepo, calls: &'a Cell<u32>) -> Self {
+        UserService { repo, calls }
+    }
+}
+impl UserService<'_> {
+    fn exists(&self, name: &str) -> bool {
+        self.calls.set(self.calls.get() + 1);
+        self.repo.users.contains(&name)
+    }
+    async fn exists_async(&self, name: &str) -> bool {
+        self.exists(name)
+    }
+}
+
+type TestSp = ServiceProvider<
+    EmptyServiceProvider,
+    HCons<
+        TransientContainer<UserService<'static>>,
+        HCons<InstanceContainer<Cell<u32>>, HCons<SingletonContainer<UserRepo>, HNil>>,
+    >,
+>;
+
+fn build_test_sp() -> TestSp {
+    ServiceProvider::new()
+        .add_singleton::<UserRepo>()
+        .add_instance(Cell::new(0))
+        .add_transient::<UserService>()
+}
+
+#[inject_test(provider = build_test_sp)]
+fn test_arguments_are_resolved(repo: &UserRepo, svc: UserService, calls: &Cell<u32>) {
+    assert!(std::ptr::eq(repo, svc.repo));
+    assert!(svc.exists("ann"));
+    assert!(!svc.exists("eve"));
+    assert_eq!(calls.get(), 2);
+}
+
+#[teloc::inject_test(provider = build_test_sp)]
+fn test_provider_is_fresh(calls: &Cell<u32>) {
+    assert_eq!(calls.get(), 0);
+}
+
+#[inject_test(provider = build_test_sp)]
+fn test_returns_result(mut svc: UserService, repo: &UserRepo) -> Result<(), String> {
+    svc.repo = repo;
+    if svc.exists("bob") {
+        Ok(())
+    } else {
+        Err("bob is missing".to_string())
+    }
+}
+
+#[inject_test(provider = build_test_sp)]
+#[should_panic(expected = "eve is missing")]
+fn test_other_attributes_are_kept(svc: UserService) {
+    assert!(svc.exists("eve"), "eve is missing");
+}
+
+#[inject_test(provider = build_test_sp)]
+async fn test_async(svc: UserService) {
+    assert!(svc.exists_async("bob").await);
+}
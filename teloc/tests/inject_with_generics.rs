@@ -1,3 +1,6 @@
+// This test only checks that the `#[inject]` expansion compiles.
+#![allow(dead_code)]
+
 use teloc::inject;
 
 #[derive(Debug, PartialEq)]
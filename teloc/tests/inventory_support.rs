@@ -0,0 +1,52 @@
+#![cfg(feature = "inventory-support")]
+
+use teloc::dynamic::DynResolver;
+use teloc::*;
+use uuid::Uuid;
+
+#[teloc::service(singleton)]
+#[derive(Clone, Dependency, DependencyClone)]
+struct Metrics {
+    #[init(Uuid::new_v4())]
+    id: Id,
+}
+
+#[derive(Clone)]
+struct Id(Uuid);
+impl Id {
+    fn init(id: Uuid) -> Self {
+        Id(id)
+    }
+}
+
+#[teloc::service]
+#[derive(Dependency)]
+struct Audit {
+    metrics: Metrics,
+    level: Level,
+}
+
+struct Level(u8);
+#[teloc::service]
+#[inject]
+impl Level {
+    fn new() -> Self {
+        Level(3)
+    }
+}
+
+#[derive(Dependency)]
+struct Plugins<'a> {
+    resolver: &'a DynResolver,
+}
+
+#[test]
+fn test_from_inventory() {
+    let sp = ServiceProvider::from_inventory().add_transient::<Plugins>();
+    let plugins: Plugins = sp.resolve();
+
+    let audit: Audit = plugins.resolver.resolve().unwrap();
+    let metrics: &Metrics = plugins.resolver.get().unwrap();
+    assert_eq!(audit.metrics.id.0, metrics.id.0);
+    assert_eq!(audit.level.0, 3);
+}
@@ -0,0 +1,112 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use teloc::*;
+
+trait Cache {
+    fn flush(&self, log: &RefCell<Vec<&'static str>>);
+}
+
+trait Named {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Dependency)]
+struct UserCache;
+impl Cache for UserCache {
+    fn flush(&self, log: &RefCell<Vec<&'static str>>) {
+        log.borrow_mut().push("users");
+    }
+}
+impl Named for UserCache {
+    fn name(&self) -> &'static str {
+        "user cache"
+    }
+}
+
+#[derive(Dependency)]
+struct OrderCache;
+impl Cache for OrderCache {
+    fn flush(&self, log: &RefCell<Vec<&'static str>>) {
+        log.borrow_mut().push("orders");
+    }
+}
+
+#[derive(Dependency)]
+struct Report;
+
+#[test]
+fn test_iter_implementors() {
+    let log = RefCell::new(vec![]);
+    let sp = ServiceProvider::new()
+        .add_singleton::<UserCache>()
+        .add_implementor::<dyn Cache, UserCache>(|c| c)
+        .add_implementor::<dyn Named, UserCache>(|c| c)
+        .add_transient::<Report>()
+        .add_instance(OrderCache)
+        .add_implementor::<dyn Cache, OrderCache>(|c| c);
+
+    sp.iter_implementors::<dyn Cache, _>()
+        .for_each(|cache| cache.flush(&log));
+    assert_eq!(*log.borrow(), ["users", "orders"]);
+
+    let names = sp
+        .iter_implementors::<dyn Named, _>()
+        .map(|n| n.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["user cache"]);
+    let _: Report = sp.resolve();
+}
+
+#[test]
+fn test_implementors_are_singletons() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Rc<UserCache>>()
+        .add_implementor::<dyn Named, Rc<UserCache>>(|c| &**c);
+
+    let cache: &Rc<UserCache> = sp.resolve();
+    let named = sp.iter_implementors::<dyn Named, _>().next().unwrap();
+    assert!(std::ptr::eq(
+        named as *const dyn Named as *const u8,
+        &**cache as *const UserCache as *const u8
+    ));
+}
+
+#[derive(Dependency)]
+struct SessionCache;
+impl Cache for SessionCache {
+    fn flush(&self, log: &RefCell<Vec<&'static str>>) {
+        log.borrow_mut().push("sessions");
+    }
+}
+
+#[test]
+fn test_implementors_are_sorted_by_priority() {
+    let log = RefCell::new(vec![]);
+    let sp = ServiceProvider::new()
+        .add_singleton::<UserCache>()
+        .add_implementor_with_priority::<dyn Cache, UserCache>(5, |c| c)
+        .add_singleton::<OrderCache>()
+        .add_implementor::<dyn Cache, OrderCache>(|c| c)
+        .add_singleton::<SessionCache>()
+        .add_implementor_with_priority::<dyn Cache, SessionCache>(-1, |c| c);
+
+    sp.iter_implementors::<dyn Cache, _>()
+        .for_each(|cache| cache.flush(&log));
+    assert_eq!(*log.borrow(), ["sessions", "orders", "users"]);
+}
+
+#[test]
+fn test_equal_priorities_keep_registration_order() {
+    let log = RefCell::new(vec![]);
+    let sp = ServiceProvider::new()
+        .add_singleton::<SessionCache>()
+        .add_implementor_with_priority::<dyn Cache, SessionCache>(1, |c| c)
+        .add_singleton::<UserCache>()
+        .add_implementor_with_priority::<dyn Cache, UserCache>(1, |c| c)
+        .add_singleton::<OrderCache>()
+        .add_implementor_with_priority::<dyn Cache, OrderCache>(1, |c| c);
+
+    sp.iter_implementors::<dyn Cache, _>()
+        .for_each(|cache| cache.flush(&log));
+    assert_eq!(*log.borrow(), ["sessions", "users", "orders"]);
+}
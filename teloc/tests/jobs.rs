@@ -0,0 +1,78 @@
+#![cfg(feature = "jobs")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use teloc::jobs::Scheduler;
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct Counter(AtomicUsize);
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter(AtomicUsize::new(0))
+    }
+}
+
+struct Report<'a> {
+    counter: &'a Counter,
+}
+#[inject]
+impl<'a> Report<'a> {
+    fn new(counter: &'a Counter) -> Self {
+        Report { counter }
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_jobs_run_by_schedule() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Counter>()
+            .add_transient::<Report>(),
+    );
+    let scheduler = Scheduler::new(sp.clone())
+        .add("* * * * * *", |scope| {
+            let report: Report = scope.resolve();
+            Box::pin(async move {
+                report.counter.0.fetch_add(1, Ordering::SeqCst);
+            })
+        })
+        .unwrap();
+
+    scheduler
+        .run(tokio::time::sleep(Duration::from_millis(4500)))
+        .await;
+
+    let counter: &Counter = sp.resolve();
+    let runs = counter.0.load(Ordering::SeqCst);
+    assert!((3..=5).contains(&runs), "{} runs", runs);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_shutdown_waits_for_running_jobs() {
+    async fn slow(counter: Arc<Counter>) {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        counter.0.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Counter>>());
+    let scheduler = Scheduler::new(sp.clone())
+        .add("* * * * * *", |scope| Box::pin(slow(scope.resolve())))
+        .unwrap();
+
+    scheduler
+        .run(tokio::time::sleep(Duration::from_millis(1500)))
+        .await;
+
+    let counter: Arc<Counter> = sp.resolve();
+    assert!(counter.0.load(Ordering::SeqCst) >= 1);
+}
+
+#[test]
+fn test_invalid_expression() {
+    let sp = Arc::new(ServiceProvider::new());
+    assert!(Scheduler::new(sp)
+        .add("every second", |_| Box::pin(async {}))
+        .is_err());
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use teloc::*;
+
+trait Payment {
+    fn charge(&self, amount: u32) -> String;
+}
+
+#[derive(Dependency)]
+struct Stripe;
+impl Payment for Stripe {
+    fn charge(&self, amount: u32) -> String {
+        format!("stripe:{}", amount)
+    }
+}
+
+struct Account(&'static str);
+
+struct Paypal {
+    account: &'static str,
+}
+#[inject]
+impl Paypal {
+    fn new(account: &Account) -> Self {
+        Paypal { account: account.0 }
+    }
+}
+impl Payment for Paypal {
+    fn charge(&self, amount: u32) -> String {
+        format!("paypal:{}:{}", self.account, amount)
+    }
+}
+impl Payment for Rc<Paypal> {
+    fn charge(&self, amount: u32) -> String {
+        (**self).charge(amount)
+    }
+}
+
+#[test]
+fn test_resolve_keyed_map() {
+    let sp = ServiceProvider::new()
+        .add_instance(Account("shop"))
+        .add_transient::<Stripe>()
+        .add_transient::<Paypal>()
+        .add_keyed_map(
+            KeyedMap::<dyn Payment>::new()
+                .insert::<Stripe>("stripe", |p| Box::new(p))
+                .insert::<Paypal>("paypal", |p| Box::new(p)),
+        );
+
+    let payments: HashMap<String, Box<dyn Payment>> = sp.resolve();
+    assert_eq!(payments.len(), 2);
+    assert_eq!(payments["stripe"].charge(5), "stripe:5");
+    assert_eq!(payments["paypal"].charge(7), "paypal:shop:7");
+}
+
+#[test]
+fn test_later_entry_replaces_earlier() {
+    let sp = ServiceProvider::new()
+        .add_instance(Account("shop"))
+        .add_transient::<Stripe>()
+        .add_transient::<Paypal>()
+        .add_keyed_map(
+            KeyedMap::<dyn Payment>::new()
+                .insert::<Stripe>("card", |p| Box::new(p))
+                .insert::<Paypal>("card", |p| Box::new(p)),
+        );
+
+    let payments: HashMap<String, Box<dyn Payment>> = sp.resolve();
+    assert_eq!(payments.len(), 1);
+    assert_eq!(payments["card"].charge(1), "paypal:shop:1");
+}
+
+#[test]
+fn test_entries_keep_their_lifetime() {
+    let sp = ServiceProvider::new()
+        .add_instance(Account("shop"))
+        .add_singleton::<Rc<Paypal>>()
+        .add_keyed_map(
+            KeyedMap::<dyn Payment>::new().insert::<Rc<Paypal>>("paypal", |p| Box::new(p)),
+        );
+
+    let first: HashMap<String, Box<dyn Payment>> = sp.resolve();
+    let second: HashMap<String, Box<dyn Payment>> = sp.resolve();
+    let paypal: &Rc<Paypal> = sp.resolve();
+    // Both maps hold clones of the same singleton.
+    assert_eq!(Rc::strong_count(paypal), 3);
+    drop((first, second));
+    assert_eq!(Rc::strong_count(paypal), 1);
+}
+
+#[test]
+fn test_keyed_map_from_fork() {
+    let sp = ServiceProvider::new()
+        .add_transient::<Stripe>()
+        .add_transient::<Paypal>();
+    let scope = sp.fork().add_instance(Account("tenant")).add_keyed_map(
+        KeyedMap::<dyn Payment>::new()
+            .insert::<Stripe>("stripe", |p| Box::new(p))
+            .insert::<Paypal>("paypal", |p| Box::new(p)),
+    );
+
+    let payments: HashMap<String, Box<dyn Payment>> = scope.resolve();
+    assert_eq!(payments["paypal"].charge(2), "paypal:tenant:2");
+}
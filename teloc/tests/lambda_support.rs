@@ -0,0 +1,67 @@
+#![cfg(feature = "lambda-support")]
+
+use futures::executor::block_on;
+use lambda_runtime::{Context, LambdaEvent};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use teloc::{inject, DiLambdaHandler, LambdaScope, Resolver, ServiceProvider};
+use tower::Service;
+
+struct Invocations(AtomicUsize);
+#[inject]
+impl Invocations {
+    fn new() -> Self {
+        Invocations(AtomicUsize::new(0))
+    }
+}
+
+struct Order(u32);
+
+struct OrderHandler<'a> {
+    order: &'a Order,
+    invocation: usize,
+    request_id: String,
+}
+#[inject]
+impl<'a> OrderHandler<'a> {
+    fn new(order: &'a Order, invocation: &'a usize, context: Context) -> Self {
+        OrderHandler {
+            order,
+            invocation: *invocation,
+            request_id: context.request_id,
+        }
+    }
+}
+
+#[test]
+fn test_scope_per_invocation() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Invocations>>()
+            .add_transient::<OrderHandler>(),
+    );
+    let mut handler = DiLambdaHandler::new(
+        sp,
+        |scope: LambdaScope<_, u32>| {
+            let invocations: Arc<Invocations> = scope.resolve();
+            let invocation = invocations.0.fetch_add(1, Ordering::SeqCst);
+            let order: &u32 = scope.resolve();
+            let order = Order(*order);
+            scope.add_instance(invocation).add_instance(order)
+        },
+        |scope| async move {
+            let handler: OrderHandler = scope.resolve();
+            Ok::<_, lambda_runtime::Error>(format!(
+                "{} {} {}",
+                handler.order.0, handler.invocation, handler.request_id
+            ))
+        },
+    );
+
+    let mut context = Context::default();
+    context.request_id = "req-1".into();
+    let first = block_on(handler.call(LambdaEvent::new(7, context))).unwrap();
+    let second = block_on(handler.call(LambdaEvent::new(8, Context::default()))).unwrap();
+    assert_eq!(first, "7 0 req-1");
+    assert_eq!(second, "8 1 ");
+}
@@ -0,0 +1,58 @@
+use std::rc::Rc;
+use teloc::*;
+use uuid::Uuid;
+
+struct Parent {
+    id: Uuid,
+    children: Vec<Rc<Child>>,
+}
+#[inject]
+impl Parent {
+    fn new(first: Rc<Child>, second: Rc<Child>) -> Self {
+        Parent {
+            id: Uuid::new_v4(),
+            children: vec![first, second],
+        }
+    }
+}
+
+struct Child {
+    parent: Later<Rc<Parent>>,
+}
+#[inject]
+impl Child {
+    fn new(parent: Later<Rc<Parent>>) -> Self {
+        assert!(parent.get().is_none());
+        Child { parent }
+    }
+}
+
+#[test]
+fn test_cyclic_singletons() {
+    let sp = ServiceProvider::new()
+        .add_singleton_cyclic::<Rc<Parent>>()
+        .add_transient::<Rc<Child>>();
+
+    let parent: &Rc<Parent> = sp.resolve();
+    assert_eq!(parent.children.len(), 2);
+    for child in &parent.children {
+        assert_eq!(child.parent.get().unwrap().id, parent.id);
+    }
+
+    let later: Later<Rc<Parent>> = sp.resolve();
+    assert!(Rc::ptr_eq(later.get().unwrap(), parent));
+    let cloned: Rc<Parent> = sp.resolve();
+    assert!(Rc::ptr_eq(&cloned, parent));
+}
+
+#[test]
+fn test_cyclic_singleton_warm_up() {
+    let sp = ServiceProvider::new()
+        .add_transient::<Rc<Child>>()
+        .add_singleton_cyclic::<Rc<Parent>>();
+
+    let later: Later<Rc<Parent>> = sp.resolve();
+    assert!(later.get().is_none());
+    sp.warm_up();
+    assert_eq!(later.get().unwrap().children.len(), 2);
+}
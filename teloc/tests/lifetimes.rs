@@ -160,3 +160,16 @@ fn test_resolve_transient_deps_with_greater_lifetime() {
 
     assert_eq!(singleton.dep, forked_singleton.dep);
 }
+
+fn resolve_num<'a, SP: Resolver<'a, &'a u8, Infer>, Infer>(sp: &'a SP) -> &'a u8 {
+    sp.resolve()
+}
+
+#[test]
+fn test_generic_bound_on_forked_provider() {
+    let provider = ServiceProvider::new().add_instance(10u8);
+    let forked_provider = provider.fork();
+
+    assert_eq!(resolve_num(&provider), &10u8);
+    assert_eq!(resolve_num(&forked_provider), &10u8);
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "log-support")]
+
+use log::kv::{Key, Value, VisitSource};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+use teloc::logging::{Logger, ScopedLogger};
+use teloc::{inject, Resolver, ServiceProvider};
+
+#[derive(Debug, PartialEq)]
+struct Entry {
+    level: Level,
+    target: String,
+    message: String,
+    fields: Vec<(String, String)>,
+    line: Option<u32>,
+}
+
+struct Capture(Mutex<Vec<Entry>>);
+
+struct Fields(Vec<(String, String)>);
+impl<'kvs> VisitSource<'kvs> for Fields {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        let mut fields = Fields(vec![]);
+        record.key_values().visit(&mut fields).unwrap();
+        self.0.lock().unwrap().push(Entry {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            fields: fields.0,
+            line: record.line(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+// Tests of this file share the global logger, so each test uses its own target.
+fn entries(target: &str) -> Vec<Entry> {
+    log::set_logger(&CAPTURE).ok();
+    log::set_max_level(LevelFilter::Trace);
+    let mut all = CAPTURE.0.lock().unwrap();
+    let (taken, rest) = all.drain(..).partition(|entry| entry.target == target);
+    *all = rest;
+    taken
+}
+
+struct Handler<'a> {
+    logger: &'a ScopedLogger,
+}
+#[inject]
+impl<'a> Handler<'a> {
+    fn new(logger: &'a ScopedLogger) -> Self {
+        Handler { logger }
+    }
+}
+
+fn field(key: &str, value: &str) -> (String, String) {
+    (key.to_string(), value.to_string())
+}
+
+#[test]
+fn test_root_logger() {
+    entries("root");
+    let sp = ServiceProvider::new().with_logger("root");
+
+    let logger: &Logger = sp.resolve();
+    let line = line!() + 1;
+    logger.warn(format_args!("disk at {}%", 90));
+    logger.debug("filtered by the logger");
+
+    assert_eq!(
+        entries("root"),
+        vec![Entry {
+            level: Level::Warn,
+            target: "root".to_string(),
+            message: "disk at 90%".to_string(),
+            fields: vec![],
+            line: Some(line),
+        }]
+    );
+}
+
+#[test]
+fn test_scoped_fields() {
+    entries("scoped");
+    let sp = ServiceProvider::new()
+        .with_logger("scoped")
+        .add_transient::<Handler>();
+
+    let first = sp
+        .fork()
+        .with_scoped_logger(&[("request_id", &1), ("user", &"ann")]);
+    let second = sp.fork().with_scoped_logger(&[("request_id", &2)]);
+    let handler: Handler = first.resolve();
+    handler.logger.info("first");
+    let handler: Handler = second.resolve();
+    handler.logger.error("second");
+
+    let fields: Vec<_> = entries("scoped").into_iter().map(|e| e.fields).collect();
+    assert_eq!(
+        fields,
+        vec![
+            vec![field("request_id", "1"), field("user", "ann")],
+            vec![field("request_id", "2")],
+        ]
+    );
+}
+
+#[test]
+fn test_scoped_logger_is_created_once_per_scope() {
+    let sp = ServiceProvider::new().with_logger("once");
+    let scope = sp.fork().with_scoped_logger(&[]);
+
+    let first: &ScopedLogger = scope.resolve();
+    let second: &ScopedLogger = scope.resolve();
+    assert!(std::ptr::eq(first, second));
+    assert_eq!(first.target(), "once");
+}
+
+#[test]
+fn test_with_field() {
+    entries("child");
+    let root = Logger::new("child");
+    root.with_field("job", "cleanup").info("done");
+    root.info("plain");
+
+    let fields: Vec<_> = entries("child").into_iter().map(|e| e.fields).collect();
+    assert_eq!(fields, vec![vec![field("job", "cleanup")], vec![]]);
+}
+
+#[test]
+fn test_dyn_resolver_logs_panic_path() {
+    use teloc::dynamic::{DynResolver, DynService};
+
+    struct Pool;
+    #[inject]
+    impl Pool {
+        fn new() -> Self {
+            panic!("database is down")
+        }
+    }
+
+    struct Repo;
+    #[inject]
+    impl Repo {
+        fn new(_: Pool) -> Self {
+            Repo
+        }
+    }
+
+    entries("teloc");
+    let resolver = DynResolver::new()
+        .with_path_tracking()
+        .add_service(DynService::transient::<Pool, _>())
+        .add_service(DynService::transient::<Repo, _>());
+    let resolver = std::panic::AssertUnwindSafe(resolver);
+    assert!(std::panic::catch_unwind(|| resolver.resolve::<Repo>()).is_err());
+
+    let messages: Vec<_> = entries("teloc").into_iter().map(|e| e.message).collect();
+    assert_eq!(
+        messages,
+        [format!(
+            "panicked while resolving {} -> {}",
+            std::any::type_name::<Repo>(),
+            std::any::type_name::<Pool>()
+        )]
+    );
+}
@@ -0,0 +1,61 @@
+use std::cell::Cell;
+use teloc::*;
+
+struct Config {
+    port: u16,
+}
+
+struct Server<'a> {
+    config: &'a Config,
+    started: &'a Cell<bool>,
+}
+#[inject]
+impl<'a> Server<'a> {
+    fn new(config: &'a Config, started: &'a Cell<bool>) -> Self {
+        Server { config, started }
+    }
+}
+impl Server<'_> {
+    async fn start(&self) -> u16 {
+        self.started.set(true);
+        self.config.port
+    }
+}
+
+type AppSp = ServiceProvider<
+    EmptyServiceProvider,
+    HCons<
+        TransientContainer<Server<'static>>,
+        HCons<InstanceContainer<Cell<bool>>, HCons<InstanceContainer<Config>, HNil>>,
+    >,
+>;
+
+fn build() -> AppSp {
+    ServiceProvider::new()
+        .add_instance(Config { port: 8080 })
+        .add_instance(Cell::new(false))
+        .add_transient::<Server>()
+}
+
+#[teloc::main(provider = build)]
+fn run(server: Server, started: &Cell<bool>) -> Result<u16, String> {
+    assert!(!started.get());
+    Ok(server.config.port)
+}
+
+#[teloc::main(provider = build, flavor = "current_thread")]
+async fn run_async(server: Server, started: &Cell<bool>) -> u16 {
+    let port = server.start().await;
+    assert!(started.get());
+    port
+}
+
+#[test]
+fn test_sync_main() {
+    assert_eq!(run(), Ok(8080));
+}
+
+#[test]
+fn test_async_main() {
+    assert_eq!(run_async(), 8080);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "devtools")]
+
+use std::any::type_name;
+use std::rc::Rc;
+use teloc::devtools::{LifetimeKind, ServiceEntry};
+use teloc::*;
+
+trait Storage {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Dependency)]
+struct MemoryStorage;
+impl Storage for MemoryStorage {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+impl From<Box<MemoryStorage>> for Box<dyn Storage> {
+    fn from(storage: Box<MemoryStorage>) -> Self {
+        storage
+    }
+}
+
+struct Port(u16);
+
+#[derive(Dependency)]
+struct Server {
+    port: Rc<Port>,
+    storage: Box<dyn Storage>,
+}
+
+fn entry<T: ?Sized>(lifetime: LifetimeKind, dependencies: &[&str]) -> ServiceEntry {
+    ServiceEntry {
+        type_name: type_name::<T>().to_string(),
+        lifetime,
+        dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_manifest() {
+    let sp = ServiceProvider::new()
+        .add_instance(8080u16)
+        .add_singleton_factory_unboxed(|(port,): (u16,)| Rc::new(Port(port)))
+        .add_transient_c::<Box<dyn Storage>, Box<MemoryStorage>>()
+        .add_transient_factory_unboxed(|(port,): (Rc<Port>,)| format!(":{}", port.0))
+        .add_transient::<Server>();
+
+    let manifest = sp.manifest();
+    assert_eq!(
+        manifest.services,
+        vec![
+            entry::<u16>(LifetimeKind::Instance, &[]),
+            entry::<Rc<Port>>(LifetimeKind::Singleton, &[type_name::<u16>()]),
+            entry::<Box<dyn Storage>>(LifetimeKind::Transient, &[]),
+            entry::<String>(LifetimeKind::Transient, &[type_name::<Rc<Port>>()]),
+            entry::<Server>(
+                LifetimeKind::Transient,
+                &[type_name::<Rc<Port>>(), type_name::<Box<dyn Storage>>()]
+            ),
+        ]
+    );
+
+    let server: Server = sp.resolve();
+    assert_eq!((server.port.0, server.storage.name()), (8080, "memory"));
+    let address: String = sp.resolve();
+    assert_eq!(address, ":8080");
+
+    let json = serde_json::to_value(&manifest).unwrap();
+    assert_eq!(json["services"][0]["lifetime"], "instance");
+}
+
+#[test]
+fn test_manifest_of_scope() {
+    let sp = ServiceProvider::new().add_singleton::<Rc<MemoryStorage>>();
+    let scope = sp.fork().add_instance(Port(80));
+    assert_eq!(
+        scope.manifest().services,
+        vec![entry::<Port>(LifetimeKind::Instance, &[])]
+    );
+}
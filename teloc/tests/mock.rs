@@ -0,0 +1,88 @@
+#![cfg(feature = "mockall")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use teloc::*;
+
+trait Mailer {
+    fn send(&self, to: &str) -> bool;
+}
+
+trait Clock {
+    fn now(&self) -> u64;
+}
+
+// Stands for a mock generated by `mockall`: expectations are set before the first call.
+#[derive(Default)]
+struct MockMailer {
+    accept: Vec<String>,
+    sent: Option<Rc<RefCell<Vec<String>>>>,
+}
+impl MockMailer {
+    fn expect_send(&mut self, to: &str) {
+        self.accept.push(to.to_string());
+    }
+}
+impl Mailer for MockMailer {
+    fn send(&self, to: &str) -> bool {
+        if let Some(sent) = &self.sent {
+            sent.borrow_mut().push(to.to_string());
+        }
+        self.accept.iter().any(|a| a == to)
+    }
+}
+
+#[derive(Default)]
+struct MockClock(u64);
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
+mockable!(Mailer => MockMailer, Clock => MockClock);
+
+struct Signup {
+    mailer: Box<dyn Mailer>,
+    clock: Box<dyn Clock>,
+}
+#[inject]
+impl Signup {
+    fn new(mailer: Box<dyn Mailer>, clock: Box<dyn Clock>) -> Self {
+        Signup { mailer, clock }
+    }
+}
+
+#[test]
+fn test_mocks_are_injected() {
+    let sent = Rc::new(RefCell::new(vec![]));
+    let recorder = sent.clone();
+    let sp = ServiceProvider::new()
+        .mock::<dyn Mailer>(move |m| {
+            m.expect_send("ann");
+            m.sent = Some(recorder.clone());
+        })
+        .mock::<dyn Clock>(|m| m.0 = 1_000)
+        .add_transient::<Signup>();
+
+    let signup: Signup = sp.resolve();
+    assert!(signup.mailer.send("ann"));
+    assert!(!signup.mailer.send("bob"));
+    assert_eq!(signup.clock.now(), 1_000);
+    assert_eq!(*sent.borrow(), vec!["ann", "bob"]);
+}
+
+#[test]
+fn test_new_mock_per_resolve() {
+    let configured = Rc::new(RefCell::new(0));
+    let counter = configured.clone();
+    let sp = ServiceProvider::new().mock::<dyn Clock>(move |m| {
+        *counter.borrow_mut() += 1;
+        m.0 = *counter.borrow() as u64;
+    });
+
+    let first: Box<dyn Clock> = sp.resolve();
+    let second: Box<dyn Clock> = sp.resolve();
+    assert_eq!((first.now(), second.now()), (1, 2));
+    assert_eq!(*configured.borrow(), 2);
+}
@@ -0,0 +1,57 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use teloc::*;
+
+struct TempFile {
+    path: String,
+    log: Rc<RefCell<Vec<String>>>,
+}
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(format!("drop {}", self.path));
+    }
+}
+
+#[test]
+fn test_on_drop_order() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let sp = ServiceProvider::new().add_instance(log.clone());
+
+    for id in 0..2 {
+        let log = log.clone();
+        let cleanup_log = log.clone();
+        let scope = sp
+            .fork()
+            .add_instance(TempFile {
+                path: format!("/tmp/{}", id),
+                log: log.clone(),
+            })
+            .on_drop(move || cleanup_log.borrow_mut().push(format!("remove /tmp/{}", id)));
+
+        let file: &TempFile = scope.resolve();
+        assert_eq!(file.path, format!("/tmp/{}", id));
+        // Callbacks of the previous scope have already been called.
+        assert_eq!(log.borrow().len(), id * 2);
+    }
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "remove /tmp/0",
+            "drop /tmp/0",
+            "remove /tmp/1",
+            "drop /tmp/1"
+        ]
+    );
+}
+
+#[test]
+fn test_on_drop_with_warm_up() {
+    let called = RefCell::new(false);
+    {
+        let sp = ServiceProvider::new().on_drop(|| *called.borrow_mut() = true);
+        sp.warm_up();
+        assert!(!*called.borrow());
+    }
+    assert!(*called.borrow());
+}
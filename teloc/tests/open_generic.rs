@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+use teloc::*;
+
+struct Db {
+    name: &'static str,
+}
+
+struct Repository<'a, T> {
+    db: &'a Db,
+    entity: PhantomData<T>,
+}
+#[inject]
+impl<'a, T> Repository<'a, T> {
+    fn new(db: &'a Db) -> Self {
+        Repository {
+            db,
+            entity: PhantomData,
+        }
+    }
+}
+
+open_generic!(Repositories => Repository<'a, _>);
+
+struct Handler<T> {
+    message: PhantomData<T>,
+}
+#[inject]
+impl<T> Handler<T> {
+    fn new() -> Self {
+        Handler {
+            message: PhantomData,
+        }
+    }
+}
+
+open_generic!(Handlers => Handler<_>);
+
+struct User;
+struct Order;
+
+struct Checkout<'a> {
+    users: Repository<'a, User>,
+    orders: Repository<'a, Order>,
+}
+#[inject]
+impl<'a> Checkout<'a> {
+    fn new(users: Repository<'a, User>, orders: Repository<'a, Order>) -> Self {
+        Checkout { users, orders }
+    }
+}
+
+#[test]
+fn test_resolve_members_of_family() {
+    let sp = ServiceProvider::new()
+        .add_instance(Db { name: "shop" })
+        .add_open_generic::<Repositories>()
+        .add_open_generic::<Handlers>()
+        .add_transient::<Checkout>();
+
+    let checkout: Checkout = sp.resolve();
+    assert_eq!(checkout.users.db.name, "shop");
+    assert!(std::ptr::eq(checkout.users.db, checkout.orders.db));
+
+    let _: Handler<User> = sp.resolve();
+    let _: Handler<Order> = sp.resolve();
+}
+
+#[test]
+fn test_family_from_parent() {
+    let sp = ServiceProvider::new().add_open_generic::<Repositories>();
+    let scope = sp.fork().add_instance(Db { name: "tenant" });
+
+    let users: Repository<User> = scope.resolve();
+    assert_eq!(users.db.name, "tenant");
+}
+
+trait Entity {
+    const TABLE: &'static str;
+}
+impl Entity for User {
+    const TABLE: &'static str = "users";
+}
+impl Entity for Order {
+    const TABLE: &'static str = "orders";
+}
+
+struct Table<'a, T> {
+    db: &'a Db,
+    entity: PhantomData<T>,
+}
+#[inject]
+impl<'a, T: Entity> Table<'a, T> {
+    fn new(db: &'a Db) -> Self {
+        Table {
+            db,
+            entity: PhantomData,
+        }
+    }
+}
+impl<T: Entity> Table<'_, T> {
+    fn path(&self) -> String {
+        format!("{}.{}", self.db.name, T::TABLE)
+    }
+}
+
+open_generic!(Tables => Table<'a, _> where _: Entity);
+
+type TablesSp = ServiceProvider<
+    EmptyServiceProvider,
+    HCons<OpenGenericContainer<Tables>, HCons<InstanceContainer<Db>, HNil>>,
+>;
+
+check_open_generic!(TablesSp: Table<'a, _> where _: Entity);
+
+#[test]
+fn test_constrained_family() {
+    let sp: TablesSp = ServiceProvider::new()
+        .add_instance(Db { name: "shop" })
+        .add_open_generic::<Tables>();
+
+    let users: Table<User> = sp.resolve();
+    let orders: Table<Order> = sp.resolve();
+    assert_eq!(users.path(), "shop.users");
+    assert_eq!(orders.path(), "shop.orders");
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "plugins")]
+
+use teloc::dynamic::DynResolver;
+
+struct Greeting(String);
+
+fn register(resolver: &mut DynResolver) {
+    resolver.register_transient(|_| Ok(Greeting(String::from("Hello from plugin!"))));
+}
+
+teloc::export_plugin!(register);
+
+#[test]
+fn test_exported_register() {
+    let mut resolver = DynResolver::new();
+    teloc_plugin_register(&mut resolver);
+    let greeting: Greeting = resolver.resolve().unwrap();
+    assert_eq!(greeting.0, "Hello from plugin!");
+}
+
+#[test]
+fn test_load_missing_plugin() {
+    let mut resolver = DynResolver::new();
+    let res = unsafe { resolver.load_plugin("libteloc_missing_plugin.so") };
+    assert!(res.is_err());
+}
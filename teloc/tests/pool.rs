@@ -0,0 +1,62 @@
+use futures::executor::block_on;
+use std::future::{ready, Future};
+use std::sync::atomic::{AtomicU32, Ordering};
+use teloc::{inject, AsyncPool, PooledResource, Resolver, ServiceProvider};
+
+#[derive(Default)]
+struct Pool {
+    acquired: AtomicU32,
+    released: AtomicU32,
+}
+
+struct Conn<'a> {
+    id: u32,
+    pool: &'a Pool,
+}
+impl Drop for Conn<'_> {
+    fn drop(&mut self) {
+        self.pool.released.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl<'a> AsyncPool for &'a Pool {
+    type Resource = Conn<'a>;
+    type Error = ();
+
+    fn acquire(&self) -> impl Future<Output = Result<Conn<'a>, ()>> {
+        let pool: &'a Pool = self;
+        let id = pool.acquired.fetch_add(1, Ordering::SeqCst);
+        ready(Ok(Conn { id, pool }))
+    }
+}
+
+struct Repository<'a, 'p> {
+    conn: &'a PooledResource<Conn<'p>>,
+}
+#[inject]
+impl<'a, 'p> Repository<'a, 'p> {
+    fn new(conn: &'a PooledResource<Conn<'p>>) -> Self {
+        Self { conn }
+    }
+}
+
+#[test]
+fn test_resource_per_scope() {
+    let pool = Pool::default();
+    let sp = ServiceProvider::new().add_transient::<Repository>();
+
+    block_on(async {
+        let scope1 = sp.fork().add_pooled_resource(&&pool).await.unwrap();
+        let scope2 = sp.fork().add_pooled_resource(&&pool).await.unwrap();
+
+        let a: Repository = scope1.resolve();
+        let b: Repository = scope1.resolve();
+        let c: Repository = scope2.resolve();
+
+        assert_eq!(a.conn.id, b.conn.id);
+        assert_ne!(a.conn.id, c.conn.id);
+    });
+
+    assert_eq!(pool.acquired.load(Ordering::SeqCst), 2);
+    assert_eq!(pool.released.load(Ordering::SeqCst), 2);
+}
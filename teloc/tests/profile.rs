@@ -0,0 +1,41 @@
+use teloc::profile::{Dev, Prod, Test};
+use teloc::*;
+
+struct Endpoint(&'static str);
+
+struct Retries(u8);
+#[inject]
+impl Retries {
+    fn new() -> Self {
+        Retries(3)
+    }
+}
+
+#[derive(Dependency)]
+struct Client {
+    endpoint: Endpoint,
+}
+
+impl From<Retries> for Endpoint {
+    fn from(_: Retries) -> Self {
+        Endpoint("retrying")
+    }
+}
+
+#[test]
+fn test_profiles() {
+    let sp = ServiceProvider::with_profile::<Dev>()
+        .add_instance_for::<Dev, _>("dev")
+        .add_instance_for::<Prod, _>("prod")
+        .add_singleton_for::<Prod, Retries>()
+        .add_transient_for::<Dev, Retries>();
+    let name: &&str = sp.resolve();
+    let retries: Retries = sp.resolve();
+    assert_eq!((*name, retries.0), ("dev", 3));
+
+    let sp = ServiceProvider::with_profile::<Test>()
+        .add_transient_c_for::<Test, Endpoint, Retries>()
+        .add_transient::<Client>();
+    let client: Client = sp.fork().resolve();
+    assert_eq!(client.endpoint.0, "retrying");
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use teloc::{inject, Projected, Resolver, ServiceProvider};
+
+struct Config {
+    name: &'static str,
+}
+
+struct Cache;
+#[inject]
+impl Cache {
+    fn new() -> Self {
+        Cache
+    }
+}
+
+struct Repository<'a> {
+    config: &'a Config,
+    cache: Arc<Cache>,
+}
+#[inject]
+impl<'a> Repository<'a> {
+    fn new(config: &'a Config, cache: Arc<Cache>) -> Self {
+        Repository { config, cache }
+    }
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn test_project() {
+    let sp = ServiceProvider::new()
+        .add_instance(Config { name: "app" })
+        .add_singleton::<Arc<Cache>>()
+        .add_transient::<Repository>();
+
+    let projected: Projected<(Repository, &Config, Arc<Cache>)> = sp.project();
+    assert_send(&projected);
+
+    let repository: &Repository = projected.resolve();
+    let config: &Config = projected.resolve();
+    let cache: Arc<Cache> = projected.resolve();
+    assert_eq!(repository.config.name, "app");
+    assert_eq!(config.name, "app");
+    assert!(Arc::ptr_eq(&repository.cache, &cache));
+}
+
+#[test]
+fn test_projected_new() {
+    let sp = ServiceProvider::new().add_instance(Config { name: "app" });
+
+    let (config,) = Projected::<(&Config,)>::new(&sp).into_inner();
+    assert_eq!(config.name, "app");
+}
@@ -0,0 +1,56 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use teloc::*;
+
+struct UserSession {
+    id: u32,
+}
+#[inject]
+impl UserSession {
+    fn new(ids: &Cell<u32>) -> Self {
+        ids.set(ids.get() + 1);
+        UserSession { id: ids.get() }
+    }
+}
+
+struct SessionManager<'a> {
+    sessions: Provider<'a, UserSession>,
+    names: Provider<'a, &'a String>,
+}
+#[inject]
+impl<'a> SessionManager<'a> {
+    fn new(sessions: Provider<'a, UserSession>, names: Provider<'a, &'a String>) -> Self {
+        SessionManager { sessions, names }
+    }
+}
+
+#[test]
+fn test_provider_in_singleton() {
+    // Singleton stores a borrow of the provider, so the provider must outlive itself.
+    let sp = Box::leak(Box::new(
+        ServiceProvider::new()
+            .add_instance(Cell::new(0))
+            .add_instance("name".to_string())
+            .add_transient::<UserSession>()
+            .add_singleton::<Rc<SessionManager>>(),
+    ));
+
+    let manager: Rc<SessionManager> = sp.resolve();
+    assert_eq!(manager.sessions.get().id, 1);
+    assert_eq!(manager.sessions.get().id, 2);
+    assert_eq!(manager.names.get(), "name");
+
+    let same: Rc<SessionManager> = sp.resolve();
+    assert!(Rc::ptr_eq(&manager, &same));
+    assert_eq!(same.sessions.get().id, 3);
+}
+
+#[test]
+fn test_provider_resolves_from_fork() {
+    let ids = Cell::new(10);
+    let sp = ServiceProvider::new().add_transient::<UserSession>();
+    let scope = sp.fork().add_instance(&ids);
+
+    let sessions: Provider<UserSession> = scope.resolve();
+    assert_eq!(sessions.get().id, 11);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "r2d2-support")]
+
+use r2d2::{ManageConnection, Pool};
+use std::sync::atomic::{AtomicU32, Ordering};
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct Connection {
+    id: u32,
+}
+
+#[derive(Default)]
+struct Manager {
+    next_id: AtomicU32,
+}
+impl ManageConnection for Manager {
+    type Connection = Connection;
+    type Error = std::io::Error;
+
+    fn connect(&self) -> Result<Connection, Self::Error> {
+        Ok(Connection {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst),
+        })
+    }
+
+    fn is_valid(&self, _: &mut Connection) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn has_broken(&self, _: &mut Connection) -> bool {
+        false
+    }
+}
+
+struct UserRepository<'a> {
+    conn: &'a Connection,
+}
+#[inject]
+impl<'a> UserRepository<'a> {
+    fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+}
+
+struct OrderRepository<'a> {
+    conn: &'a Connection,
+}
+#[inject]
+impl<'a> OrderRepository<'a> {
+    fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+}
+
+#[test]
+fn test_connection_per_scope() {
+    let pool = Pool::builder()
+        .max_size(2)
+        .build(Manager::default())
+        .unwrap();
+    let sp = ServiceProvider::new()
+        .add_transient::<UserRepository>()
+        .add_transient::<OrderRepository>();
+
+    let scope1 = sp.fork().add_pooled_connection(&pool).unwrap();
+    let scope2 = sp.fork().add_pooled_connection(&pool).unwrap();
+    let users: UserRepository = scope1.resolve();
+    let orders: OrderRepository = scope1.resolve();
+    let other: UserRepository = scope2.resolve();
+
+    assert!(std::ptr::eq(users.conn, orders.conn));
+    assert_ne!(users.conn.id, other.conn.id);
+    assert_eq!(pool.state().idle_connections, 0);
+}
+
+#[test]
+fn test_connection_returned_on_drop() {
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(Manager::default())
+        .unwrap();
+    let sp = ServiceProvider::new().add_transient::<UserRepository>();
+
+    {
+        let scope = sp.fork().add_pooled_connection(&pool).unwrap();
+        let _: UserRepository = scope.resolve();
+        assert_eq!(pool.state().idle_connections, 0);
+    }
+
+    assert_eq!(pool.state().idle_connections, 1);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::thread;
+use teloc::random::Rng;
+use teloc::{Resolver, ServiceProvider};
+
+fn draw(rng: &Rng) -> Vec<u64> {
+    (0..8).map(|_| rng.next_u64()).collect()
+}
+
+#[test]
+fn test_seeded_is_deterministic() {
+    let first = ServiceProvider::new().with_seeded_rng(7);
+    let second = ServiceProvider::new().with_seeded_rng(7);
+    let other = ServiceProvider::new().with_seeded_rng(8);
+
+    let first: &Rng = first.resolve();
+    let second: &Rng = second.resolve();
+    let other: &Rng = other.resolve();
+    let sequence = draw(first);
+    assert_eq!(sequence, draw(second));
+    assert_ne!(sequence, draw(other));
+}
+
+#[test]
+fn test_entropy_differs() {
+    let sp = ServiceProvider::new().with_rng();
+    let rng: &Rng = sp.resolve();
+    assert_ne!(draw(rng), draw(&Rng::from_entropy()));
+}
+
+#[test]
+fn test_ranges() {
+    let rng = Rng::seeded(1);
+    let mut seen = HashSet::new();
+    for _ in 0..1000 {
+        let n = rng.gen_range(10..16);
+        assert!((10..16).contains(&n));
+        seen.insert(n);
+
+        let f = rng.next_f64();
+        assert!((0.0..1.0).contains(&f));
+    }
+    assert_eq!(seen.len(), 6);
+    assert!(!rng.gen_bool(0.0));
+    assert!(rng.gen_bool(1.0));
+}
+
+#[test]
+#[should_panic(expected = "cannot sample an empty range")]
+fn test_empty_range() {
+    Rng::seeded(1).gen_range(5..5);
+}
+
+#[test]
+fn test_threads_do_not_repeat() {
+    let rng = Arc::new(Rng::seeded(3));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let rng = rng.clone();
+            thread::spawn(move || draw(&rng))
+        })
+        .collect();
+    let numbers: Vec<u64> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    let unique: HashSet<_> = numbers.iter().collect();
+    assert_eq!(unique.len(), numbers.len());
+}
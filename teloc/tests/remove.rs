@@ -0,0 +1,43 @@
+use std::rc::Rc;
+use teloc::*;
+
+trait Mailer {
+    fn name(&self) -> &'static str;
+}
+
+struct SmtpMailer;
+impl Mailer for SmtpMailer {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+}
+
+struct FakeMailer;
+impl Mailer for FakeMailer {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+}
+
+#[derive(Dependency)]
+struct Notifier {
+    mailer: Rc<dyn Mailer>,
+}
+
+#[test]
+fn test_remove_overrides_registration() {
+    let defaults = ServiceProvider::new()
+        .add_instance(Rc::new(SmtpMailer) as Rc<dyn Mailer>)
+        .add_transient::<Notifier>();
+
+    let notifier: Notifier = defaults.resolve();
+    assert_eq!(notifier.mailer.name(), "smtp");
+
+    let sp = defaults
+        .remove::<InstanceContainer<Rc<dyn Mailer>>, _>()
+        .add_instance(Rc::new(FakeMailer) as Rc<dyn Mailer>)
+        .check_unique();
+
+    let notifier: Notifier = sp.resolve();
+    assert_eq!(notifier.mailer.name(), "fake");
+}
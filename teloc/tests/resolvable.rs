@@ -0,0 +1,64 @@
+use std::rc::Rc;
+use teloc::*;
+
+struct Config {
+    name: &'static str,
+}
+
+struct Repository;
+#[inject]
+impl Repository {
+    fn new() -> Self {
+        Repository
+    }
+}
+
+#[derive(Dependency)]
+struct Controller {
+    repository: Rc<Repository>,
+}
+
+#[derive(Resolvable)]
+struct AppServices<'a> {
+    config: &'a Config,
+    repository: Rc<Repository>,
+    controller: Controller,
+}
+
+#[derive(Resolvable)]
+struct Names<'a>(&'a Config, &'a Config);
+
+#[derive(Resolvable)]
+struct Nothing;
+
+#[derive(Resolvable)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+fn name<'a, SP: Resolver<'a, AppServices<'a>, Infer>, Infer>(sp: &'a SP) -> &'static str {
+    let app: AppServices = sp.resolve();
+    app.config.name
+}
+
+#[test]
+fn test_resolvable() {
+    let sp = ServiceProvider::new()
+        .add_instance(Config { name: "app" })
+        .add_singleton::<Rc<Repository>>()
+        .add_transient::<Controller>();
+
+    let app: AppServices = sp.resolve();
+    assert_eq!(app.config.name, "app");
+    assert!(Rc::ptr_eq(&app.repository, &app.controller.repository));
+
+    let Names(first, second) = sp.resolve();
+    assert_eq!((first.name, second.name), ("app", "app"));
+
+    let Nothing = sp.resolve();
+
+    let wrapper: Wrapper<Rc<Repository>> = sp.resolve();
+    assert!(Rc::ptr_eq(&wrapper.inner, &app.repository));
+
+    assert_eq!(name(&sp.fork()), "app");
+}
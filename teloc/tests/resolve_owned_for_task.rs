@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use std::thread;
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct Counter {
+    start: u32,
+}
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter { start: 5 }
+    }
+}
+
+#[test]
+fn test_singleton_arc_moves_into_thread() {
+    let sp = ServiceProvider::new().add_singleton::<Arc<Counter>>();
+
+    let counter: Arc<Counter> = sp.resolve_owned_for_task();
+    let start = thread::spawn(move || counter.start).join().unwrap();
+    let same: Arc<Counter> = sp.resolve_owned_for_task();
+
+    assert_eq!(start, same.start);
+    assert_eq!(Arc::strong_count(&same), 2);
+}
+
+#[test]
+fn test_transient_moves_into_thread() {
+    let sp = ServiceProvider::new().add_transient::<Counter>();
+
+    let counter: Counter = sp.resolve_owned_for_task();
+    let start = thread::spawn(move || counter.start).join().unwrap();
+
+    assert_eq!(start, 5);
+}
@@ -0,0 +1,43 @@
+use std::rc::Rc;
+use teloc::*;
+
+struct Config {
+    name: &'static str,
+}
+
+struct Counter(u32);
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter(1)
+    }
+}
+
+#[derive(Dependency)]
+struct Controller {
+    counter: Rc<Counter>,
+}
+
+fn check<'a, SP: Resolver<'a, (&'a Config, Rc<Counter>), Infer>, Infer>(sp: &'a SP) -> u32 {
+    let (config, counter) = sp.resolve();
+    assert_eq!(config.name, "app");
+    counter.0
+}
+
+#[test]
+fn test_resolve_tuple() {
+    let sp = ServiceProvider::new()
+        .add_instance(Config { name: "app" })
+        .add_singleton::<Rc<Counter>>()
+        .add_transient::<Controller>();
+
+    let (config, controller, counter): (&Config, Controller, Rc<Counter>) = sp.resolve();
+    assert_eq!(config.name, "app");
+    assert!(Rc::ptr_eq(&controller.counter, &counter));
+
+    let (single,): (&Config,) = sp.resolve();
+    assert_eq!(single.name, "app");
+
+    assert_eq!(check(&sp), 1);
+    assert_eq!(check(&sp.fork()), 1);
+}
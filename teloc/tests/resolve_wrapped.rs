@@ -0,0 +1,38 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use teloc::*;
+
+struct Counter(u8);
+#[inject]
+impl Counter {
+    fn new(start: &u8) -> Self {
+        Counter(*start)
+    }
+}
+
+#[derive(Dependency)]
+struct Controller {
+    boxed: Box<Counter>,
+    shared: Rc<Counter>,
+    atomic: Arc<Counter>,
+}
+
+#[test]
+fn test_resolve_wrapped() {
+    let sp = ServiceProvider::new()
+        .add_instance(7u8)
+        .add_transient::<Counter>()
+        .add_transient::<Controller>();
+
+    let controller: Controller = sp.resolve();
+    assert_eq!(controller.boxed.0, 7);
+    assert_eq!(controller.shared.0, 7);
+    assert_eq!(controller.atomic.0, 7);
+
+    let first: Rc<Counter> = sp.fork().resolve();
+    let second: Rc<Counter> = sp.resolve();
+    assert!(!Rc::ptr_eq(&first, &second));
+
+    let nested: Box<Rc<Counter>> = sp.resolve();
+    assert_eq!(nested.0, 7);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "rocket-support")]
+
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::{get, routes};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use teloc::rocket_support::{Inject, TelocFairing};
+use teloc::{inject, Resolver, ServiceProvider};
+
+struct Counter(AtomicU64);
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter(AtomicU64::new(0))
+    }
+}
+
+struct RequestId(u64);
+
+struct Controller {
+    id: Arc<RequestId>,
+    path: Arc<String>,
+}
+#[inject]
+impl Controller {
+    fn new(id: Arc<RequestId>, path: Arc<String>) -> Self {
+        Controller { id, path }
+    }
+}
+
+#[get("/scope")]
+fn scope(first: Inject<Controller>, second: Inject<Controller>) -> String {
+    format!(
+        "{} {} {}",
+        first.path,
+        first.id.0,
+        Arc::ptr_eq(&first.id, &second.id)
+    )
+}
+
+#[get("/missing")]
+fn missing(_counter: Inject<Arc<Counter>>) -> &'static str {
+    "unreachable"
+}
+
+async fn client() -> Client {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Counter>>()
+            .add_transient::<Controller>(),
+    );
+    let rocket = rocket::build().mount("/", routes![scope, missing]).attach(
+        TelocFairing::new(sp, |scope, req| {
+            let counter: Arc<Counter> = scope.resolve();
+            let id = counter.0.fetch_add(1, Ordering::SeqCst);
+            scope
+                .add_instance(Arc::new(RequestId(id)))
+                .add_instance(Arc::new(req.uri().path().to_string()))
+        })
+        .inject::<Controller, _>(|scope| scope.resolve()),
+    );
+    Client::tracked(rocket).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn test_inject_from_request_scope() {
+    let client = client().await;
+    let first = client.get("/scope").dispatch().await;
+    assert_eq!(first.into_string().await.unwrap(), "/scope 0 true");
+    let second = client.get("/scope").dispatch().await;
+    assert_eq!(second.into_string().await.unwrap(), "/scope 1 true");
+}
+
+#[rocket::async_test]
+async fn test_not_registered_type() {
+    let client = client().await;
+    let res = client.get("/missing").dispatch().await;
+    assert_eq!(res.status(), Status::InternalServerError);
+}
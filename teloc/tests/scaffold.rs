@@ -0,0 +1,57 @@
+use teloc::{inject, Dependency, Resolver, ServiceProvider};
+
+teloc::scaffold! {
+    pub trait Greeter {
+        fn greet(&self, name: &str) -> String;
+        fn farewell(&self) -> String {
+            "Bye!".to_string()
+        }
+    }
+    impl EnglishGreeter;
+}
+
+pub struct EnglishGreeter;
+#[inject]
+impl EnglishGreeter {
+    fn new() -> Self {
+        EnglishGreeter
+    }
+}
+impl Greeter for EnglishGreeter {
+    fn greet(&self, name: &str) -> String {
+        format!("Hello, {}!", name)
+    }
+}
+
+#[derive(Dependency)]
+struct Controller {
+    greeter: Box<dyn Greeter>,
+}
+
+#[test]
+fn test_registration() {
+    let sp = ServiceProvider::new()
+        .add_greeter()
+        .add_transient::<Controller>();
+    let controller: Controller = sp.resolve();
+
+    assert_eq!(controller.greeter.greet("Bob"), "Hello, Bob!");
+    assert_eq!(controller.greeter.farewell(), "Bye!");
+}
+
+#[test]
+fn test_stub_default_method() {
+    let sp = ServiceProvider::new().add_greeter_stub();
+    let greeter: Box<dyn Greeter> = sp.resolve();
+
+    assert_eq!(greeter.farewell(), "Bye!");
+}
+
+#[test]
+#[should_panic(expected = "`Greeter::greet` is not implemented by the stub")]
+fn test_stub_panics() {
+    let sp = ServiceProvider::new().add_greeter_stub();
+    let greeter: Box<dyn Greeter> = sp.resolve();
+
+    greeter.greet("Bob");
+}
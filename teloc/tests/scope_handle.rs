@@ -0,0 +1,37 @@
+use std::rc::Rc;
+use teloc::*;
+
+struct Config {
+    name: &'static str,
+}
+
+struct RequestId(u32);
+
+struct Locator<'a> {
+    scope: ScopeHandle<'a>,
+}
+#[inject]
+impl<'a> Locator<'a> {
+    fn new(scope: ScopeHandle<'a>) -> Self {
+        Locator { scope }
+    }
+}
+
+#[test]
+fn test_scope_handle() {
+    let sp = ServiceProvider::new()
+        .add_instance(Rc::new(Config { name: "app" }))
+        .add_scope_handle::<(Rc<Config>, Rc<RequestId>)>()
+        .add_transient::<Locator>();
+
+    let scope = sp.fork().add_instance(Rc::new(RequestId(7)));
+    let locator: Locator = scope.resolve();
+    assert_eq!(locator.scope.resolve::<Rc<Config>>().unwrap().name, "app");
+    assert!(locator.scope.resolve::<Rc<String>>().is_none());
+    assert_eq!(locator.scope.resolve::<Rc<RequestId>>().unwrap().0, 7);
+
+    let handle: ScopeHandle = scope.resolve();
+    let first: Rc<Config> = handle.resolve().unwrap();
+    let second: Rc<Config> = handle.resolve().unwrap();
+    assert!(Rc::ptr_eq(&first, &second));
+}
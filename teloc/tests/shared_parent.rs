@@ -0,0 +1,79 @@
+#![cfg(feature = "sync")]
+
+use std::sync::Arc;
+use teloc::*;
+
+struct Config {
+    name: &'static str,
+}
+#[inject]
+impl Config {
+    fn new() -> Self {
+        Config { name: "app" }
+    }
+}
+
+struct Request(u32);
+
+#[derive(Dependency)]
+struct Handler<'a> {
+    config: Arc<Config>,
+    request: &'a Request,
+}
+
+type Shared = HList![Arc<Config>];
+
+fn handle(scope: SharedFork<Shared>) -> (Arc<Config>, u32) {
+    let scope = scope.add_instance(Request(3)).add_transient::<Handler>();
+    let handler: Handler = scope.resolve();
+    (handler.config, handler.request.0)
+}
+
+#[test]
+fn test_shared_parent_resolves_singletons_of_provider() {
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Config>>());
+    let config: Arc<Config> = sp.resolve();
+    let parent = SharedParent::builder(sp).share(|sp| sp.resolve()).build();
+
+    let (first, request) = handle(parent.fork());
+    let (second, _) = handle(parent.fork());
+    assert_eq!(first.name, "app");
+    assert_eq!(request, 3);
+    assert!(Arc::ptr_eq(&first, &config));
+    assert!(Arc::ptr_eq(&second, &config));
+}
+
+#[test]
+fn test_shared_parent_type_does_not_depend_on_provider() {
+    let small = Arc::new(ServiceProvider::new().add_singleton::<Arc<Config>>());
+    let large = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Config>>()
+            .add_instance(5u8)
+            .add_instance(Request(1)),
+    );
+    let parents: Vec<SharedParent<Shared>> = vec![
+        SharedParent::builder(small)
+            .share(|sp| sp.resolve())
+            .build(),
+        SharedParent::builder(large)
+            .share(|sp| sp.resolve())
+            .build(),
+    ];
+
+    for parent in &parents {
+        assert_eq!(handle(parent.fork()).0.name, "app");
+    }
+}
+
+#[tokio::test]
+async fn test_shared_fork_moves_into_task() {
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Config>>());
+    let parent = SharedParent::builder(sp).share(|sp| sp.resolve()).build();
+
+    let scope = parent.fork();
+    let name = tokio::spawn(async move { handle(scope).0.name })
+        .await
+        .unwrap();
+    assert_eq!(name, "app");
+}
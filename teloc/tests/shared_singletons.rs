@@ -0,0 +1,70 @@
+#![cfg(all(feature = "shared-singletons", feature = "sync"))]
+
+use std::sync::Arc;
+use std::thread;
+use teloc::*;
+use uuid::Uuid;
+
+struct Config {
+    id: Uuid,
+}
+#[inject]
+impl Config {
+    fn new() -> Self {
+        Config { id: Uuid::new_v4() }
+    }
+}
+
+#[derive(Dependency)]
+struct Handler {
+    config: Arc<Config>,
+}
+
+struct Port(u16);
+
+#[test]
+fn test_clones_share_singletons() {
+    let sp = ServiceProvider::new()
+        .add_singleton::<Arc<Config>>()
+        .add_transient::<Handler>();
+    let clone = sp.clone();
+
+    let first: Handler = clone.resolve();
+    let second: Handler = sp.resolve();
+    assert!(Arc::ptr_eq(&first.config, &second.config));
+}
+
+#[test]
+fn test_clone_before_resolving() {
+    let sp = ServiceProvider::new().add_singleton::<Arc<Config>>();
+    let config: Arc<Config> = sp.resolve();
+
+    let clone = sp.clone();
+    let config2: &Arc<Config> = clone.resolve();
+    assert_eq!(config.id, config2.id);
+}
+
+#[test]
+fn test_clone_per_worker() {
+    let sp = ServiceProvider::new()
+        .add_instance(8080u16)
+        .add_singleton_factory_unboxed(|(port,): (u16,)| Arc::new(Port(port)))
+        .add_singleton::<Arc<Config>>()
+        .add_transient::<Handler>();
+
+    let workers = (0..4)
+        .map(|_| {
+            let sp = sp.clone();
+            thread::spawn(move || {
+                let handler: Handler = sp.resolve();
+                let port: Arc<Port> = sp.resolve();
+                (handler.config.id, port.0)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let config: Arc<Config> = sp.resolve();
+    for worker in workers {
+        assert_eq!(worker.join().unwrap(), (config.id, 8080));
+    }
+}
@@ -0,0 +1,100 @@
+use std::cell::Cell;
+use teloc::*;
+
+trait Mailer {
+    fn send(&self, to: &str) -> String;
+}
+
+#[derive(Default)]
+struct FakeMailer;
+impl Mailer for FakeMailer {
+    fn send(&self, to: &str) -> String {
+        format!("fake mail to {}", to)
+    }
+}
+
+trait Audit {
+    fn record(&self) -> u32;
+}
+
+#[derive(Default)]
+struct NoAudit;
+impl Audit for NoAudit {
+    fn record(&self) -> u32 {
+        0
+    }
+}
+
+#[derive(Default)]
+struct Config {
+    retries: u8,
+}
+
+#[derive(Default)]
+struct Counter(Cell<u32>);
+
+#[derive(Dependency)]
+#[dependency(lifetime = "singleton")]
+struct Templates;
+
+struct Signup<'a> {
+    config: &'a Config,
+    counter: &'a Counter,
+    mailer: Box<dyn Mailer>,
+    audit: Box<dyn Audit>,
+}
+#[inject]
+impl<'a> Signup<'a> {
+    fn new(
+        config: &'a Config,
+        counter: &'a Counter,
+        mailer: Box<dyn Mailer>,
+        audit: Box<dyn Audit>,
+        _: &Templates,
+    ) -> Self {
+        Signup {
+            config,
+            counter,
+            mailer,
+            audit,
+        }
+    }
+}
+impl Signup<'_> {
+    fn run(&self, to: &str) -> String {
+        self.counter.0.set(self.counter.0.get() + 1);
+        self.mailer.send(to)
+    }
+}
+
+#[test]
+fn test_all_sections() {
+    let sp = test_provider! {
+        services: { Templates, transient Signup },
+        defaults: { Config, Counter },
+        overrides: { Mailer => FakeMailer, Audit => NoAudit },
+    };
+
+    let signup: Signup = sp.resolve();
+    assert_eq!(signup.run("ann"), "fake mail to ann");
+    assert_eq!(signup.config.retries, 0);
+    assert_eq!(signup.audit.record(), 0);
+
+    let counter: &Counter = sp.resolve();
+    assert_eq!(counter.0.get(), 1);
+}
+
+#[test]
+fn test_optional_sections() {
+    let sp = test_provider! {
+        overrides: { Mailer => FakeMailer }
+    };
+    let mailer: Box<dyn Mailer> = sp.resolve();
+    assert_eq!(mailer.send("bob"), "fake mail to bob");
+
+    let sp = test_provider! { defaults: { Config } };
+    let config: &Config = sp.resolve();
+    assert_eq!(config.retries, 0);
+
+    let _empty: ServiceProvider<EmptyServiceProvider, HNil> = test_provider! {};
+}
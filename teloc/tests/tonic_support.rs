@@ -0,0 +1,104 @@
+#![cfg(feature = "tonic-support")]
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use teloc::{inject, Dependency, DiGrpcService, DiInterceptor, Resolver, ServiceProvider};
+use tonic::service::Interceptor;
+use tonic::{Code, Request, Status};
+
+struct Repository;
+#[inject]
+impl Repository {
+    fn new() -> Self {
+        Repository
+    }
+}
+
+struct ServiceImpl {
+    repository: Arc<Repository>,
+}
+#[inject]
+impl ServiceImpl {
+    fn new(repository: Arc<Repository>) -> Self {
+        ServiceImpl { repository }
+    }
+}
+
+struct Peer(Option<SocketAddr>);
+#[inject]
+impl Peer {
+    fn new(addr: Option<SocketAddr>) -> Self {
+        Peer(addr)
+    }
+}
+
+#[test]
+fn test_service_built_from_provider() {
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Repository>>());
+    let service = DiGrpcService::new(&sp, ServiceImpl::init);
+    let same: Arc<Repository> = teloc::Resolver::resolve(&*sp);
+    assert!(Arc::ptr_eq(&service.repository, &same));
+
+    let err = service
+        .resolve::<Arc<Repository>, _>(&Request::new(()))
+        .err()
+        .unwrap();
+    assert_eq!(err.code(), Code::Internal);
+}
+
+#[test]
+fn test_peer_addr_in_rpc_scope() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Repository>>()
+            .add_transient::<Peer>(),
+    );
+    let service = DiGrpcService::builder(&sp)
+        .inject::<Peer, _>(|scope| scope.resolve())
+        .build(ServiceImpl::init);
+
+    let peer = service.resolve::<Peer, _>(&Request::new(())).unwrap();
+    assert_eq!(peer.0, None);
+}
+
+struct Metrics(AtomicUsize);
+
+#[allow(clippy::result_large_err)]
+fn check_token(
+    request: Request<()>,
+    metrics: Arc<Metrics>,
+    token: &str,
+) -> Result<Request<()>, Status> {
+    metrics.0.fetch_add(1, Ordering::SeqCst);
+    match request.metadata().get("token") {
+        Some(value) if value == token => Ok(request),
+        _ => Err(Status::unauthenticated("invalid token")),
+    }
+}
+
+#[test]
+#[allow(clippy::result_large_err)]
+fn test_interceptor_with_dependencies() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_instance(Arc::new(Metrics(AtomicUsize::new(0))))
+            .add_instance("secret".to_string()),
+    );
+
+    let mut interceptor = DiInterceptor::new(sp.clone(), |sp, request| {
+        let token: &String = sp.resolve();
+        check_token(request, sp.resolve(), token)
+    });
+
+    let mut request = Request::new(());
+    request
+        .metadata_mut()
+        .insert("token", "secret".parse().unwrap());
+    assert!(interceptor.call(request).is_ok());
+    let err = interceptor.call(Request::new(())).err().unwrap();
+    assert_eq!(err.code(), Code::Unauthenticated);
+
+    let metrics: Arc<Metrics> = sp.resolve();
+    assert_eq!(metrics.0.load(Ordering::SeqCst), 2);
+}
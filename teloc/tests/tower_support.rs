@@ -0,0 +1,59 @@
+#![cfg(feature = "tower-support")]
+
+use http::{Extensions, Request};
+use std::convert::Infallible;
+use std::sync::Arc;
+use teloc::tower_support::{ProvideLayer, Scoped, ScopedError};
+use teloc::{inject, Resolver, ServiceProvider};
+use tower::{service_fn, Layer, ServiceExt};
+
+struct RequestId(u64);
+
+struct Controller {
+    id: Arc<RequestId>,
+}
+#[inject]
+impl Controller {
+    fn new(id: Arc<RequestId>) -> Self {
+        Controller { id }
+    }
+}
+
+#[tokio::test]
+async fn test_scope_shared_in_request() {
+    let sp = Arc::new(ServiceProvider::new().add_transient::<Controller>());
+    let layer = ProvideLayer::new(sp, |scope, parts| {
+        scope.add_instance(Arc::new(RequestId(parts.uri.path().len() as u64)))
+    })
+    .inject::<Controller, _>(|scope| scope.resolve());
+
+    let service = layer.layer(service_fn(|req: Request<()>| async move {
+        let first = Scoped::<Controller>::from_extensions(req.extensions()).unwrap();
+        let second = Scoped::<Controller>::from_extensions(req.extensions()).unwrap();
+        Ok::<_, Infallible>((first.id.0, Arc::ptr_eq(&first.id, &second.id)))
+    }));
+
+    let req = Request::get("/abc").body(()).unwrap();
+    assert_eq!(service.oneshot(req).await.unwrap(), (4, true));
+}
+
+#[tokio::test]
+async fn test_errors() {
+    assert_eq!(
+        Scoped::<u8>::from_extensions(&Extensions::new()).err(),
+        Some(ScopedError::MissingLayer)
+    );
+
+    let sp = Arc::new(ServiceProvider::new());
+    let layer = ProvideLayer::new(sp, |scope, _| scope);
+    let service = layer.layer(service_fn(|req: Request<()>| async move {
+        Ok::<_, Infallible>(Scoped::<u8>::from_extensions(req.extensions()).err())
+    }));
+
+    let err = service.oneshot(Request::new(())).await.unwrap().unwrap();
+    assert_eq!(err, ScopedError::NotRegistered("u8"));
+    assert_eq!(
+        err.to_string(),
+        "`u8` is not registered in the `ProvideLayer`"
+    );
+}
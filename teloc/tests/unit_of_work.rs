@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+use futures::executor::block_on;
+use std::cell::RefCell;
+use std::future::{ready, Future};
+use std::rc::Rc;
+use teloc::{inject, Resolver, ServiceProvider, Transaction, UnitOfWork};
+
+#[derive(Default)]
+struct Log(RefCell<Vec<&'static str>>);
+
+struct FakeTx {
+    log: Rc<Log>,
+}
+impl Transaction for FakeTx {
+    type Error = ();
+
+    fn commit(self) -> impl Future<Output = Result<(), ()>> {
+        self.log.0.borrow_mut().push("commit");
+        ready(Ok(()))
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<(), ()>> {
+        self.log.0.borrow_mut().push("rollback");
+        ready(Ok(()))
+    }
+}
+impl Drop for FakeTx {
+    fn drop(&mut self) {
+        self.log.0.borrow_mut().push("drop");
+    }
+}
+
+struct Repository<'a> {
+    uow: &'a UnitOfWork<FakeTx>,
+}
+#[inject]
+impl<'a> Repository<'a> {
+    fn new(uow: &'a UnitOfWork<FakeTx>) -> Self {
+        Self { uow }
+    }
+}
+
+#[test]
+fn test_commit() {
+    let log = Rc::new(Log::default());
+    let sp = ServiceProvider::new().add_transient::<Repository>();
+    let scope = sp.fork().add_unit_of_work(FakeTx { log: log.clone() });
+
+    let repository: Repository = scope.resolve();
+    block_on(repository.uow.commit()).unwrap();
+
+    assert!(repository.uow.is_completed());
+    assert_eq!(*log.0.borrow(), ["commit", "drop"]);
+}
+
+#[test]
+fn test_same_transaction_in_scope() {
+    let log = Rc::new(Log::default());
+    let sp = ServiceProvider::new().add_transient::<Repository>();
+    let scope1 = sp.fork().add_unit_of_work(FakeTx { log: log.clone() });
+    let scope2 = sp.fork().add_unit_of_work(FakeTx { log: log.clone() });
+
+    let a: Repository = scope1.resolve();
+    let b: Repository = scope1.resolve();
+    let c: Repository = scope2.resolve();
+
+    assert!(std::ptr::eq(a.uow, b.uow));
+    assert!(!std::ptr::eq(a.uow, c.uow));
+}
+
+#[test]
+fn test_drop_without_commit() {
+    let log = Rc::new(Log::default());
+    {
+        let sp = ServiceProvider::new().add_transient::<Repository>();
+        let scope = sp.fork().add_unit_of_work(FakeTx { log: log.clone() });
+        let _: Repository = scope.resolve();
+    }
+    assert_eq!(*log.0.borrow(), ["drop"]);
+}
+
+#[test]
+#[should_panic(expected = "Unit of work was already completed")]
+fn test_double_commit() {
+    let log = Rc::new(Log::default());
+    let uow = UnitOfWork::new(FakeTx { log: log.clone() });
+    block_on(uow.commit()).unwrap();
+    block_on(uow.rollback()).unwrap();
+}
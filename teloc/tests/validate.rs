@@ -0,0 +1,120 @@
+use std::cell::Cell;
+use teloc::*;
+
+struct ServerConfig {
+    port: u16,
+    host: &'static str,
+}
+impl Validate for ServerConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.port == 0 {
+            return Err("port must not be 0".into());
+        }
+        if self.host.is_empty() {
+            return Err("host must not be empty".into());
+        }
+        Ok(())
+    }
+}
+
+struct Limits(u32);
+impl Validate for Limits {
+    fn validate(&self) -> Result<(), String> {
+        if self.0 > 100 {
+            return Err(format!("limit {} is above 100", self.0));
+        }
+        Ok(())
+    }
+}
+
+struct Server;
+#[inject]
+impl Server {
+    fn new(created: &Cell<bool>) -> Self {
+        created.set(true);
+        Server
+    }
+}
+
+#[test]
+fn test_valid() {
+    let created = Cell::new(false);
+    let sp = ServiceProvider::new()
+        .add_instance(&created)
+        .add_instance(ServerConfig {
+            port: 80,
+            host: "localhost",
+        })
+        .add_instance(Limits(10))
+        .add_singleton::<Server>()
+        .add_validation::<&ServerConfig>()
+        .add_validation::<&Limits>();
+
+    assert_eq!(sp.try_warm_up(), Ok(()));
+    assert!(created.get());
+}
+
+#[test]
+fn test_failures_are_aggregated() {
+    let created = Cell::new(false);
+    let sp = ServiceProvider::new()
+        .add_instance(&created)
+        .add_instance(ServerConfig {
+            port: 0,
+            host: "localhost",
+        })
+        .add_instance(Limits(500))
+        .add_singleton::<Server>()
+        .add_validation::<&ServerConfig>()
+        .add_validation::<&Limits>();
+
+    let err = match sp.try_warm_up() {
+        Err(StartupError::Validation(err)) => err,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    let messages: Vec<&str> = err.failures().iter().map(|(_, m)| m.as_str()).collect();
+    assert_eq!(messages, ["port must not be 0", "limit 500 is above 100"]);
+    assert!(err.failures()[0].0.ends_with("ServerConfig"));
+    assert!(err
+        .to_string()
+        .starts_with("2 validation(s) failed at startup:\n  - "));
+    assert!(!created.get(), "singletons must not be created");
+}
+
+#[test]
+fn test_validate_singleton() {
+    struct Pool {
+        size: u32,
+    }
+    #[inject]
+    impl Pool {
+        fn new() -> Self {
+            Pool { size: 200 }
+        }
+    }
+    impl Validate for Pool {
+        fn validate(&self) -> Result<(), String> {
+            Limits(self.size).validate()
+        }
+    }
+
+    let sp = ServiceProvider::new()
+        .add_singleton::<Pool>()
+        .add_validation::<&Pool>();
+
+    let err = match sp.try_warm_up() {
+        Err(StartupError::Validation(err)) => err,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    assert_eq!(err.failures()[0].1, "limit 200 is above 100");
+}
+
+#[test]
+#[should_panic(expected = "validation(s) failed at startup")]
+fn test_warm_up_panics() {
+    let sp = ServiceProvider::new()
+        .add_instance(Limits(1000))
+        .add_validation::<&Limits>();
+
+    sp.warm_up();
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "std")]
+
+use futures::executor::block_on;
+use std::cell::Cell;
+use std::rc::Rc;
+use teloc::reexport::frunk::HNil;
+use teloc::{inject, Deferred, Dependency, Resolver, ServiceProvider};
+
+struct Late {
+    id: u32,
+}
+#[inject]
+impl Late {
+    fn new(counter: &Cell<u32>) -> Self {
+        counter.set(counter.get() + 1);
+        Late { id: counter.get() }
+    }
+}
+
+struct Early {
+    late: Deferred<Rc<Late>>,
+}
+#[inject]
+impl Early {
+    fn new(late: Deferred<Rc<Late>>) -> Self {
+        Early { late }
+    }
+}
+
+#[derive(Dependency)]
+struct FromFactory;
+
+#[test]
+fn test_warm_up_creates_singletons() {
+    let counter = Cell::new(0);
+    let sp = ServiceProvider::new()
+        .add_instance(&counter)
+        .add_singleton::<Late>()
+        .add_singleton_factory(|_: HNil| 5u8)
+        .add_transient::<FromFactory>();
+
+    sp.warm_up();
+    sp.warm_up();
+    let late: &Late = sp.resolve();
+
+    assert_eq!(counter.get(), 1);
+    assert_eq!(late.id, 1);
+}
+
+#[test]
+fn test_deferred_is_set_on_warm_up() {
+    let counter = Cell::new(0);
+    let sp = ServiceProvider::new()
+        .add_instance(&counter)
+        .add_singleton::<Rc<Late>>()
+        .add_deferred::<Rc<Late>>()
+        .add_transient::<Early>();
+
+    let early: Early = sp.resolve();
+    let waiting = early.late.clone();
+    assert!(early.late.get().is_none());
+
+    sp.warm_up();
+
+    let late: Rc<Late> = sp.resolve();
+    assert!(Rc::ptr_eq(early.late.get().unwrap(), &late));
+    assert!(Rc::ptr_eq(&block_on(waiting), &late));
+}
+
+#[test]
+fn test_deferred_future_wakes_on_set() {
+    let deferred = Deferred::<u32>::new();
+    let waiting = deferred.clone();
+    let handle = std::thread::spawn(move || block_on(waiting));
+
+    deferred.set(42).unwrap();
+
+    assert_eq!(handle.join().unwrap(), 42);
+    assert_eq!(deferred.set(1), Err(1));
+}
+
+#[test]
+fn test_warm_up_fork() {
+    let counter = Cell::new(0);
+    let sp = ServiceProvider::new().add_instance(&counter);
+    let scope = sp.fork().add_singleton::<Late>();
+
+    scope.warm_up();
+
+    assert_eq!(counter.get(), 1);
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "warp-support")]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use teloc::warp_support::{inject, with_provider, with_scope};
+use teloc::{inject, Resolver, ServiceProvider};
+use warp::Filter;
+
+struct Counter(AtomicU64);
+#[inject]
+impl Counter {
+    fn new() -> Self {
+        Counter(AtomicU64::new(0))
+    }
+}
+
+struct RequestId(u64);
+
+struct Controller<'a> {
+    id: &'a RequestId,
+}
+#[inject]
+impl<'a> Controller<'a> {
+    fn new(id: &'a RequestId) -> Self {
+        Controller { id }
+    }
+}
+
+#[tokio::test]
+async fn test_scope_per_request() {
+    let sp = Arc::new(
+        ServiceProvider::new()
+            .add_singleton::<Arc<Counter>>()
+            .add_transient::<Controller>(),
+    );
+    let filter = with_scope(sp, |scope| {
+        let counter: Arc<Counter> = scope.resolve();
+        let id = counter.0.fetch_add(1, Ordering::SeqCst);
+        scope.add_instance(RequestId(id))
+    })
+    .map(|scope: Arc<_>| {
+        let controller: Controller = Resolver::resolve(&*scope);
+        controller.id.0.to_string()
+    });
+
+    let first = warp::test::request().reply(&filter).await;
+    let second = warp::test::request().reply(&filter).await;
+    assert_eq!(first.body(), "0");
+    assert_eq!(second.body(), "1");
+}
+
+#[tokio::test]
+async fn test_inject_and_provider() {
+    let sp = Arc::new(ServiceProvider::new().add_singleton::<Arc<Counter>>());
+    let filter = inject::<Arc<Counter>, _, _>(sp, |sp| sp.resolve())
+        .and(with_provider(Arc::new(
+            ServiceProvider::new().add_instance(5u64),
+        )))
+        .map(|counter: Arc<Counter>, sp: Arc<ServiceProvider<_, _>>| {
+            let step: &u64 = sp.resolve();
+            counter.0.fetch_add(*step, Ordering::SeqCst).to_string()
+        });
+
+    assert_eq!(warp::test::request().reply(&filter).await.body(), "0");
+    assert_eq!(warp::test::request().reply(&filter).await.body(), "5");
+}
@@ -0,0 +1,49 @@
+//! Smoke test for `wasm32-unknown-unknown`. Run it with
+//! `wasm-pack test --node -- --no-default-features --test wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use std::rc::Rc;
+use teloc::{inject, Resolver, ServiceProvider};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+struct Settings {
+    title: String,
+}
+
+struct Counter {
+    settings: Rc<Settings>,
+}
+#[inject]
+impl Counter {
+    fn new(settings: Rc<Settings>) -> Self {
+        Counter { settings }
+    }
+}
+
+struct Component<'a> {
+    counter: &'a Counter,
+    value: &'a u32,
+}
+#[inject]
+impl<'a> Component<'a> {
+    fn new(counter: &'a Counter, value: &'a u32) -> Self {
+        Component { counter, value }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_resolve_in_wasm() {
+    let sp = ServiceProvider::new()
+        .add_instance(Rc::new(Settings {
+            title: "counter".to_string(),
+        }))
+        .add_singleton::<Counter>()
+        .add_transient::<Component>();
+    let scope = sp.fork().add_instance(5u32);
+
+    let first: Component = scope.resolve();
+    let second: Component = scope.resolve();
+    assert!(std::ptr::eq(first.counter, second.counter));
+    assert_eq!(first.counter.settings.title, "counter");
+    assert_eq!(*first.value, 5);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "watch")]
+
+use futures::executor::block_on;
+use teloc::watch::Watch;
+use teloc::{inject, Resolver, ServiceProvider};
+use tokio::sync::watch::Sender;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Config {
+    level: &'static str,
+}
+
+struct Logger {
+    config: Watch<Config>,
+}
+#[inject]
+impl Logger {
+    fn new(config: Watch<Config>) -> Self {
+        Logger { config }
+    }
+}
+
+#[test]
+fn test_reads_latest_value() {
+    let sp = ServiceProvider::new()
+        .add_watch(Config { level: "info" })
+        .add_transient::<Logger>();
+
+    let logger: Logger = sp.resolve();
+    let sender: &Sender<Config> = sp.resolve();
+    sender.send_replace(Config { level: "debug" });
+
+    assert_eq!(logger.config.borrow().level, "debug");
+}
+
+#[test]
+fn test_subscriptions_are_independent() {
+    let sp = ServiceProvider::new().add_watch(Config { level: "info" });
+    let mut first: Watch<Config> = sp.resolve();
+    let mut second: Watch<Config> = sp.resolve();
+
+    let sender: &Sender<Config> = sp.resolve();
+    sender.send_replace(Config { level: "warn" });
+
+    block_on(first.changed()).unwrap();
+    assert_eq!(first.borrow_and_update().level, "warn");
+    block_on(second.changed()).unwrap();
+    assert_eq!(second.borrow().level, "warn");
+}
+
+#[test]
+fn test_changed_fails_when_provider_dropped() {
+    let sp = ServiceProvider::new().add_watch(Config { level: "info" });
+    let mut config: Watch<Config> = sp.resolve();
+    drop(sp);
+
+    assert!(block_on(config.changed()).is_err());
+    assert_eq!(*config.borrow(), Config { level: "info" });
+}
+
+#[test]
+fn test_resolve_from_fork() {
+    let sp = ServiceProvider::new().add_watch(Config { level: "info" });
+    let scope = sp.fork().add_transient::<Logger>();
+
+    let logger: Logger = scope.resolve();
+    assert_eq!(logger.config.borrow().level, "info");
+}
@@ -0,0 +1,50 @@
+use crate::common::compile_error;
+use crate::derive_teloc;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{DataStruct, Fields, Generics};
+
+pub fn derive(
+    ds: &DataStruct,
+    ident: Ident,
+    generics: &Generics,
+) -> Result<TokenStream, TokenStream> {
+    if !generics.params.is_empty() {
+        return Err(compile_error("`AppState` does not support generic structs"));
+    }
+    let fields = match &ds.fields {
+        Fields::Named(named) => &named.named,
+        _ => {
+            return Err(compile_error(
+                "`AppState` supports only structs with named fields",
+            ))
+        }
+    };
+
+    let dependency = derive_teloc::derive(ds, &[], ident.clone(), generics)?;
+    let field_ty = fields.iter().map(|f| &f.ty);
+    let field_ty2 = fields.iter().map(|f| &f.ty);
+    let field = fields.iter().map(|f| &f.ident);
+
+    Ok(quote! {
+        #dependency
+
+        #(
+        impl teloc::reexport::axum::extract::FromRef<#ident> for #field_ty {
+            fn from_ref(state: &#ident) -> Self {
+                Clone::clone(&state.#field)
+            }
+        }
+        )*
+
+        impl #ident {
+            /// Resolve all fields of the state from the `ServiceProvider`.
+            pub fn from_provider<'a, SP, Infer>(sp: &'a SP) -> Self
+            where
+                SP: teloc::GetDependencies<'a, teloc::reexport::HList![#(#field_ty2),*], Infer>,
+            {
+                teloc::Dependency::init(sp.get_deps())
+            }
+        }
+    })
+}
@@ -14,7 +14,7 @@ pub fn compile_error<T: ToTokens>(data: T) -> proc_macro2::TokenStream {
 pub fn get_1_teloc_attr(attrs: &[Attribute]) -> Result<Option<&Attribute>, TokenStream> {
     let mut teloc_attrs = vec![];
     attrs.iter().for_each(|attr| {
-        if attr.path.is_ident("init") {
+        if attr.path.is_ident("init") || attr.path.is_ident("config") {
             teloc_attrs.push(attr);
         }
     });
@@ -22,7 +22,7 @@ pub fn get_1_teloc_attr(attrs: &[Attribute]) -> Result<Option<&Attribute>, Token
         [] => Ok(None),
         [x] => Ok(Some(x)),
         _ => Err(compile_error(format!(
-            "Expected 0 or 1 `clone` or `init` attribute, found {}",
+            "Expected 0 or 1 `init` or `config` attribute, found {}",
             teloc_attrs.len()
         ))),
     }
@@ -0,0 +1,177 @@
+use crate::common::compile_error;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    Attribute, FnArg, GenericArgument, ImplItem, Item, Meta, NestedMeta, PathArguments, ReturnType,
+    Type,
+};
+
+pub struct CyclesInput {
+    items: Vec<Item>,
+}
+
+impl Parse for CyclesInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items = vec![];
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(CyclesInput { items })
+    }
+}
+
+impl ToTokens for CyclesInput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.items.iter().for_each(|item| item.to_tokens(tokens))
+    }
+}
+
+/// Name of a service with its dependencies, in the order of declaration.
+type Graph = BTreeMap<String, Vec<String>>;
+
+pub fn expand(input: &CyclesInput) -> Result<TokenStream, TokenStream> {
+    let mut graph = Graph::new();
+    for item in &input.items {
+        if let Some((name, deps)) = service_of(item) {
+            graph.insert(
+                name,
+                deps.iter().filter_map(|ty| service_name(ty)).collect(),
+            );
+        }
+    }
+
+    let mut visited = vec![];
+    for name in graph.keys() {
+        let mut path = vec![];
+        if let Some(cycle) = find_cycle(&graph, name, &mut path, &mut visited) {
+            return Err(compile_error(format!(
+                "Dependency cycle detected: {}. Break it by taking `Deferred<T>` \
+                 instead of one of the services.",
+                cycle.join(" -> ")
+            )));
+        }
+    }
+    Ok(TokenStream::new())
+}
+
+fn find_cycle(
+    graph: &Graph,
+    name: &str,
+    path: &mut Vec<String>,
+    visited: &mut Vec<String>,
+) -> Option<Vec<String>> {
+    if let Some(start) = path.iter().position(|n| n == name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(name.to_string());
+        return Some(cycle);
+    }
+    if visited.iter().any(|n| n == name) {
+        return None;
+    }
+    let deps = graph.get(name)?;
+    path.push(name.to_string());
+    for dep in deps {
+        if let Some(cycle) = find_cycle(graph, dep, path, visited) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    visited.push(name.to_string());
+    None
+}
+
+/// Returns the name of the service declared by the item and types of its dependencies.
+fn service_of(item: &Item) -> Option<(String, Vec<&Type>)> {
+    match item {
+        Item::Struct(s) if derives_dependency(&s.attrs) => {
+            let deps = s
+                .fields
+                .iter()
+                .filter(|field| !field.attrs.iter().any(|attr| attr.path.is_ident("init")))
+                .map(|field| &field.ty)
+                .collect();
+            Some((s.ident.to_string(), deps))
+        }
+        Item::Impl(imp) if has_inject(&imp.attrs) => {
+            let method = imp.items.iter().find_map(|item| match item {
+                ImplItem::Method(method) => Some(method),
+                _ => None,
+            })?;
+            let deps = method.sig.inputs.iter().filter_map(typed_arg).collect();
+            Some((service_name(&imp.self_ty)?, deps))
+        }
+        Item::Fn(f) if has_inject(&f.attrs) => {
+            let ty = match &f.sig.output {
+                ReturnType::Type(_, ty) => ty,
+                ReturnType::Default => return None,
+            };
+            let deps = f.sig.inputs.iter().filter_map(typed_arg).collect();
+            Some((service_name(ty)?, deps))
+        }
+        _ => None,
+    }
+}
+
+fn typed_arg(arg: &FnArg) -> Option<&Type> {
+    match arg {
+        FnArg::Typed(pat) => Some(pat.ty.as_ref()),
+        FnArg::Receiver(_) => None,
+    }
+}
+
+fn derives_dependency(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Dependency"),
+                _ => false,
+            }),
+            _ => false,
+        })
+}
+
+fn has_inject(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "inject")
+    })
+}
+
+/// Name of the service that is resolved for a dependency of type `ty`. References, smart pointers
+/// and `Provider` are looked through, because they are resolved from the same service. `Deferred`
+/// does not resolve the service, so it does not form a cycle.
+fn service_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Reference(reference) => service_name(&reference.elem),
+        Type::Paren(paren) => service_name(&paren.elem),
+        Type::Group(group) => service_name(&group.elem),
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let ident = segment.ident.to_string();
+            match ident.as_str() {
+                "Deferred" => None,
+                "Rc" | "Arc" | "Box" | "Provider" => match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => {
+                        args.args.iter().find_map(|arg| match arg {
+                            GenericArgument::Type(ty) => service_name(ty),
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                },
+                _ => Some(ident),
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,24 @@
+use crate::generics::{get_impl_block_generics, get_struct_block_generics};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::Generics;
+
+pub fn derive(ident: Ident, generics: &Generics) -> TokenStream {
+    let impl_block_generics = get_impl_block_generics(generics);
+    let struct_block_generics = get_struct_block_generics(generics);
+    let predicates = generics
+        .where_clause
+        .as_ref()
+        .map(|clause| clause.predicates.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    // `Clone` may be derived with bounds on the generics, so require it for the whole type.
+    quote! {
+        impl #impl_block_generics teloc::DependencyClone for #ident #struct_block_generics
+        where
+            Self: Clone,
+            #(#predicates,)*
+        {
+        }
+    }
+}
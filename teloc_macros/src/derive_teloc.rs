@@ -1,13 +1,37 @@
-use crate::common::{compile_error, get_1_teloc_attr};
+use crate::common::{compile_error, get_1_teloc_attr, ident_generator};
 use crate::generics::{get_impl_block_generics, get_struct_block_generics, get_where_clause};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::parse::{Parse, ParseBuffer};
 use syn::punctuated::Punctuated;
 use syn::Token;
-use syn::{DataStruct, Expr, Field, Fields, Generics, Type};
+use syn::{
+    parse_quote, Attribute, DataStruct, Expr, Field, Fields, GenericParam, Generics, LifetimeDef,
+    Lit, LitStr, Meta, NestedMeta, Type,
+};
 
 pub fn derive(
+    ds: &DataStruct,
+    attrs: &[Attribute],
+    ident: Ident,
+    generics: &Generics,
+) -> Result<TokenStream, TokenStream> {
+    let dependency_attr = parse_dependency_attr(attrs)?;
+    let auto_lifetime = match &dependency_attr.lifetime {
+        Some(lifetime) => derive_auto_lifetime(lifetime, &ident, generics)?,
+        None => TokenStream::new(),
+    };
+    let dependency = match dependency_attr.ctor {
+        Some(ctor) => derive_with_ctor(ds, ctor, ident, generics)?,
+        None => derive_with_fields(ds, ident, generics)?,
+    };
+    Ok(quote! {
+        #dependency
+        #auto_lifetime
+    })
+}
+
+fn derive_with_fields(
     ds: &DataStruct,
     ident: Ident,
     generics: &Generics,
@@ -15,20 +39,53 @@ pub fn derive(
     let TelocStruct {
         initable,
         injectable,
+        configurable,
     } = parse_teloc_struct(ds)?;
 
-    let impl_block_generics = get_impl_block_generics(generics);
+    // Fields with `#[config]` are read from the `ConfigTree`, that is resolved as the last
+    // dependency with its own lifetime.
+    let mut impl_generics = generics.clone();
+    let mut config_dep = None;
+    if !configurable.is_empty() {
+        let lifetime: LifetimeDef = parse_quote!('__teloc_config);
+        impl_generics
+            .params
+            .insert(0, GenericParam::Lifetime(lifetime));
+        config_dep = Some(quote! { &'__teloc_config teloc::config::ConfigTree });
+    }
+
+    let impl_block_generics = get_impl_block_generics(&impl_generics);
     let struct_block_generics = get_struct_block_generics(generics);
     let where_clause = get_where_clause(generics);
 
-    let init_field = initable.iter().map(|f| &f.field);
+    let init_field = initable.iter().map(|f| f.field).collect::<Vec<_>>();
     let init_field_ty = initable.iter().map(|f| &f.field_ty);
     let init_field_exprs = initable.iter().map(|f| &f.args);
 
-    let ty_dep = injectable.iter().map(|f| f.field_ty);
-    let ty_dep2 = injectable.iter().map(|f| f.field_ty);
+    let config_field = configurable.iter().map(|f| f.field).collect::<Vec<_>>();
+    let config_field_ty = configurable.iter().map(|f| f.field_ty);
+    let config_key = configurable.iter().map(|f| &f.key);
 
-    let mut destructure = quote! { teloc::reexport::frunk::HNil };
+    let ty_dep = injectable
+        .iter()
+        .map(|f| {
+            let ty = f.field_ty;
+            quote!(#ty)
+        })
+        .chain(config_dep)
+        .collect::<Vec<_>>();
+    let ty_dep2 = &ty_dep;
+
+    let mut destructure = if configurable.is_empty() {
+        quote! { teloc::reexport::frunk::HNil }
+    } else {
+        quote! {
+            teloc::reexport::frunk::HCons {
+                head: __teloc_config,
+                tail: teloc::reexport::frunk::HNil
+            }
+        }
+    };
     injectable.iter().map(|f| f.field).rev().for_each(|id| {
         destructure = quote! {
             teloc::reexport::frunk::HCons {
@@ -46,9 +103,21 @@ pub fn derive(
         {
             fn init(deps: teloc::reexport::HList![#(#ty_dep2),*]) -> Self {
                 let #destructure = deps;
+                // Fields are bound before `Self` is created, so `#[init]` expressions can use
+                // resolved fields, `#[config]` fields and `#[init]` fields declared above.
+                #(
+                    let #config_field: #config_field_ty =
+                        teloc::config::field(__teloc_config, #config_key);
+                )*
+                #(
+                    let #init_field = <#init_field_ty>::init(#init_field_exprs);
+                )*
                 Self {
                     #(
-                        #init_field : <#init_field_ty>::init(#init_field_exprs),
+                        #config_field,
+                    )*
+                    #(
+                        #init_field,
                     )*
                     #(
                         #names,
@@ -59,6 +128,72 @@ pub fn derive(
     })
 }
 
+fn derive_with_ctor(
+    ds: &DataStruct,
+    ctor: Ident,
+    ident: Ident,
+    generics: &Generics,
+) -> Result<TokenStream, TokenStream> {
+    let fields = get_fields(ds);
+    if fields
+        .iter()
+        .any(|field| get_1_teloc_attr(field.attrs.as_slice()).map_or(true, |a| a.is_some()))
+    {
+        return Err(compile_error(
+            "`#[init]` and `#[config]` attributes cannot be used together with `#[dependency(ctor = ...)]`",
+        ));
+    }
+
+    let impl_block_generics = get_impl_block_generics(generics);
+    let struct_block_generics = get_struct_block_generics(generics);
+    let where_clause = get_where_clause(generics);
+
+    // Derive macro cannot see the signature of the constructor, so arguments are the types of the
+    // fields in declaration order.
+    let ty_dep = fields.iter().map(|f| &f.ty);
+    let ty_dep2 = fields.iter().map(|f| &f.ty);
+    let names = ident_generator(fields.len());
+
+    Ok(quote! {
+        impl #impl_block_generics
+            teloc::Dependency<teloc::reexport::HList![#(#ty_dep),*]>
+        for #ident #struct_block_generics #where_clause
+        {
+            fn init(deps: teloc::reexport::HList![#(#ty_dep2),*]) -> Self {
+                let teloc::reexport::frunk::hlist_pat![#(#names),*] = deps;
+                Self::#ctor(#(#names),*)
+            }
+        }
+    })
+}
+
+fn derive_auto_lifetime(
+    lifetime: &LitStr,
+    ident: &Ident,
+    generics: &Generics,
+) -> Result<TokenStream, TokenStream> {
+    let impl_block_generics = get_impl_block_generics(generics);
+    let struct_block_generics = get_struct_block_generics(generics);
+    let where_clause = get_where_clause(generics);
+
+    let container = match lifetime.value().as_str() {
+        "transient" => quote! { teloc::TransientContainer<Self> },
+        "singleton" => quote! { teloc::SingletonContainer<Self> },
+        other => {
+            return Err(compile_error(format!(
+                "Unknown lifetime `{}`, expected `transient` or `singleton`",
+                other
+            )))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_block_generics teloc::AutoLifetime for #ident #struct_block_generics #where_clause {
+            type Container = #container;
+        }
+    })
+}
+
 pub fn derive_on_unit(ident: Ident, generics: &Generics) -> Result<TokenStream, TokenStream> {
     let impl_block_generics = get_impl_block_generics(generics);
     let struct_block_generics = get_struct_block_generics(generics);
@@ -76,10 +211,11 @@ pub fn derive_on_unit(ident: Ident, generics: &Generics) -> Result<TokenStream,
     })
 }
 
-fn parse_teloc_struct(ds: &DataStruct) -> Result<TelocStruct, TokenStream> {
+fn parse_teloc_struct(ds: &DataStruct) -> Result<TelocStruct<'_>, TokenStream> {
     let fields = get_fields(ds);
     let mut initable = vec![];
     let mut injectable = vec![];
+    let mut configurable = vec![];
     for field in fields {
         match get_1_teloc_attr(field.attrs.as_slice())? {
             Some(attr) => {
@@ -95,6 +231,16 @@ fn parse_teloc_struct(ds: &DataStruct) -> Result<TelocStruct, TokenStream> {
                             field: field.ident.as_ref().unwrap(), // TODO: unnamed fields
                         })
                     }
+                    "config" => {
+                        let key = attr
+                            .parse_args::<LitStr>()
+                            .map_err(|e| compile_error(e.to_compile_error()))?;
+                        configurable.push(ConfigField {
+                            key,
+                            field_ty: &field.ty,
+                            field: field.ident.as_ref().unwrap(), // TODO: unnamed fields
+                        })
+                    }
                     _ => unreachable!(),
                 }
             }
@@ -109,9 +255,51 @@ fn parse_teloc_struct(ds: &DataStruct) -> Result<TelocStruct, TokenStream> {
     Ok(TelocStruct {
         initable,
         injectable,
+        configurable,
     })
 }
 
+fn parse_dependency_attr(attrs: &[Attribute]) -> Result<DependencyAttr, TokenStream> {
+    let mut res = DependencyAttr {
+        ctor: None,
+        lifetime: None,
+    };
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("dependency")) {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            Ok(_) => {
+                return Err(compile_error(
+                    "Expected `#[dependency(key = \"value\", ...)]`",
+                ))
+            }
+            Err(e) => return Err(compile_error(e.to_compile_error())),
+        };
+        for nested in list.nested {
+            let (key, value) = match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) => match nv.lit {
+                    Lit::Str(value) => (nv.path, value),
+                    _ => return Err(compile_error("Expected string literal as a value")),
+                },
+                _ => return Err(compile_error("Expected `key = \"value\"`")),
+            };
+            if key.is_ident("ctor") {
+                let ctor = value
+                    .parse::<Ident>()
+                    .map_err(|e| compile_error(e.to_compile_error()))?;
+                res.ctor = Some(ctor);
+            } else if key.is_ident("lifetime") {
+                res.lifetime = Some(value);
+            } else {
+                return Err(compile_error(format!(
+                    "Unknown key `{}` in `#[dependency]` attribute",
+                    quote!(#key)
+                )));
+            }
+        }
+    }
+    Ok(res)
+}
+
 fn get_fields(ds: &DataStruct) -> Vec<&Field> {
     match &ds.fields {
         Fields::Named(named) => named.named.iter().collect(),
@@ -131,9 +319,15 @@ impl Parse for TelocAttr {
     }
 }
 
+struct DependencyAttr {
+    ctor: Option<Ident>,
+    lifetime: Option<LitStr>,
+}
+
 struct TelocStruct<'a> {
     initable: Vec<InitableField<'a>>,
     injectable: Vec<InjectableField<'a>>,
+    configurable: Vec<ConfigField<'a>>,
 }
 
 struct InitableField<'a> {
@@ -145,3 +339,8 @@ struct InjectableField<'a> {
     field_ty: &'a Type,
     field: &'a Ident,
 }
+struct ConfigField<'a> {
+    key: LitStr,
+    field_ty: &'a Type,
+    field: &'a Ident,
+}
@@ -0,0 +1,103 @@
+use crate::common::compile_error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, FnArg, Ident, ItemFn, Pat, Token, Type};
+
+/// Arguments of entry point attributes: `provider = path::to::builder`, followed by arguments
+/// that are passed to the `tokio` attribute of `async fn`, like `flavor = "current_thread"`.
+pub struct EntryArgs {
+    provider: Expr,
+    runtime: Vec<TokenStream>,
+}
+
+impl Parse for EntryArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut provider = None;
+        let mut runtime = vec![];
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Expr = input.parse()?;
+            if key == "provider" {
+                provider = Some(value);
+            } else {
+                runtime.push(quote! { #key = #value });
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let provider =
+            provider.ok_or_else(|| input.error("Expected `provider = function` argument"))?;
+        Ok(EntryArgs { provider, runtime })
+    }
+}
+
+/// Wrap the body of the function, so its arguments are resolved from the provider built by
+/// `args.provider`. The function takes no arguments after the expansion.
+fn resolve_args(args: &EntryArgs, input: &mut ItemFn) -> Result<(), TokenStream> {
+    let (pats, tys) = input
+        .sig
+        .inputs
+        .iter()
+        .map(|inp| match inp {
+            FnArg::Receiver(_) => Err(compile_error("Function must not give self as arg!")),
+            FnArg::Typed(pat) => Ok((pat.pat.as_ref(), pat.ty.as_ref())),
+        })
+        .collect::<Result<(Vec<&Pat>, Vec<&Type>), _>>()?;
+
+    let provider = &args.provider;
+    let block = &input.block;
+    let body = quote! {{
+        let __teloc_sp = #provider();
+        #(let #pats: #tys = teloc::Resolver::resolve(&__teloc_sp);)*
+        #block
+    }};
+    *input.block = syn::parse2(body).map_err(|e| e.to_compile_error())?;
+    input.sig.inputs.clear();
+    Ok(())
+}
+
+/// Attribute that runs the function: `#[tokio::#attr]` for `async fn`, `sync` for others.
+fn runner(
+    args: &EntryArgs,
+    input: &ItemFn,
+    attr: &str,
+    sync: TokenStream,
+) -> Result<TokenStream, TokenStream> {
+    let runtime = &args.runtime;
+    if input.sig.asyncness.is_some() {
+        let attr = Ident::new(attr, proc_macro2::Span::call_site());
+        Ok(quote! { #[::tokio::#attr(#(#runtime),*)] })
+    } else if runtime.is_empty() {
+        Ok(sync)
+    } else {
+        Err(compile_error(
+            "Only `provider` argument is expected for a function that is not async",
+        ))
+    }
+}
+
+pub fn expand_test(args: &EntryArgs, mut input: ItemFn) -> Result<TokenStream, TokenStream> {
+    let test = runner(
+        args,
+        &input,
+        "test",
+        quote! { #[::core::prelude::v1::test] },
+    )?;
+    resolve_args(args, &mut input)?;
+    Ok(quote! {
+        #test
+        #input
+    })
+}
+
+pub fn expand_main(args: &EntryArgs, mut input: ItemFn) -> Result<TokenStream, TokenStream> {
+    let main = runner(args, &input, "main", quote! {})?;
+    resolve_args(args, &mut input)?;
+    Ok(quote! {
+        #main
+        #input
+    })
+}
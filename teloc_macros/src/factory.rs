@@ -0,0 +1,31 @@
+use crate::common::compile_error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemFn, ReturnType};
+
+/// Rewrite the function, so it takes all arguments as one tuple, like factory closures do.
+pub fn expand(mut input: ItemFn) -> Result<TokenStream, TokenStream> {
+    if let ReturnType::Default = input.sig.output {
+        return Err(compile_error(
+            "Expected return type, found default return type",
+        ));
+    }
+    if input.sig.asyncness.is_some() {
+        return Err(compile_error("Factory function must not be async!"));
+    }
+
+    let (pats, tys) = input
+        .sig
+        .inputs
+        .iter()
+        .map(|inp| match inp {
+            FnArg::Receiver(_) => Err(compile_error("Function must not give self as arg!")),
+            FnArg::Typed(pat) => Ok((pat.pat.as_ref(), pat.ty.as_ref())),
+        })
+        .collect::<Result<(Vec<_>, Vec<_>), _>>()?;
+
+    let arg: FnArg = syn::parse_quote! { (#(#pats,)*): (#(#tys,)*) };
+    input.sig.inputs = std::iter::once(arg).collect();
+
+    Ok(quote! { #input })
+}
@@ -0,0 +1,89 @@
+use crate::common::{compile_error, ident_generator};
+use crate::generics::get_struct_block_generics;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{DataStruct, Fields, GenericArgument, Generics, Member, PathArguments, Type};
+
+pub fn derive(
+    ds: &DataStruct,
+    ident: Ident,
+    generics: &Generics,
+) -> Result<TokenStream, TokenStream> {
+    if let Fields::Unit = ds.fields {
+        return Err(compile_error(
+            "`InjectFields` requires a struct with fields",
+        ));
+    }
+    let params = generics.params.iter();
+    let struct_block_generics = get_struct_block_generics(generics);
+    let predicates = generics
+        .where_clause
+        .as_ref()
+        .map(|clause| clause.predicates.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let injected: Vec<_> = ds
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.attrs.iter().any(|attr| attr.path.is_ident("injected")))
+        .collect();
+    let names = ident_generator(injected.len());
+    let mut field_ty = vec![];
+    let mut assign = vec![];
+    for ((i, field), name) in injected.into_iter().zip(&names) {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        match option_inner(&field.ty) {
+            Some(ty) => {
+                field_ty.push(ty);
+                assign.push(quote! { self.#member = Some(#name); });
+            }
+            None => {
+                field_ty.push(&field.ty);
+                assign.push(quote! { self.#member = #name; });
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl<'__teloc, #(#params,)* __TelocSP, __TelocInfer>
+            teloc::InjectFields<'__teloc, __TelocSP, __TelocInfer>
+        for #ident #struct_block_generics
+        where
+            __TelocSP: teloc::GetDependencies<
+                '__teloc,
+                teloc::reexport::HList![#(#field_ty),*],
+                __TelocInfer,
+            >,
+            #(#predicates,)*
+        {
+            fn inject_fields(&mut self, sp: &'__teloc __TelocSP) {
+                let teloc::reexport::frunk::hlist_pat![#(#names),*] =
+                    teloc::GetDependencies::get_deps(sp);
+                #(#assign)*
+            }
+        }
+    })
+}
+
+/// Returns `T` if the type is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(ty)) if args.args.len() == 1 => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
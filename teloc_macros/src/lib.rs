@@ -1,8 +1,17 @@
+mod app_state;
 mod common;
+mod cycles;
+mod dependency_clone;
 mod derive_teloc;
+mod entry;
+mod factory;
 mod generics;
 mod inject;
+mod inject_fields;
 mod parse;
+mod resolvable;
+mod scaffold;
+mod service;
 
 extern crate proc_macro;
 extern crate quote;
@@ -20,7 +29,9 @@ use syn::{parse_macro_input, DeriveInput};
 ///
 /// By default macro define all fields as dependencies, but you can initialize field by yourself
 /// using attribute `#[init]`. In curly braces you must define a parameters, that will be passed
-/// to calling `FieldType::init` method.
+/// to calling `FieldType::init` method. Parameters can use other fields by their names: fields
+/// resolved from the `ServiceProvider` and fields with `#[init]` declared above, for example
+/// `#[init(config.timeout)]`.
 ///
 /// Example:
 /// ```compile_fail
@@ -31,28 +42,168 @@ use syn::{parse_macro_input, DeriveInput};
 ///     fn init(number: u8) -> Self { Number(number) }
 /// }
 ///
-/// #[derive(Teloc)]
+/// #[derive(Dependency)]
 /// struct Foo {
 ///     #[init(5)]
 ///     a: Number
 /// }
 ///
-/// #[derive(Teloc)]
+/// #[derive(Dependency)]
 /// struct Bar {
 ///     foo: Foo,
 /// }
 /// ```
-#[proc_macro_derive(Dependency, attributes(init))]
+///
+/// With the `config` feature of `teloc`, a field can be read from the registered
+/// `teloc::config::ConfigTree` by a key path: `#[config("server.port")] port: u16`. The value is
+/// deserialized when the service is created, and a missing or invalid value panics with the key
+/// in the message.
+///
+/// Types with private fields often have a constructor instead. Use `#[dependency(ctor = "new")]`
+/// to create the value by calling it. The derive macro cannot see the signature of the
+/// constructor, so it must take the types of the fields in the order of their declaration. For
+/// other constructors use `#[inject]` on the `impl` block.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::Dependency;
+///
+/// #[derive(Dependency)]
+/// #[dependency(ctor = "new")]
+/// pub struct Timeout(u64);
+/// impl Timeout {
+///     fn new(secs: u64) -> Self { Timeout(secs.max(1)) }
+/// }
+/// ```
+///
+/// The lifetime of the service can be declared next to the type with
+/// `#[dependency(lifetime = "transient")]` or `#[dependency(lifetime = "singleton")]`. Then the
+/// macro also implements `AutoLifetime` trait, and the service can be registered with
+/// `ServiceProvider::add_auto`.
+#[proc_macro_derive(Dependency, attributes(init, config, dependency))]
 pub fn derive_teloc(tokens: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as DeriveInput);
     let res = match input.data {
-        Data::Struct(ds) => derive_teloc::derive(&ds, input.ident, &input.generics),
+        Data::Struct(ds) => derive_teloc::derive(&ds, &input.attrs, input.ident, &input.generics),
         Data::Enum(_) => return compile_error("Expected struct, found enum").into(),
         Data::Union(_) => derive_teloc::derive_on_unit(input.ident, &input.generics),
     };
     res.unwrap_or_else(identity).into()
 }
 
+/// Derive macro implements `DependencyClone` marker trait, so the service can be resolved by
+/// cloning from singletons and instances. The type must implement `Clone`. Derive it only for
+/// cheap handles to shared state (like a struct with `Arc` fields or references) or small `Copy`
+/// values.
+///
+/// Example:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// #[derive(Clone, DependencyClone)]
+/// struct Handle<'a> {
+///     config: &'a Config,
+///     pool: Arc<Pool>,
+/// }
+/// ```
+#[proc_macro_derive(DependencyClone)]
+pub fn derive_dependency_clone(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    dependency_clone::derive(input.ident, &input.generics).into()
+}
+
+/// Derive macro implements `Resolver` for a struct whose every field can be resolved from the
+/// `ServiceProvider`, so the whole struct is resolved in one call. The struct is not registered in
+/// the provider: fields are resolved directly, so they can borrow services from the provider. It is
+/// useful to pass a typed set of services instead of the whole provider.
+///
+/// Example:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// #[derive(Resolvable)]
+/// struct AppServices<'a> {
+///     config: &'a Config,
+///     repository: Arc<Repository>,
+/// }
+///
+/// let app: AppServices = sp.resolve();
+/// ```
+#[proc_macro_derive(Resolvable)]
+pub fn derive_resolvable(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    let res = match input.data {
+        Data::Struct(ds) => resolvable::derive(&ds, input.ident, &input.generics),
+        _ => return compile_error("Expected struct").into(),
+    };
+    res.unwrap_or_else(identity).into()
+}
+
+/// Derive macro implements `InjectFields` for a struct, so fields marked with `#[injected]`
+/// attribute can be filled from the `ServiceProvider` using `ServiceProvider::inject`. It is useful
+/// for values that cannot be created by the container: deserialized structs, objects owned by FFI,
+/// etc. Field of type `Option<T>` is set to `Some` with resolved `T`, other fields are overwritten.
+///
+/// Example:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::*;
+///
+/// #[derive(Deserialize, InjectFields)]
+/// struct Command {
+///     name: String,
+///     #[serde(skip)]
+///     #[injected]
+///     repository: Option<Arc<Repository>>,
+/// }
+///
+/// let mut command: Command = serde_json::from_str(json)?;
+/// sp.inject(&mut command);
+/// ```
+#[proc_macro_derive(InjectFields, attributes(injected))]
+pub fn derive_inject_fields(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    let res = match input.data {
+        Data::Struct(ds) => inject_fields::derive(&ds, input.ident, &input.generics),
+        _ => return compile_error("Expected struct").into(),
+    };
+    res.unwrap_or_else(identity).into()
+}
+
+/// Derive macro for an axum `State` struct with **named fields**, built from services of the
+/// `ServiceProvider` (usually `Arc<_>` singletons). It implements `Dependency` like the `Dependency`
+/// derive does (so do not derive both), adds `from_provider` constructor and implements
+/// `axum::extract::FromRef` for every field, so handlers can extract `State<FieldType>` directly.
+/// Because of this, field types must be unique and implement `Clone`.
+///
+/// Requires `axum-support` feature of teloc.
+///
+/// Example:
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use teloc::axum_support::AppState;
+///
+/// #[derive(Clone, AppState)]
+/// struct State {
+///     repository: Arc<Repository>,
+///     config: Arc<Config>,
+/// }
+///
+/// let state = State::from_provider(&sp);
+/// let app = Router::new().route("/", get(index)).with_state(state);
+/// ```
+#[proc_macro_derive(AppState, attributes(init))]
+pub fn derive_app_state(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+    let res = match input.data {
+        Data::Struct(ds) => app_state::derive(&ds, input.ident, &input.generics),
+        _ => return compile_error("Expected struct").into(),
+    };
+    res.unwrap_or_else(identity).into()
+}
+
 /// Macro can be used on free functions and impls, including impl traits, with *only one* implement
 /// method. It will generate `Dependency` impl in which calling function that will tagged by this
 /// macro.
@@ -83,3 +234,204 @@ pub fn inject(_: TokenStream, input: TokenStream) -> TokenStream {
     let tokens = res.unwrap_or_else(identity);
     (quote::quote! { #imp #tokens }).into()
 }
+
+/// Macro turns a function into a factory that can be registered using `ServiceProvider::add_factory`
+/// (or any other `add_*_factory` method). Arguments of the function are dependencies resolved
+/// from the provider, the return type is the created service. Arguments can borrow from the
+/// provider, so the returned service can have a lifetime.
+///
+/// The function is rewritten to take all arguments as one tuple, so to call it by yourself pass
+/// them as a tuple: `make_label((&text,))`.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// struct Label<'a> { text: &'a str }
+///
+/// #[factory]
+/// fn make_label(text: &String) -> Label<'_> {
+///     Label { text }
+/// }
+///
+/// let sp = ServiceProvider::new()
+///     .add_instance("label".to_string())
+///     .add_factory(make_label);
+/// let label: Label = sp.resolve();
+/// ```
+#[proc_macro_attribute]
+pub fn factory(_: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::ItemFn);
+    factory::expand(input).unwrap_or_else(identity).into()
+}
+
+/// Macro generates boilerplate for a new injected service behind a trait object. It takes a trait
+/// definition followed by `impl ImplementationName;` and generates, next to the trait:
+/// - `From<Box<ImplementationName>> for Box<dyn Trait>`, so the implementation can be bound to
+///   `Box<dyn Trait>`;
+/// - `TraitStub`, a test double whose methods panic unless the trait has a default body;
+/// - `TraitRegistration`, an extension trait for `ServiceProvider` with `add_trait` and
+///   `add_trait_stub` methods.
+///
+/// Generic traits and traits with associated types or consts are not supported.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// teloc::scaffold! {
+///     pub trait Greeter {
+///         fn greet(&self, name: &str) -> String;
+///     }
+///     impl EnglishGreeter;
+/// }
+///
+/// pub struct EnglishGreeter;
+/// #[inject]
+/// impl EnglishGreeter {
+///     fn new() -> Self { EnglishGreeter }
+/// }
+/// impl Greeter for EnglishGreeter {
+///     fn greet(&self, name: &str) -> String { format!("Hello, {}!", name) }
+/// }
+///
+/// let sp = ServiceProvider::new().add_greeter();
+/// let greeter: Box<dyn Greeter> = sp.resolve();
+/// ```
+#[proc_macro]
+pub fn scaffold(input: TokenStream) -> TokenStream {
+    let scaffold = parse_macro_input!(input as scaffold::ScaffoldInput);
+    let res = scaffold::expand(&scaffold);
+    let tokens = res.unwrap_or_else(identity);
+    (quote::quote! { #scaffold #tokens }).into()
+}
+
+/// Macro checks that services declared inside it do not depend on each other in a cycle. Without it
+/// a cycle shows up as an overflow error while evaluating trait bounds when the service is resolved.
+/// Wrap declarations of your services (structs with `#[derive(Dependency)]`, `#[inject]` impls and
+/// functions) into the macro, and it fails the compilation with an error naming the cycle path,
+/// like `Dependency cycle detected: A -> B -> A`. Items are emitted unchanged.
+///
+/// Only dependencies on services declared inside one macro call are checked. References, `Rc`,
+/// `Arc`, `Box` and `Provider` around a service are treated as the service itself. `Deferred<T>`
+/// does not resolve `T`, so it can be used to break a cycle.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// detect_cycles! {
+///     #[derive(Dependency)]
+///     struct Orders {
+///         users: Users,
+///     }
+///
+///     struct Users {
+///         orders: Box<Orders>,
+///     }
+///     #[inject]
+///     impl Users {
+///         fn new(orders: Box<Orders>) -> Self { Users { orders } }
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn detect_cycles(input: TokenStream) -> TokenStream {
+    let items = parse_macro_input!(input as cycles::CyclesInput);
+    let res = cycles::expand(&items);
+    let tokens = res.unwrap_or_else(identity);
+    (quote::quote! { #items #tokens }).into()
+}
+
+/// Attribute registers the service in the global registry of the dynamic layer, so it is added to
+/// `DynResolver::from_inventory` in any crate linked into the binary. It can be used on a struct
+/// or an enum that implements `Dependency` (for example, using `#[derive(Dependency)]`), or on an
+/// `impl` block with `#[inject]`. Lifetime is `transient` by default, use
+/// `#[teloc::service(singleton)]` for singletons. Requires the `inventory-support` feature of
+/// `teloc`.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// #[teloc::service(singleton)]
+/// #[derive(Clone, Dependency, DependencyClone)]
+/// struct MetricsPlugin;
+///
+/// let resolver = teloc::dynamic::DynResolver::from_inventory();
+/// let _: &MetricsPlugin = resolver.get().unwrap();
+/// ```
+#[proc_macro_attribute]
+pub fn service(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let lifetime = parse_macro_input!(attr as Option<syn::Ident>);
+    let item = parse_macro_input!(input as syn::Item);
+    let tokens = service::expand(lifetime, &item).unwrap_or_else(identity);
+    (quote::quote! { #item #tokens }).into()
+}
+
+/// Attribute for test functions, whose arguments are resolved from the provider built by the
+/// function in the `provider` argument, before the body runs. The provider is built for every run
+/// of the test. `async fn` tests run on `#[tokio::test]`, so `tokio` with `macros` and `rt`
+/// features must be a dependency of the crate. Other arguments of the attribute are passed to
+/// `#[tokio::test]`, for example `flavor = "multi_thread"`.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// struct UserRepo;
+/// #[inject]
+/// impl UserRepo {
+///     fn new() -> Self { UserRepo }
+/// }
+///
+/// fn build_test_sp() -> ServiceProvider<EmptyServiceProvider, HCons<SingletonContainer<UserRepo>, HNil>> {
+///     ServiceProvider::new().add_singleton::<UserRepo>()
+/// }
+///
+/// #[teloc::inject_test(provider = build_test_sp)]
+/// fn finds_user(repo: &UserRepo) {
+///     let _ = repo;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn inject_test(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as entry::EntryArgs);
+    let input = parse_macro_input!(input as syn::ItemFn);
+    entry::expand_test(&args, input)
+        .unwrap_or_else(identity)
+        .into()
+}
+
+/// Attribute for `main`, that makes it the composition root of the application: the provider is
+/// built by the function in the `provider` argument, and arguments of `main` are resolved from it.
+/// `async fn main` runs on `#[tokio::main]`, other arguments of the attribute are passed to it,
+/// for example `flavor = "current_thread"`.
+///
+/// Example:
+/// ```compile_fail
+/// use teloc::*;
+///
+/// struct Server { port: u16 }
+/// #[inject]
+/// impl Server {
+///     fn new() -> Self { Server { port: 8080 } }
+/// }
+///
+/// fn build() -> ServiceProvider<EmptyServiceProvider, HCons<TransientContainer<Server>, HNil>> {
+///     ServiceProvider::new().add_transient::<Server>()
+/// }
+///
+/// #[teloc::main(provider = build)]
+/// fn main(server: Server) {
+///     println!("listening on {}", server.port);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as entry::EntryArgs);
+    let input = parse_macro_input!(input as syn::ItemFn);
+    entry::expand_main(&args, input)
+        .unwrap_or_else(identity)
+        .into()
+}
@@ -0,0 +1,54 @@
+use crate::common::ident_generator;
+use crate::generics::get_struct_block_generics;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{DataStruct, Fields, Generics};
+
+pub fn derive(
+    ds: &DataStruct,
+    ident: Ident,
+    generics: &Generics,
+) -> Result<TokenStream, TokenStream> {
+    let params = generics.params.iter();
+    let struct_block_generics = get_struct_block_generics(generics);
+    let predicates = generics
+        .where_clause
+        .as_ref()
+        .map(|clause| clause.predicates.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let field_ty: Vec<_> = ds.fields.iter().map(|f| &f.ty).collect();
+    let names = ident_generator(field_ty.len());
+    let construct = match &ds.fields {
+        Fields::Named(named) => {
+            let field = named.named.iter().map(|f| &f.ident);
+            quote! { #ident { #(#field: #names),* } }
+        }
+        Fields::Unnamed(_) => quote! { #ident(#(#names),*) },
+        Fields::Unit => quote! { #ident },
+    };
+
+    Ok(quote! {
+        impl<'__teloc, #(#params,)* __TelocParent, __TelocConts, __TelocInfer>
+            teloc::Resolver<
+                '__teloc,
+                #ident #struct_block_generics,
+                (teloc::ResolveStruct, __TelocInfer),
+            >
+        for teloc::ServiceProvider<__TelocParent, __TelocConts>
+        where
+            Self: teloc::GetDependencies<
+                '__teloc,
+                teloc::reexport::HList![#(#field_ty),*],
+                __TelocInfer,
+            >,
+            #(#predicates,)*
+        {
+            fn resolve(&'__teloc self) -> #ident #struct_block_generics {
+                let teloc::reexport::frunk::hlist_pat![#(#names),*] =
+                    teloc::GetDependencies::get_deps(self);
+                #construct
+            }
+        }
+    })
+}
@@ -0,0 +1,165 @@
+use crate::common::compile_error;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{ItemTrait, Token, TraitItem};
+
+pub struct ScaffoldInput {
+    item_trait: ItemTrait,
+    implementation: Ident,
+}
+
+impl Parse for ScaffoldInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let item_trait = input.parse()?;
+        input.parse::<Token![impl]>()?;
+        let implementation = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(ScaffoldInput {
+            item_trait,
+            implementation,
+        })
+    }
+}
+
+impl ToTokens for ScaffoldInput {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.item_trait.to_tokens(tokens)
+    }
+}
+
+pub fn expand(input: &ScaffoldInput) -> Result<TokenStream, TokenStream> {
+    let ItemTrait {
+        vis,
+        ident: trait_ident,
+        generics,
+        items,
+        ..
+    } = &input.item_trait;
+    let implementation = &input.implementation;
+
+    if !generics.params.is_empty() || generics.where_clause.is_some() {
+        return Err(compile_error("`scaffold!` does not support generic traits"));
+    }
+
+    let stub_methods = items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Method(method) if method.default.is_none() => {
+                let sig = &method.sig;
+                let message = format!(
+                    "`{}::{}` is not implemented by the stub",
+                    trait_ident, sig.ident
+                );
+                Some(Ok(quote! { #sig { unimplemented!(#message) } }))
+            }
+            TraitItem::Method(_) => None,
+            _ => Some(Err(compile_error(
+                "`scaffold!` supports only methods in the trait",
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let stub = format_ident!("{}Stub", trait_ident);
+    let registration = format_ident!("{}Registration", trait_ident);
+    let snake = to_snake_case(&trait_ident.to_string());
+    let add = format_ident!("add_{}", snake);
+    let add_stub = format_ident!("add_{}_stub", snake);
+
+    let stub_doc = format!(
+        "Stub test double of `{}`. Every method panics unless the trait provides a default body.",
+        trait_ident
+    );
+    let registration_doc = format!(
+        "Registers `Box<dyn {}>` in a `ServiceProvider` as a `Transient`.",
+        trait_ident
+    );
+    let add_doc = format!(
+        "Add `{}` as the implementation of `Box<dyn {}>`.",
+        implementation, trait_ident
+    );
+    let add_stub_doc = format!(
+        "Add `{}` as the implementation of `Box<dyn {}>`.",
+        stub, trait_ident
+    );
+
+    let binding = |ty: &Ident| {
+        quote! {
+            teloc::ServiceProvider<
+                Parent,
+                teloc::reexport::frunk::HCons<
+                    teloc::ConvertContainer<
+                        teloc::TransientContainer<Box<#ty>>,
+                        Box<#ty>,
+                        Box<dyn #trait_ident>,
+                    >,
+                    Conts,
+                >,
+            >
+        }
+    };
+    let implementation_binding = binding(implementation);
+    let stub_binding = binding(&stub);
+
+    Ok(quote! {
+        impl From<Box<#implementation>> for Box<dyn #trait_ident> {
+            fn from(x: Box<#implementation>) -> Self {
+                x
+            }
+        }
+
+        #[doc = #stub_doc]
+        #vis struct #stub;
+
+        impl teloc::Dependency<teloc::reexport::frunk::HNil> for #stub {
+            fn init(_: teloc::reexport::frunk::HNil) -> Self {
+                #stub
+            }
+        }
+
+        #[allow(unused_variables)]
+        impl #trait_ident for #stub {
+            #(#stub_methods)*
+        }
+
+        impl From<Box<#stub>> for Box<dyn #trait_ident> {
+            fn from(x: Box<#stub>) -> Self {
+                x
+            }
+        }
+
+        #[doc = #registration_doc]
+        #vis trait #registration<Parent, Conts> {
+            #[doc = #add_doc]
+            fn #add(self) -> #implementation_binding;
+            #[doc = #add_stub_doc]
+            fn #add_stub(self) -> #stub_binding;
+        }
+
+        impl<Parent, Conts: teloc::reexport::frunk::hlist::HList> #registration<Parent, Conts>
+            for teloc::ServiceProvider<Parent, Conts>
+        {
+            fn #add(self) -> #implementation_binding {
+                self.add_transient_c::<Box<dyn #trait_ident>, Box<#implementation>>()
+            }
+            fn #add_stub(self) -> #stub_binding {
+                self.add_transient_c::<Box<dyn #trait_ident>, Box<#stub>>()
+            }
+        }
+    })
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(ch.to_lowercase());
+        } else {
+            snake.push(ch);
+        }
+    }
+    snake
+}
@@ -0,0 +1,44 @@
+use crate::common::compile_error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Item, Type};
+
+/// Submit the type to the global registry of `DynResolver::from_inventory`.
+pub fn expand(attr: Option<Ident>, item: &Item) -> Result<TokenStream, TokenStream> {
+    let constructor = match attr {
+        None => quote! { transient },
+        Some(lifetime) if lifetime == "transient" || lifetime == "singleton" => {
+            quote! { #lifetime }
+        }
+        Some(other) => {
+            return Err(compile_error(format!(
+                "Unknown lifetime `{}`, expected `transient` or `singleton`",
+                other
+            )))
+        }
+    };
+
+    let (ty, generics): (Type, &Generics) = match item {
+        Item::Struct(s) => {
+            let ident = &s.ident;
+            (syn::parse_quote! { #ident }, &s.generics)
+        }
+        Item::Enum(e) => {
+            let ident = &e.ident;
+            (syn::parse_quote! { #ident }, &e.generics)
+        }
+        Item::Impl(i) => (i.self_ty.as_ref().clone(), &i.generics),
+        _ => return Err(compile_error("Expected struct, enum or impl block")),
+    };
+    if !generics.params.is_empty() {
+        return Err(compile_error(
+            "Services of the dynamic layer must be `'static` types without generics",
+        ));
+    }
+
+    Ok(quote! {
+        teloc::reexport::inventory::submit! {
+            teloc::dynamic::DynService::#constructor::<#ty, _>()
+        }
+    })
+}